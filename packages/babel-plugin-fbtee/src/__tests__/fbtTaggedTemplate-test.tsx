@@ -0,0 +1,54 @@
+import { describe, expect, it } from '@jest/globals';
+import { transform, withFbtImportStatement } from './FbtTestUtil.tsx';
+
+const withFbsImportStatement = (code: string) => `import { fbs } from "fbtee";
+  ${code}`;
+
+describe('fbt.t`...` tagged templates', () => {
+  it('should compile like the equivalent fbt() call', () => {
+    expect(
+      transform(
+        withFbtImportStatement(
+          "const x = fbt.t('greeting', { project: 'app' })`Hello ${fbt.param('name', user.name)}, you have ${fbt.plural('message', count, { showCount: 'yes' })}`;",
+        ),
+      ),
+    ).toEqual(
+      transform(
+        withFbtImportStatement(
+          "const x = fbt('Hello ' + fbt.param('name', user.name) + ', you have ' + fbt.plural('message', count, { showCount: 'yes' }), 'greeting', { project: 'app' });",
+        ),
+      ),
+    );
+  });
+
+  it('should compile a bare tag like a common string', () => {
+    const options = { fbtCommon: { Done: 'Button label' } };
+    expect(
+      transform(withFbsImportStatement('const x = fbs.t`Done`;'), options),
+    ).toEqual(
+      transform(withFbsImportStatement("const x = fbs.c('Done');"), options),
+    );
+  });
+
+  it('should reject interpolations that are not fbt constructs', () => {
+    expect(() =>
+      transform(
+        withFbtImportStatement("const x = fbt.t('d')`Hello ${name}`;"),
+      ),
+    ).toThrow(
+      "fbt.t`...` interpolations must be fbt constructs or strings. Wrap the Identifier value in fbt.param('name', value).",
+    );
+  });
+
+  it('should reject interpolations in a tag without a description', () => {
+    expect(() =>
+      transform(
+        withFbtImportStatement(
+          "const x = fbt.t`Hello ${fbt.param('name', name)}`;",
+        ),
+      ),
+    ).toThrow(
+      "fbt.t`...` needs a description. Use fbt.t('description')`...`, or add the text to 'fbtCommon'.",
+    );
+  });
+});
@@ -209,7 +209,19 @@ export default class FbtFunctionCallProcessor {
         ? arrayExpression(fbtRuntimeArgs)
         : nullLiteral(),
       objectExpression([
-        objectProperty(identifier('hk'), stringLiteral(fbtHashKey(jsfbt.t))),
+        objectProperty(
+          identifier('hk'),
+          stringLiteral(fbtHashKey(jsfbt.t, this.pluginOptions.hashScheme)),
+        ),
+        ...(this.pluginOptions.hashScheme != null &&
+        this.pluginOptions.hashScheme !== 'jenkins'
+          ? [
+              objectProperty(
+                identifier('hs'),
+                stringLiteral(this.pluginOptions.hashScheme),
+              ),
+            ]
+          : []),
         ...(project != null && project != ''
           ? [objectProperty(identifier('project'), valueToNode(project))]
           : []),
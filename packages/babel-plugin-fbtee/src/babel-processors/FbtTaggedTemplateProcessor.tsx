@@ -0,0 +1,117 @@
+import type { NodePath as NodePathT } from '@babel/core';
+import {
+  callExpression,
+  CallExpression,
+  Expression,
+  identifier,
+  isCallExpression,
+  isIdentifier,
+  isMemberExpression,
+  isStringLiteral,
+  isTemplateLiteral,
+  memberExpression,
+  TaggedTemplateExpression,
+  TSType,
+} from '@babel/types';
+import type { BindingName } from '../FbtConstants.tsx';
+import { errorAt } from '../FbtUtil.tsx';
+
+type NodePath = NodePathT<TaggedTemplateExpression>;
+
+const getModuleName = (node: Expression): BindingName | null => {
+  const callee = isCallExpression(node) ? node.callee : node;
+  if (
+    isMemberExpression(callee) &&
+    isIdentifier(callee.property, { name: 't' }) &&
+    isIdentifier(callee.object) &&
+    (callee.object.name === 'fbt' || callee.object.name === 'fbs')
+  ) {
+    return callee.object.name;
+  }
+  return null;
+};
+
+/**
+ * This class converts tagged templates to the basic function call form.
+ * I.e. fbt.t('desc')`Hello ${fbt.param('name', name)}` or fbs.t`Done`
+ */
+export default class FbtTaggedTemplateProcessor {
+  moduleName: BindingName;
+  node: NodePath['node'];
+
+  constructor({
+    moduleName,
+    path,
+  }: {
+    moduleName: BindingName;
+    path: NodePath;
+  }) {
+    this.moduleName = moduleName;
+    this.node = path.node;
+  }
+
+  static create({
+    path,
+  }: {
+    path: NodePath;
+  }): FbtTaggedTemplateProcessor | null {
+    const moduleName = getModuleName(path.node.tag);
+    return moduleName != null
+      ? new FbtTaggedTemplateProcessor({ moduleName, path })
+      : null;
+  }
+
+  /**
+   * Converts fbt.t(desc, options)`text` to `fbt(`text`, desc, options)`, and a bare fbt.t`text`
+   * to the common string call `fbt.c(`text`)`.
+   */
+  convertToCall(): CallExpression {
+    const { moduleName, node } = this;
+    const { quasi, tag } = node;
+    for (const expression of quasi.expressions) {
+      if (!this._isTemplateValue(expression)) {
+        throw errorAt(
+          expression,
+          `${moduleName}.t\`...\` interpolations must be ${moduleName} constructs or strings. ` +
+            `Wrap the ${expression.type} value in ${moduleName}.param('name', value).`,
+        );
+      }
+    }
+
+    let callNode: CallExpression;
+    if (isCallExpression(tag)) {
+      callNode = callExpression(identifier(moduleName), [
+        quasi,
+        ...tag.arguments,
+      ]);
+    } else {
+      if (quasi.expressions.length > 0) {
+        throw errorAt(
+          node,
+          `${moduleName}.t\`...\` needs a description. Use ${moduleName}.t('description')\`...\`, or add the text to 'fbtCommon'.`,
+        );
+      }
+      callNode = callExpression(
+        memberExpression(identifier(moduleName), identifier('c')),
+        [quasi],
+      );
+    }
+
+    callNode.loc = node.loc;
+    return callNode;
+  }
+
+  _isTemplateValue(expression: Expression | TSType): boolean {
+    if (isCallExpression(expression)) {
+      const { callee } = expression;
+      return (
+        isMemberExpression(callee) &&
+        isIdentifier(callee.object, { name: this.moduleName })
+      );
+    }
+    return (
+      isStringLiteral(expression) ||
+      (isTemplateLiteral(expression) && expression.expressions.length === 0)
+    );
+  }
+}
@@ -1,3 +1,4 @@
+import type { HashScheme } from '../fbtHashKey.tsx';
 import fbtHashKey from '../fbtHashKey.tsx';
 import jenkinsHash from '../fbtJenkinsHash.tsx';
 import type { PackagerPhrase } from './FbtCollector.tsx';
@@ -7,11 +8,16 @@ import type { PackagerPhrase } from './FbtCollector.tsx';
  * entire payload for identification
  */
 export default class PhrasePackager {
+  _hashScheme: HashScheme;
+  constructor(hashScheme: HashScheme = 'jenkins') {
+    this._hashScheme = hashScheme;
+  }
+
   pack(phrases: Array<PackagerPhrase>): Array<PackagerPhrase> {
     return phrases.map((phrase) => {
       return {
         hash_code: jenkinsHash(phrase.jsfbt.t),
-        hash_key: fbtHashKey(phrase.jsfbt.t),
+        hash_key: fbtHashKey(phrase.jsfbt.t, this._hashScheme),
         ...(phrase as PackagerPhrase),
       };
     });
@@ -1,4 +1,5 @@
 import path from 'node:path';
+import type { HashScheme } from '../../fbtHashKey.tsx';
 import packagerTypes from '../collectFbtConstants.tsx';
 import {
   buildCollectFbtOutput,
@@ -12,6 +13,7 @@ async function collect(
   options: {
     customCollector?: string;
     genFbtNodes?: boolean;
+    hashScheme?: HashScheme;
     packagerType?: string;
   } = {},
 ) {
@@ -30,6 +32,7 @@ async function collect(
   const packagers = await getPackagers(
     packager,
     path.join(import.meta.dirname, '../md5.tsx'),
+    options.hashScheme,
   );
 
   await (Array.isArray(source)
@@ -38,10 +41,27 @@ async function collect(
 
   return buildCollectFbtOutput(fbtCollector, packagers, {
     genFbtNodes: !!options.genFbtNodes,
+    hashScheme: options.hashScheme,
   });
 }
 
 describe('collectFbt', () => {
+  it('should only record non-default hash schemes', async () => {
+    const source =
+      'import { fbt } from \'fbt\';<fbt desc="It\'s simple">A simple string</fbt>';
+    const hashKey = async (hashScheme: HashScheme) => {
+      const res = await collect(source, {
+        hashScheme,
+        packagerType: packagerTypes.PHRASE,
+      });
+      return [res.hashScheme, res.phrases[0].hash_key];
+    };
+    // The same keys as the SWC plugin's `hashes_simple_strings_like_babel` test.
+    expect(await hashKey('jenkins')).toEqual([undefined, 'pITkM']);
+    expect(await hashKey('md5')).toEqual(['md5', 'gasWxhWXjWzrjGWE07rKrw==']);
+    expect(await hashKey('fnv1a64')).toEqual(['fnv1a64', 'ltUKBCtAJOY']);
+  });
+
   it('should extract fbt strings', async () => {
     const res = await collect(
       'import { fbt } from \'fbt\';<fbt desc="foo">bar</fbt>',
@@ -777,6 +777,30 @@ describe('translate-test.js', () => {
         });
       });
 
+      it('should record non-default hash schemes in each locale', async () => {
+        const sourceFile = join(testDir, 'source_strings.json');
+        const frFile = join(testDir, 'fr_FR.json');
+
+        writeFileSync(
+          sourceFile,
+          JSON.stringify({ ...mockSourceStrings, hashScheme: 'md5' }),
+        );
+        writeFileSync(frFile, JSON.stringify(mockTranslations.fr_FR));
+
+        const result = await processFiles(sourceFile, [frFile], {
+          hashModule: false,
+          jenkins: true,
+          strict: false,
+        });
+
+        expect(result).toEqual({
+          'fr-FR': {
+            '+RQyVB1csfPTnXXpuDFI7Q==': 'Bonjour',
+            __hashScheme: 'md5',
+          },
+        });
+      });
+
       it('should throw when aliasing translation input files both exist', async () => {
         const sourceFile = join(testDir, 'source_strings.json');
         const legacyFile = join(testDir, 'de_DE.json');
@@ -5,6 +5,8 @@ import { pathToFileURL } from 'node:url';
 import yargs from 'yargs';
 import type { PlainFbtNode } from '../fbt-nodes/FbtNode.tsx';
 import { FbtOptionConfig } from '../FbtConstants.tsx';
+import type { HashScheme } from '../fbtHashKey.tsx';
+import { HashSchemes } from '../fbtHashKey.tsx';
 import type { TableJSFBT } from '../index.tsx';
 import packagerTypes from './collectFbtConstants.tsx';
 import {
@@ -65,6 +67,11 @@ export type CollectFbtOutput = {
    * This field is present only when the GEN_FBT_NODES script option is `true`
    */
   fbtElementNodes?: Array<PlainFbtNode> | null;
+  /**
+   * The scheme used for phrase `hash_key`s, so that translation tools compute
+   * the same `hk` as the compiler.
+   */
+  hashScheme?: HashScheme;
   /**
    * List of phrases extracted from the given JS source code.
    * Note that for a given fbt callsite, we may extract multiple phrases.
@@ -99,6 +106,14 @@ const argv = y
       "  'none' - No hashing or alteration of phrase data\n",
   )
   .choices('packager', Object.values(packagerTypes))
+  .string('hash-scheme')
+  .default('hash-scheme', 'jenkins')
+  .describe(
+    'hash-scheme',
+    'Hash scheme used for phrase hash keys (`hk`). Must match the `hashScheme` ' +
+      'option of the compiler.',
+  )
+  .choices('hash-scheme', HashSchemes)
   .describe('h', 'Display usage message')
   .alias('h', 'help')
   .string('common')
@@ -187,9 +202,11 @@ async function writeOutput(collector: IFbtCollector) {
   const packagers = await getPackagers(
     argv['packager'] || 'text',
     argv['hash-module'] || null,
+    argv['hash-scheme'] as HashScheme,
   );
   const output = buildCollectFbtOutput(collector, packagers, {
     genFbtNodes: argv['generate-fbt-nodes'],
+    hashScheme: argv['hash-scheme'] as HashScheme,
   });
 
   if (argv['include-default-strings']) {
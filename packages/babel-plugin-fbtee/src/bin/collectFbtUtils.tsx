@@ -2,6 +2,7 @@ import path from 'node:path';
 import { pathToFileURL } from 'node:url';
 import invariant from 'invariant';
 import type { FbtOptionConfig } from '../FbtConstants.tsx';
+import type { HashScheme } from '../fbtHashKey.tsx';
 import type { CollectFbtOutput } from './collect.tsx';
 import packagerTypes from './collectFbtConstants.tsx';
 import type {
@@ -26,12 +27,17 @@ export function buildCollectFbtOutput(
   >,
   options: {
     genFbtNodes: boolean;
+    hashScheme?: HashScheme;
   },
 ): CollectFbtOutput {
   return {
     childParentMappings: Object.fromEntries(
       fbtCollector.getChildParentMappings(),
     ),
+    // Only non-default schemes are recorded, so jenkins output stays unchanged.
+    ...(options.hashScheme != null && options.hashScheme !== 'jenkins'
+      ? { hashScheme: options.hashScheme }
+      : {}),
     fbtElementNodes: options.genFbtNodes
       ? fbtCollector.getFbtElementNodes()
       : // using `undefined` so that the field is not outputted by JSON.stringify
@@ -72,6 +78,7 @@ async function getTextPackager(
 export async function getPackagers(
   packager: string,
   hashModulePath: string | null,
+  hashScheme: HashScheme = 'jenkins',
 ): Promise<
   ReadonlyArray<
     | {
@@ -85,9 +92,12 @@ export async function getPackagers(
     case packagerTypes.TEXT:
       return [await getTextPackager(hashModulePath)];
     case packagerTypes.PHRASE:
-      return [new PhrasePackager()];
+      return [new PhrasePackager(hashScheme)];
     case packagerTypes.BOTH:
-      return [await getTextPackager(hashModulePath), new PhrasePackager()];
+      return [
+        await getTextPackager(hashModulePath),
+        new PhrasePackager(hashScheme),
+      ];
     case packagerTypes.NONE:
       return [{ pack: (phrases) => phrases }];
     default:
@@ -1,6 +1,7 @@
 import { mkdirSync, readFileSync, writeFileSync } from 'node:fs';
 import path from 'node:path';
 import { pathToFileURL } from 'node:url';
+import type { HashScheme } from '../fbtHashKey.tsx';
 import FbtHashKey from '../fbtHashKey.tsx';
 import {
   formatLocaleForStyle,
@@ -29,6 +30,13 @@ export type Options = Readonly<{
   strict: boolean;
 }>;
 
+/**
+ * Key under which each locale's translations record a non-default hash scheme,
+ * so the runtime can report builds that use a different one. Hash keys are
+ * base62 or base64 and never contain `_`.
+ */
+export const HASH_SCHEME_KEY = '__hashScheme';
+
 export type LocaleToHashToTranslationResult = {
   [fbLocale: string]: Partial<Record<PatternHash, TranslationResult>>;
 };
@@ -77,6 +85,7 @@ function throwIfLocaleConflicts(locales: ReadonlyArray<string>): void {
 
 /** Phrases and translation data in one JSON object */
 type InputJSONType = Readonly<{
+  hashScheme?: HashScheme;
   phrases: ReadonlyArray<CollectFbtOutputPhrase>;
   translationGroups: ReadonlyArray<TranslationGroup>;
 }>;
@@ -98,7 +107,7 @@ export async function processFiles(
   options: Options,
 ): Promise<LocaleToHashToTranslationResult> {
   throwIfLocaleFileConflicts(translationFiles);
-  const { phrases } = loadJSON<CollectFbtOutput>(stringFile);
+  const { hashScheme, phrases } = loadJSON<CollectFbtOutput>(stringFile);
   const fbtSites = phrases.map(createFbtSiteFromJSON);
   return await processGroups(
     phrases,
@@ -106,6 +115,7 @@ export async function processFiles(
       processTranslations(fbtSites, loadJSON<TranslationGroup>(file), options),
     ),
     options,
+    hashScheme,
   );
 }
 
@@ -128,6 +138,7 @@ export async function processJSON(
       processTranslations(fbtSites, group, options),
     ),
     options,
+    json.hashScheme,
   );
 }
 
@@ -135,11 +146,14 @@ async function processGroups(
   phrases: ReadonlyArray<CollectFbtOutputPhrase>,
   translatedGroups: TranslatedGroups,
   options: Options,
+  hashScheme: HashScheme = 'jenkins',
 ): Promise<LocaleToHashToTranslationResult> {
   throwIfLocaleConflicts(translatedGroups.map((group) => group['fb-locale']));
-  let fbtHash: typeof FbtHashKey | null = null;
+  let fbtHash: ((jsfbt: Parameters<typeof FbtHashKey>[0]) => string) | null =
+    null;
   if (options.jenkins) {
-    fbtHash = (await import('../fbtHashKey.tsx')).default;
+    const fbtHashKey = (await import('../fbtHashKey.tsx')).default;
+    fbtHash = (jsfbt) => fbtHashKey(jsfbt, hashScheme);
   } else if (typeof options.hashModule === 'string') {
     fbtHash = (await import(pathToFileURL(options.hashModule).href)).default;
   }
@@ -152,6 +166,9 @@ async function processGroups(
   for (const group of translatedGroups) {
     const hashToFbt: Partial<Record<string, TranslationResult>> =
       (localeToHashToFbt[group['fb-locale']] = {});
+    if (options.jenkins && hashScheme !== 'jenkins') {
+      hashToFbt[HASH_SCHEME_KEY] = hashScheme;
+    }
     phrases.forEach((phrase, idx) => {
       const translatedFbt = group.translatedPhrases[idx];
       const jsfbt = nullthrows(
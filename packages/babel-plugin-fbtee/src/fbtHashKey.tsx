@@ -1,23 +1,61 @@
-import fbtJenkinsHash from './fbtJenkinsHash.tsx';
+import crypto from 'node:crypto';
+import fbtJenkinsHash, { getFbtHashInput } from './fbtJenkinsHash.tsx';
 import type { TableJSFBTTree } from './index.tsx';
 
+/**
+ * Algorithm used to derive the `hk` of a phrase:
+ *   'jenkins' - 32-bit jenkins hash in base 62 (default)
+ *       'md5' - base64 md5 digest, as used by the `TextPackager`
+ *   'fnv1a64' - 64-bit FNV-1a hash in base 62
+ */
+export type HashScheme = 'jenkins' | 'md5' | 'fnv1a64';
+
+export const HashSchemes: ReadonlyArray<HashScheme> = [
+  'jenkins',
+  'md5',
+  'fnv1a64',
+];
+
 const BaseNSymbols =
   '0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ';
 
 // Compute the baseN string for a given unsigned integer.
-function uintToBaseN(numberArg: number, base: number) {
-  let number = numberArg;
-  if (base < 2 || base > 62 || number < 0) {
+function uintToBaseN(numberArg: number | bigint, base: number) {
+  let number = BigInt(numberArg);
+  if (base < 2 || base > 62 || number < 0n) {
     return '';
   }
+  const bigBase = BigInt(base);
   let output = '';
   do {
-    output = BaseNSymbols.charAt(number % base).concat(output);
-    number = Math.floor(number / base);
-  } while (number > 0);
+    output = BaseNSymbols.charAt(Number(number % bigBase)).concat(output);
+    number = number / bigBase;
+  } while (number > 0n);
   return output;
 }
 
-export default function fbtHashKey(jsfbt: Readonly<TableJSFBTTree>): string {
-  return uintToBaseN(fbtJenkinsHash(jsfbt), 62);
+function fnv1a64Hash(str: string): bigint {
+  let hash = 0xcb_f2_9c_e4_84_22_23_25n;
+  for (const byte of Buffer.from(str, 'utf8')) {
+    hash =
+      ((hash ^ BigInt(byte)) * 0x1_00_00_00_01_b3n) & 0xff_ff_ff_ff_ff_ff_ff_ffn;
+  }
+  return hash;
+}
+
+export default function fbtHashKey(
+  jsfbt: Readonly<TableJSFBTTree>,
+  scheme: HashScheme = 'jenkins',
+): string {
+  switch (scheme) {
+    case 'jenkins':
+      return uintToBaseN(fbtJenkinsHash(jsfbt), 62);
+    case 'md5':
+      return crypto
+        .createHash('md5')
+        .update(getFbtHashInput(jsfbt))
+        .digest('base64');
+    case 'fnv1a64':
+      return uintToBaseN(fnv1a64Hash(getFbtHashInput(jsfbt)), 62);
+  }
 }
@@ -3,9 +3,10 @@ import type { TableJSFBTTree, TableJSFBTTreeLeaf } from './index.tsx';
 import jenkinsHash from './jenkinsHash.tsx';
 import { mapLeaves, onEachLeaf } from './JSFbtUtil.tsx';
 
-export default function fbtJenkinsHash(
-  jsfbt: Readonly<TableJSFBTTree>,
-): number {
+/**
+ * The string a phrase's `hk` is computed from, whichever hash scheme is used.
+ */
+export function getFbtHashInput(jsfbt: Readonly<TableJSFBTTree>): string {
   let desc: string | null = null;
   let leavesHaveSameDesc = true;
   onEachLeaf({ jsfbt: { m: [], t: jsfbt } }, (leaf: TableJSFBTTreeLeaf) => {
@@ -29,8 +30,7 @@ export default function fbtJenkinsHash(
       desc != null,
       'Expect `desc` to be nonnull as `TableJSFBTTree` should contain at least one leaf.',
     );
-    const key = JSON.stringify(hashInputTree) + '|' + desc;
-    return jenkinsHash(key);
+    return JSON.stringify(hashInputTree) + '|' + desc;
   }
 
   const hashInputTree = mapLeaves(
@@ -42,5 +42,11 @@ export default function fbtJenkinsHash(
         : newLeaf;
     },
   );
-  return jenkinsHash(JSON.stringify(hashInputTree));
+  return JSON.stringify(hashInputTree);
+}
+
+export default function fbtJenkinsHash(
+  jsfbt: Readonly<TableJSFBTTree>,
+): number {
+  return jenkinsHash(getFbtHashInput(jsfbt));
 }
@@ -4,11 +4,13 @@ import {
   ImportDeclaration,
   JSXElement,
   Node,
+  TaggedTemplateExpression,
 } from '@babel/types';
 import { parse as parseDocblock } from 'jest-docblock';
 import FbtCommonFunctionCallProcessor from './babel-processors/FbtCommonFunctionCallProcessor.tsx';
 import type { MetaPhrase } from './babel-processors/FbtFunctionCallProcessor.tsx';
 import FbtFunctionCallProcessor from './babel-processors/FbtFunctionCallProcessor.tsx';
+import FbtTaggedTemplateProcessor from './babel-processors/FbtTaggedTemplateProcessor.tsx';
 import JSXFbtProcessor from './babel-processors/JSXFbtProcessor.tsx';
 import FbtElementNode from './fbt-nodes/FbtElementNode.tsx';
 import type { AnyFbtNode, PlainFbtNode } from './fbt-nodes/FbtNode.tsx';
@@ -19,12 +21,14 @@ import type { FbtCallSiteOptions, FbtOptionConfig } from './FbtConstants.tsx';
 import { ValidFbtOptions } from './FbtConstants.tsx';
 import type { EnumManifest } from './FbtEnumRegistrar.tsx';
 import FbtEnumRegistrar from './FbtEnumRegistrar.tsx';
+import type { HashScheme } from './fbtHashKey.tsx';
 import FbtNodeChecker from './FbtNodeChecker.tsx';
 import { checkOption, errorAt } from './FbtUtil.tsx';
 import { FbtVariationType } from './translate/IntlVariations.tsx';
 import type { FbtTableKey, PatternHash, PatternString } from './Types.ts';
 
 export { default as fbtHashKey } from './fbtHashKey.tsx';
+export type { HashScheme } from './fbtHashKey.tsx';
 export { default as replaceClearTokensWithTokenAliases } from './replaceClearTokensWithTokenAliases.tsx';
 export { mapLeaves } from './JSFbtUtil.tsx';
 export { ModuleName, BindingNames } from './FbtConstants.tsx';
@@ -71,6 +75,8 @@ export type PluginOptions = {
   filename?: string | null;
   // If true, generate the `outerTokenName` property on the JSFbt tree leaves.
   generateOuterTokenName?: boolean;
+  // Hash scheme used for the `hk` of each phrase. Non-default schemes are recorded as `hs`.
+  hashScheme?: HashScheme;
 };
 /**
  * Token alias (aka mangled token name)
@@ -276,6 +282,16 @@ export default function transform() {
         FbtEnumRegistrar.registerImportIfApplicable(path);
       },
 
+      /**
+       * Transform tagged templates such as fbt.t('desc')`...` to fbt() calls.
+       */
+      TaggedTemplateExpression(path: NodePath<TaggedTemplateExpression>) {
+        const processor = FbtTaggedTemplateProcessor.create({ path });
+        if (processor) {
+          path.replaceWith(processor.convertToCall());
+        }
+      },
+
       /**
        * Transform jsx-style <fbt> to fbt() calls.
        */
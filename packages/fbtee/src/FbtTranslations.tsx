@@ -11,7 +11,12 @@ export type TranslationDictionary = {
   };
 };
 
+// Written by `fbtee translate` into each locale's table for non-default hash
+// schemes. Hash keys never contain `_`.
+const HASH_SCHEME_KEY = '__hashScheme';
+
 let currentTranslations: TranslationDictionary = {};
+const reportedHashSchemes = new Set<string>();
 
 const defaultLocale = 'en-US';
 
@@ -35,6 +40,21 @@ export default {
         console.warn('Translations have not been provided.');
       }
     }
+    if (table && process.env.NODE_ENV !== 'production') {
+      const callScheme = options?.hs ?? 'jenkins';
+      const tableScheme = table[HASH_SCHEME_KEY] ?? 'jenkins';
+      const mismatch = `${locale}:${callScheme}:${tableScheme}`;
+      if (callScheme !== tableScheme && !reportedHashSchemes.has(mismatch)) {
+        reportedHashSchemes.add(mismatch);
+        // eslint-disable-next-line no-console
+        console.error(
+          `fbtee: This build hashes strings with "${callScheme}", but the ` +
+            `translations for "${locale}" were hashed with "${tableScheme}". ` +
+            `No translation will match. Collect and translate with the same ` +
+            `hashScheme as the build.`,
+        );
+      }
+    }
 
     return hashKey == null || table?.[hashKey] == null
       ? null
@@ -60,6 +60,10 @@ export type FbtEnumHashKeyTable = Partial<Record<FbtTableKey, PatternString>>;
 export type FbtInputOpts = {
   // hash key
   hk?: string;
+  // hash scheme of `hk`, when not the default jenkins scheme. Translations are
+  // looked up by `hk` alone; the runtime reports translations hashed with a
+  // different scheme.
+  hs?: string;
 };
 /**
  * Map of extra fbt options (or JSX attributes) to accept on fbt callsites.
@@ -125,15 +125,17 @@ type FbtParamOutput = FbsParamOutput;
 export type FbtConjunction = 'and' | 'none' | 'or';
 export type FbtDelimiter = 'bullet' | 'comma' | 'semicolon';
 
+type FbtCallOptions = {
+  author?: string;
+  project?: string;
+  subject?: IntlVariations;
+};
+
 type FbtAPIT<Output, ParamInput, ParamOutput> = {
   (
     text: string | ReadonlyArray<string>,
     description: string,
-    options?: {
-      author?: string;
-      project?: string;
-      subject?: IntlVariations;
-    },
+    options?: FbtCallOptions,
   ): Output;
   c: (text: string) => Output;
   enum: (
@@ -174,6 +176,16 @@ type FbtAPIT<Output, ParamInput, ParamOutput> = {
     },
   ) => ParamOutput;
   sameParam: (name: string) => ParamOutput;
+  t: {
+    (strings: TemplateStringsArray): Output;
+    (
+      description: string,
+      options?: FbtCallOptions,
+    ): (
+      strings: TemplateStringsArray,
+      ...values: ReadonlyArray<ParamOutput | string>
+    ) => Output;
+  };
 };
 
 type StringBasedFbtFunctionAPI<Output, ParamInput, ParamOutput> = FbtAPIT<
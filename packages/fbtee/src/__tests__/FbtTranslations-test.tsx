@@ -1,4 +1,4 @@
-import { describe, expect, it } from '@jest/globals';
+import { describe, expect, it, jest } from '@jest/globals';
 import FbtTranslations from '../FbtTranslations.tsx';
 import Hooks from '../Hooks.tsx';
 
@@ -81,4 +81,45 @@ describe('FbtTranslations', () => {
       table: 'Hallo',
     });
   });
+
+  it('reports translations hashed with a different scheme', () => {
+    const consoleError = jest
+      .spyOn(console, 'error')
+      .mockImplementation(() => {});
+    FbtTranslations.registerTranslations({
+      fr_FR: { __hashScheme: 'md5', 'gasWxhWXjWzrjGWE07rKrw==': 'Bonjour' },
+    });
+    Hooks.register({
+      getViewerContext: () => ({
+        GENDER: 3,
+        locale: 'fr-FR',
+      }),
+    });
+
+    expect(
+      FbtTranslations.getTranslatedInput({
+        args: null,
+        options: { hk: 'gasWxhWXjWzrjGWE07rKrw==', hs: 'md5' },
+        table: 'Hello',
+      }),
+    ).toEqual({
+      args: null,
+      table: 'Bonjour',
+    });
+    expect(consoleError).not.toHaveBeenCalled();
+
+    expect(
+      FbtTranslations.getTranslatedInput({
+        args: null,
+        options: { hk: 'pITkM' },
+        table: 'Hello',
+      }),
+    ).toBeNull();
+    expect(consoleError).toHaveBeenCalledTimes(1);
+    expect(consoleError.mock.calls[0][0]).toContain(
+      'This build hashes strings with "jenkins", but the translations for "fr-FR" were hashed with "md5".',
+    );
+
+    consoleError.mockRestore();
+  });
 });
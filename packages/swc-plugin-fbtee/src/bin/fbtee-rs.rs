@@ -0,0 +1,931 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+use swc_plugin_fbtee::{
+    carry_over::{carry_over_translations, hash_mapping, match_changed_leaves},
+    codemod::{
+        fbt_form::{convert_fbt_form, FbtForm},
+        i18next::{self, flatten_catalog, migrate_i18next},
+        react_intl::{self, migrate_react_intl},
+        source_files, unified_diff,
+        wrap_jsx_text::wrap_jsx_text,
+    },
+    gettext::{export_pot, import_po},
+    icu::{export_icu, import_icu},
+    locale::{available_locale_file, locale_files, LocaleStyle},
+    translations::{
+        load_json, merge_translation_group, prepare_translations, write_json, CollectFbtOutput,
+        PrepareTranslationsOptions, TranslationGroup,
+    },
+    validation::{TranslationValidator, ValidationReport},
+    xliff::{export_xliff, import_xliff},
+};
+
+const HELP: &str = "Usage: fbtee-rs <command> [options]
+
+Commands:
+  prepare-translations    Prepare translation files from collected strings.
+  validate-translations   Check translation files against collected strings.
+  export-xliff            Export collected strings and translations to XLIFF 2.0.
+  import-xliff            Import XLIFF 2.0 files into translation files.
+  export-pot              Export collected strings to a gettext POT template.
+  import-po               Import gettext PO files into translation files.
+  carry-over              Map changed phrases to their new hashes and carry translations over.
+  export-icu              Export collected strings as ICU MessageFormat messages.
+  import-icu              Import translated ICU MessageFormat messages into translation files.
+  wrap-jsx-text           Wrap JSX text outside of fbt in <fbt> for translation.
+  convert-fbt             Convert phrases between the fbt() call form and the <fbt> JSX form.
+  migrate-react-intl      Rewrite react-intl messages as fbt phrases and carry their translations over.
+  migrate-i18next         Inline i18next t() calls as fbt phrases and carry their translations over.
+
+Run \"fbtee-rs <command> --help\" for command-specific options.
+";
+
+const PREPARE_TRANSLATIONS_HELP: &str = "Prepare translation files by merging phrases with existing translations:
+fbtee-rs prepare-translations [options]
+
+Options:
+  --source-strings              The file containing source strings, as collected by `fbtee collect`. [default: \"source_strings.json\"]
+  --output-dir, -o              The directory where all translation files will be written. Existing translation files will be loaded from this directory. [default: \"translations/\"]
+  --locales, --locale           A list of locales to process. Useful to create the initial translation files if none exist.
+  --sort-by-hash                Sort translation entries by hash key in output JSON. Applies to all entries (both existing and new).
+  --output-locale-style, --locale-style
+                                Controls newly-created locale file names and fb-locale values. Existing locale files are updated in place. [choices: \"bcp47\", \"legacy\", \"preserve\"] [default: \"bcp47\"]
+  --help, -h                    Display usage message
+";
+
+const VALIDATE_TRANSLATIONS_HELP: &str = "Check translation files for missing or unknown tokens, unknown hashes, invalid variations, malformed locales and empty translations:
+fbtee-rs validate-translations [options]
+
+Options:
+  --source-strings              The file containing source strings, as collected by `fbtee collect`. [default: \"source_strings.json\"]
+  --translations                Translation files or directories containing them. [default: \"translations/\"]
+  --json                        Print the report as JSON.
+  --help, -h                    Display usage message
+
+Exits with a non-zero status if any errors were found.
+";
+
+const EXPORT_XLIFF_HELP: &str = "Export collected strings to XLIFF 2.0, one file per locale. Existing translations become targets:
+fbtee-rs export-xliff [options]
+
+Options:
+  --source-strings              The file containing source strings, as collected by `fbtee collect`. [default: \"source_strings.json\"]
+  --translations                The directory containing translation files. [default: \"translations/\"]
+  --locales, --locale           Additional locales to export without existing translation files.
+  --source-locale               The language of the source strings. [default: \"en_US\"]
+  --output-dir, -o              The directory where XLIFF files will be written. Without locales, a single \"source.xlf\" is written. [default: \"xliff/\"]
+  --help, -h                    Display usage message
+";
+
+const IMPORT_XLIFF_HELP: &str = "Import translated XLIFF 2.0 files into translation files, replacing entries with the same hash:
+fbtee-rs import-xliff [options] <files...>
+
+Options:
+  --output-dir, -o              The directory containing translation files. [default: \"translations/\"]
+  --output-locale-style, --locale-style
+                                Controls newly-created locale file names and fb-locale values. [choices: \"bcp47\", \"legacy\", \"preserve\"] [default: \"bcp47\"]
+  --help, -h                    Display usage message
+";
+
+const EXPORT_POT_HELP: &str = "Export collected strings to a gettext POT template:
+fbtee-rs export-pot [options]
+
+Options:
+  --source-strings              The file containing source strings, as collected by `fbtee collect`. [default: \"source_strings.json\"]
+  --output, -o                  The POT file to write. [default: \"messages.pot\"]
+  --help, -h                    Display usage message
+";
+
+const IMPORT_PO_HELP: &str = "Import translated gettext PO files into translation files, replacing entries with the same hash:
+fbtee-rs import-po [options] <files...>
+
+Options:
+  --locale                      The locale of the PO files. Defaults to each file's \"Language\" header.
+  --output-dir, -o              The directory containing translation files. [default: \"translations/\"]
+  --output-locale-style, --locale-style
+                                Controls newly-created locale file names and fb-locale values. [choices: \"bcp47\", \"legacy\", \"preserve\"] [default: \"bcp47\"]
+  --help, -h                    Display usage message
+";
+
+const CARRY_OVER_HELP: &str = "Pair phrases that changed between two collected outputs and write an old-hash to new-hash mapping:
+fbtee-rs carry-over --old <file> [options]
+
+Options:
+  --old                         The previously collected source strings.
+  --new                         The newly collected source strings. [default: \"source_strings.json\"]
+  --mapping                     The JSON file to write the mapping to. [default: \"hash_mapping.json\"]
+  --threshold                   Minimum similarity (0 to 1) of text, description and tokens to pair two phrases. [default: 0.7]
+  --carry-translations          Copy translations onto the new hashes with a \"needs-review\" status.
+  --translations                The directory containing translation files. [default: \"translations/\"]
+  --help, -h                    Display usage message
+";
+
+const EXPORT_ICU_HELP: &str = "Export collected strings as ICU MessageFormat messages keyed by leaf hash:
+fbtee-rs export-icu [options]
+
+Options:
+  --source-strings              The file containing source strings, as collected by `fbtee collect`. [default: \"source_strings.json\"]
+  --output, -o                  The JSON file to write. [default: \"messages.json\"]
+  --help, -h                    Display usage message
+";
+
+const IMPORT_ICU_HELP: &str = "Import translated ICU MessageFormat messages into translation files, replacing entries with the same hash:
+fbtee-rs import-icu --locale <locale> [options] <files...>
+
+Options:
+  --source-strings              The file containing source strings, as collected by `fbtee collect`. [default: \"source_strings.json\"]
+  --locale                      The locale of the message files.
+  --output-dir, -o              The directory containing translation files. [default: \"translations/\"]
+  --output-locale-style, --locale-style
+                                Controls newly-created locale file names and fb-locale values. [choices: \"bcp47\", \"legacy\", \"preserve\"] [default: \"bcp47\"]
+  --help, -h                    Display usage message
+";
+
+const WRAP_JSX_TEXT_HELP: &str = "Wrap JSX text outside of fbt in <fbt desc=\"TODO: ...\"> and user-facing attributes in fbs() calls. Expressions between text become <fbt:param>s, and inline elements such as <a> and <b> stay inside the phrase:
+fbtee-rs wrap-jsx-text [options] <files or directories...>
+
+Options:
+  --dry-run                     Print a diff of the changes instead of writing files.
+  --help, -h                    Display usage message
+";
+
+const CONVERT_FBT_HELP: &str = "Convert fbt and fbs phrases between the fbt() call form and the <fbt> JSX form. Phrases whose hk would change are left alone:
+fbtee-rs convert-fbt --to <form> [options] <files or directories...>
+
+Options:
+  --to                          The form to convert phrases to. [choices: \"call\", \"jsx\"]
+  --config                      A JSON file with the plugin config needed to compile the files, such as fbtCommon or fbtEnumManifest.
+  --dry-run                     Print a diff of the changes instead of writing files.
+  --help, -h                    Display usage message
+";
+
+const MIGRATE_REACT_INTL_HELP: &str = "Rewrite <FormattedMessage> as <fbt>, and formatMessage() calls on a react-intl intl object as fbs() phrases, or fbt() in JSX children. Unused useIntl() bindings are removed. ICU arguments, plurals and selects become fbt:param, fbt:plural and fbt:enum. Messages fbt cannot express are left alone:
+fbtee-rs migrate-react-intl [options] <files or directories...>
+
+Options:
+  --translations                A directory of react-intl message catalogs named after their locale, such as \"lang/fr.json\". Their messages are imported into translation files under the new hashes.
+  --output-dir, -o              The directory containing translation files. [default: \"translations/\"]
+  --output-locale-style, --locale-style
+                                Controls newly-created locale file names and fb-locale values. [choices: \"bcp47\", \"legacy\", \"preserve\"] [default: \"bcp47\"]
+  --config                      A JSON file with the plugin config needed to compile the files.
+  --dry-run                     Print a diff of the changes instead of writing files.
+  --help, -h                    Display usage message
+";
+
+const MIGRATE_I18NEXT_HELP: &str = "Inline t() calls on t functions and instances from i18next or useTranslation() as fbt() phrases in JSX children and fbs() phrases elsewhere, described by their key. Unused useTranslation() bindings and i18next imports are removed. {{var}} interpolations become params and _one/_other keys become plurals. Calls fbt cannot express are left alone:
+fbtee-rs migrate-i18next --catalog <file> [options] <files or directories...>
+
+Options:
+  --catalog                     The source-language i18next resource file.
+  --translations                A directory of i18next resources for other locales, either \"<locale>.json\" files or \"<locale>/\" directories containing a file named like the catalog. They are imported into translation files under the new hashes.
+  --output-dir, -o              The directory containing translation files. [default: \"translations/\"]
+  --output-locale-style, --locale-style
+                                Controls newly-created locale file names and fb-locale values. [choices: \"bcp47\", \"legacy\", \"preserve\"] [default: \"bcp47\"]
+  --config                      A JSON file with the plugin config needed to compile the files.
+  --dry-run                     Print a diff of the changes instead of writing files.
+  --help, -h                    Display usage message
+";
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let args = Args::new(args.collect());
+    let result = match command.as_deref() {
+        None | Some("help" | "--help" | "-h") => {
+            print!("{HELP}");
+            Ok(())
+        }
+        Some("prepare-translations") => run_prepare_translations(args),
+        Some("validate-translations") => run_validate_translations(args),
+        Some("export-xliff") => run_export_xliff(args),
+        Some("import-xliff") => run_import_xliff(args),
+        Some("export-pot") => run_export_pot(args),
+        Some("import-po") => run_import_po(args),
+        Some("carry-over") => run_carry_over(args),
+        Some("export-icu") => run_export_icu(args),
+        Some("import-icu") => run_import_icu(args),
+        Some("wrap-jsx-text") => run_wrap_jsx_text(args),
+        Some("convert-fbt") => run_convert_fbt(args),
+        Some("migrate-react-intl") => run_migrate_react_intl(args),
+        Some("migrate-i18next") => run_migrate_i18next(args),
+        Some(command) => {
+            eprint!("Unknown command: {command}\n\n{HELP}");
+            process::exit(1);
+        }
+    };
+    if let Err(error) = result {
+        eprintln!("{error}");
+        process::exit(1);
+    }
+}
+
+fn run_prepare_translations(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{PREPARE_TRANSLATIONS_HELP}");
+        return Ok(());
+    }
+    let source_strings = args
+        .value(&["source-strings"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let options = PrepareTranslationsOptions {
+        locales: args.values(&["locales", "locale"]),
+        output_locale_style: args
+            .value(&["output-locale-style", "locale-style"])?
+            .map(|style| style.parse::<LocaleStyle>())
+            .transpose()?
+            .unwrap_or_default(),
+        sort_by_hash: args.flag(&["sort-by-hash"])?,
+    };
+    args.finish()?;
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let source = load_json::<CollectFbtOutput>(&root.join(source_strings))?;
+    for locale in prepare_translations(&source, &root.join(output_dir), &options)? {
+        println!("Processing locale: {locale}");
+    }
+    Ok(())
+}
+
+fn run_validate_translations(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{VALIDATE_TRANSLATIONS_HELP}");
+        return Ok(());
+    }
+    let source_strings = args
+        .value(&["source-strings"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let mut translations = args.values(&["translations"]);
+    if translations.is_empty() {
+        translations.push("translations/".to_string());
+    }
+    let json = args.flag(&["json"])?;
+    args.finish()?;
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let source = load_json::<CollectFbtOutput>(&root.join(source_strings))?;
+    let mut files: Vec<PathBuf> = vec![];
+    for path in translations {
+        let path = root.join(path);
+        if path.is_dir() {
+            files.extend(locale_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+
+    let validator = TranslationValidator::new(&source);
+    let mut report = ValidationReport::default();
+    for file in files {
+        let group = load_json::<TranslationGroup>(&file)?;
+        let label = file
+            .strip_prefix(&root)
+            .unwrap_or(&file)
+            .display()
+            .to_string();
+        let file_report = validator.validate(&label, &group);
+        report.errors += file_report.errors;
+        report.warnings += file_report.warnings;
+        report.issues.extend(file_report.issues);
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).map_err(|error| error.to_string())?
+        );
+    } else if !report.issues.is_empty() {
+        println!("{}", report.to_text());
+    }
+    if report.has_errors() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_export_xliff(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{EXPORT_XLIFF_HELP}");
+        return Ok(());
+    }
+    let source_strings = args
+        .value(&["source-strings"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let translations = args
+        .value(&["translations"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let locales = args.values(&["locales", "locale"]);
+    let source_locale = args
+        .value(&["source-locale"])?
+        .unwrap_or_else(|| "en_US".to_string());
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "xliff/".to_string());
+    args.finish()?;
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let source = load_json::<CollectFbtOutput>(&root.join(source_strings))?;
+    let translations = root.join(translations);
+    let output_dir = root.join(output_dir);
+    let mut groups = vec![];
+    for file in locale_files(&translations)? {
+        groups.push(load_json::<TranslationGroup>(&file)?);
+    }
+    for locale in locales {
+        if available_locale_file(&translations, &locale)?.is_none() {
+            groups.push(TranslationGroup {
+                fb_locale: locale,
+                ..TranslationGroup::default()
+            });
+        }
+    }
+
+    fs::create_dir_all(&output_dir)
+        .map_err(|error| format!("Could not create '{}': {error}", output_dir.display()))?;
+    let write = |name: &str, group: Option<&TranslationGroup>| {
+        let path = output_dir.join(format!("{name}.xlf"));
+        fs::write(&path, export_xliff(&source, group, &source_locale))
+            .map_err(|error| format!("Could not write '{}': {error}", path.display()))
+    };
+    if groups.is_empty() {
+        write("source", None)?;
+    }
+    for group in &groups {
+        println!("Exporting locale: {}", group.fb_locale);
+        write(&group.fb_locale, Some(group))?;
+    }
+    Ok(())
+}
+
+fn run_import_xliff(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{IMPORT_XLIFF_HELP}");
+        return Ok(());
+    }
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let output_locale_style = args
+        .value(&["output-locale-style", "locale-style"])?
+        .map(|style| style.parse::<LocaleStyle>())
+        .transpose()?
+        .unwrap_or_default();
+    let files = args.rest()?;
+    if files.is_empty() {
+        return Err(format!("No XLIFF files given.\n\n{IMPORT_XLIFF_HELP}"));
+    }
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    for file in files {
+        let path = root.join(file);
+        let xml = fs::read_to_string(&path)
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+        let group = import_xliff(&xml)
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+        let written = merge_translation_group(&root.join(&output_dir), group, output_locale_style)?;
+        println!("Imported {} into {}", path.display(), written.display());
+    }
+    Ok(())
+}
+
+fn run_export_pot(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{EXPORT_POT_HELP}");
+        return Ok(());
+    }
+    let source_strings = args
+        .value(&["source-strings"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let output = args
+        .value(&["output", "o"])?
+        .unwrap_or_else(|| "messages.pot".to_string());
+    args.finish()?;
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let source = load_json::<CollectFbtOutput>(&root.join(source_strings))?;
+    let path = root.join(output);
+    fs::write(&path, export_pot(&source))
+        .map_err(|error| format!("Could not write '{}': {error}", path.display()))
+}
+
+fn run_import_po(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{IMPORT_PO_HELP}");
+        return Ok(());
+    }
+    let locale = args.value(&["locale"])?;
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let output_locale_style = args
+        .value(&["output-locale-style", "locale-style"])?
+        .map(|style| style.parse::<LocaleStyle>())
+        .transpose()?
+        .unwrap_or_default();
+    let files = args.rest()?;
+    if files.is_empty() {
+        return Err(format!("No PO files given.\n\n{IMPORT_PO_HELP}"));
+    }
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    for file in files {
+        let path = root.join(file);
+        let po = fs::read_to_string(&path)
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+        let group = import_po(&po, locale.as_deref())
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+        let written = merge_translation_group(&root.join(&output_dir), group, output_locale_style)?;
+        println!("Imported {} into {}", path.display(), written.display());
+    }
+    Ok(())
+}
+
+fn run_carry_over(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{CARRY_OVER_HELP}");
+        return Ok(());
+    }
+    let old = args
+        .value(&["old"])?
+        .ok_or_else(|| format!("Missing required argument: old\n\n{CARRY_OVER_HELP}"))?;
+    let new = args
+        .value(&["new"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let mapping = args
+        .value(&["mapping"])?
+        .unwrap_or_else(|| "hash_mapping.json".to_string());
+    let threshold = match args.value(&["threshold"])? {
+        Some(threshold) => threshold
+            .parse::<f64>()
+            .ok()
+            .filter(|threshold| (0.0..=1.0).contains(threshold))
+            .ok_or_else(|| {
+                format!("Invalid threshold '{threshold}'. Expected a number from 0 to 1.")
+            })?,
+        None => 0.7,
+    };
+    let carry_translations = args.flag(&["carry-translations"])?;
+    let translations = args
+        .value(&["translations"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    args.finish()?;
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let old = load_json::<CollectFbtOutput>(&root.join(old))?.hash_to_leaf();
+    let new = load_json::<CollectFbtOutput>(&root.join(new))?.hash_to_leaf();
+    let matches = match_changed_leaves(&old, &new, threshold);
+    for carry_over in &matches {
+        println!(
+            "{} -> {} ({:.3})",
+            carry_over.old_hash, carry_over.new_hash, carry_over.score
+        );
+    }
+    write_json(&root.join(mapping), &hash_mapping(&matches))?;
+
+    if carry_translations {
+        for file in locale_files(&root.join(translations))? {
+            let mut group = load_json::<TranslationGroup>(&file)?;
+            let carried = carry_over_translations(&mut group, &matches, &new);
+            if carried > 0 {
+                write_json(&file, &group)?;
+                println!("Carried {carried} translations into {}", file.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_export_icu(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{EXPORT_ICU_HELP}");
+        return Ok(());
+    }
+    let source_strings = args
+        .value(&["source-strings"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let output = args
+        .value(&["output", "o"])?
+        .unwrap_or_else(|| "messages.json".to_string());
+    args.finish()?;
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let source = load_json::<CollectFbtOutput>(&root.join(source_strings))?;
+    write_json(&root.join(output), &export_icu(&source))
+}
+
+fn run_import_icu(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{IMPORT_ICU_HELP}");
+        return Ok(());
+    }
+    let source_strings = args
+        .value(&["source-strings"])?
+        .unwrap_or_else(|| "source_strings.json".to_string());
+    let locale = args
+        .value(&["locale"])?
+        .ok_or_else(|| format!("Missing required argument: locale\n\n{IMPORT_ICU_HELP}"))?;
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let output_locale_style = args
+        .value(&["output-locale-style", "locale-style"])?
+        .map(|style| style.parse::<LocaleStyle>())
+        .transpose()?
+        .unwrap_or_default();
+    let files = args.rest()?;
+    if files.is_empty() {
+        return Err(format!("No message files given.\n\n{IMPORT_ICU_HELP}"));
+    }
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let source = load_json::<CollectFbtOutput>(&root.join(source_strings))?;
+    for file in files {
+        let path = root.join(file);
+        let messages = load_json::<IndexMap<String, Value>>(&path)?;
+        let group = import_icu(&source, &messages, &locale)
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+        let written = merge_translation_group(&root.join(&output_dir), group, output_locale_style)?;
+        println!("Imported {} into {}", path.display(), written.display());
+    }
+    Ok(())
+}
+
+fn run_wrap_jsx_text(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{WRAP_JSX_TEXT_HELP}");
+        return Ok(());
+    }
+    let dry_run = args.flag(&["dry-run"])?;
+    let paths = args.rest()?;
+    if paths.is_empty() {
+        return Err(format!(
+            "No files or directories given.\n\n{WRAP_JSX_TEXT_HELP}"
+        ));
+    }
+    run_codemod(&paths, dry_run, |source, filename| {
+        wrap_jsx_text(source, filename).map(|wrapped| (wrapped.source, wrapped.phrases))
+    })
+}
+
+fn run_convert_fbt(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{CONVERT_FBT_HELP}");
+        return Ok(());
+    }
+    let form = args
+        .value(&["to"])?
+        .ok_or_else(|| format!("Missing required argument: to\n\n{CONVERT_FBT_HELP}"))?
+        .parse::<FbtForm>()?;
+    let config = args
+        .value(&["config"])?
+        .map(|path| {
+            fs::read_to_string(&path).map_err(|error| format!("Could not read '{path}': {error}"))
+        })
+        .transpose()?;
+    let dry_run = args.flag(&["dry-run"])?;
+    let paths = args.rest()?;
+    if paths.is_empty() {
+        return Err(format!(
+            "No files or directories given.\n\n{CONVERT_FBT_HELP}"
+        ));
+    }
+    run_codemod(&paths, dry_run, |source, filename| {
+        let converted = convert_fbt_form(source, filename, form, config.as_deref())?;
+        for skipped in &converted.skipped {
+            eprintln!("Skipped {skipped}");
+        }
+        Ok((converted.source, converted.converted))
+    })
+}
+
+fn run_migrate_react_intl(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{MIGRATE_REACT_INTL_HELP}");
+        return Ok(());
+    }
+    let translations = args.value(&["translations"])?;
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let output_locale_style = args
+        .value(&["output-locale-style", "locale-style"])?
+        .map(|style| style.parse::<LocaleStyle>())
+        .transpose()?
+        .unwrap_or_default();
+    let config = args
+        .value(&["config"])?
+        .map(|path| {
+            fs::read_to_string(&path).map_err(|error| format!("Could not read '{path}': {error}"))
+        })
+        .transpose()?;
+    let dry_run = args.flag(&["dry-run"])?;
+    let paths = args.rest()?;
+    if paths.is_empty() {
+        return Err(format!(
+            "No files or directories given.\n\n{MIGRATE_REACT_INTL_HELP}"
+        ));
+    }
+
+    let mut messages = vec![];
+    run_codemod(&paths, dry_run, |source, filename| {
+        let migrated = migrate_react_intl(source, filename, config.as_deref())?;
+        for skipped in &migrated.skipped {
+            eprintln!("Skipped {skipped}");
+        }
+        messages.extend(migrated.messages);
+        Ok((migrated.source, migrated.migrated))
+    })?;
+    let Some(translations) = translations else {
+        return Ok(());
+    };
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let entries = fs::read_dir(root.join(&translations))
+        .map_err(|error| format!("Could not read '{translations}': {error}"))?;
+    let mut catalogs = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Could not read '{translations}': {error}"))?;
+    catalogs.retain(|path| {
+        path.extension()
+            .is_some_and(|extension| extension == "json")
+    });
+    catalogs.sort();
+    for path in catalogs {
+        let Some(locale) = path.file_stem().map(|stem| stem.to_string_lossy()) else {
+            continue;
+        };
+        let catalog = load_json::<IndexMap<String, Value>>(&path)?;
+        let group = react_intl::migrate_translations(&messages, &catalog, &locale)
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+        import_migrated_translations(
+            &path,
+            group,
+            &root.join(&output_dir),
+            output_locale_style,
+            dry_run,
+        )?;
+    }
+    Ok(())
+}
+
+fn run_migrate_i18next(mut args: Args) -> Result<(), String> {
+    if args.flag(&["help", "h"])? {
+        print!("{MIGRATE_I18NEXT_HELP}");
+        return Ok(());
+    }
+    let catalog_path = args
+        .value(&["catalog"])?
+        .ok_or_else(|| format!("Missing required argument: catalog\n\n{MIGRATE_I18NEXT_HELP}"))?;
+    let translations = args.value(&["translations"])?;
+    let output_dir = args
+        .value(&["output-dir", "o"])?
+        .unwrap_or_else(|| "translations/".to_string());
+    let output_locale_style = args
+        .value(&["output-locale-style", "locale-style"])?
+        .map(|style| style.parse::<LocaleStyle>())
+        .transpose()?
+        .unwrap_or_default();
+    let config = args
+        .value(&["config"])?
+        .map(|path| {
+            fs::read_to_string(&path).map_err(|error| format!("Could not read '{path}': {error}"))
+        })
+        .transpose()?;
+    let dry_run = args.flag(&["dry-run"])?;
+    let paths = args.rest()?;
+    if paths.is_empty() {
+        return Err(format!(
+            "No files or directories given.\n\n{MIGRATE_I18NEXT_HELP}"
+        ));
+    }
+
+    let root = env::current_dir().map_err(|error| error.to_string())?;
+    let catalog_path = root.join(catalog_path);
+    let catalog = flatten_catalog(&load_json::<Value>(&catalog_path)?);
+    let mut messages = vec![];
+    run_codemod(&paths, dry_run, |source, filename| {
+        let migrated = migrate_i18next(source, filename, &catalog, config.as_deref())?;
+        for skipped in &migrated.skipped {
+            eprintln!("Skipped {skipped}");
+        }
+        messages.extend(migrated.messages);
+        Ok((migrated.source, migrated.migrated))
+    })?;
+    let Some(translations) = translations else {
+        return Ok(());
+    };
+
+    let catalog_name = catalog_path.file_name().unwrap_or_default();
+    let entries = fs::read_dir(root.join(&translations))
+        .map_err(|error| format!("Could not read '{translations}': {error}"))?;
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Could not read '{translations}': {error}"))?;
+    paths.sort();
+    for path in paths {
+        let (locale, resources) = if path.is_dir() {
+            (path.file_name(), path.join(catalog_name))
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            (path.file_stem(), path.clone())
+        } else {
+            continue;
+        };
+        let Some(locale) = locale.map(|locale| locale.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if !resources.is_file() || resources == catalog_path {
+            continue;
+        }
+        let catalog = flatten_catalog(&load_json::<Value>(&resources)?);
+        let group = i18next::migrate_translations(&messages, &catalog, &locale)
+            .map_err(|error| format!("{error}\nFile path: \"{}\"", resources.display()))?;
+        import_migrated_translations(
+            &resources,
+            group,
+            &root.join(&output_dir),
+            output_locale_style,
+            dry_run,
+        )?;
+    }
+    Ok(())
+}
+
+/// Merges the translations a migration carried over from `path` into the output directory.
+fn import_migrated_translations(
+    path: &Path,
+    group: TranslationGroup,
+    output_dir: &Path,
+    output_locale_style: LocaleStyle,
+    dry_run: bool,
+) -> Result<(), String> {
+    if group.translations.is_empty() {
+        return Ok(());
+    }
+    if dry_run {
+        println!(
+            "Would import {} translations from {}",
+            group.translations.len(),
+            path.display()
+        );
+    } else {
+        let written = merge_translation_group(output_dir, group, output_locale_style)?;
+        println!("Imported {} into {}", path.display(), written.display());
+    }
+    Ok(())
+}
+
+/// Rewrites every source file under `paths` with `codemod`, which returns the new source and the
+/// number of changes. A dry run prints a diff instead of writing. Files that cannot be read,
+/// migrated or written are reported at the end without stopping the others.
+fn run_codemod(
+    paths: &[String],
+    dry_run: bool,
+    mut codemod: impl FnMut(&str, &str) -> Result<(String, usize), String>,
+) -> Result<(), String> {
+    let mut changed_files = 0;
+    let mut changes = 0;
+    let mut errors = vec![];
+    for path in source_files(paths)? {
+        let display = path.display().to_string();
+        let result = fs::read_to_string(&path)
+            .map_err(|error| format!("Could not read '{display}': {error}"))
+            .and_then(|source| {
+                let (output, count) = codemod(&source, &display)?;
+                Ok((source, output, count))
+            });
+        let (source, output, count) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+        if output == source {
+            continue;
+        }
+        if dry_run {
+            print!("{}", unified_diff(&display, &source, &output));
+        } else if let Err(error) = fs::write(&path, output) {
+            errors.push(format!("Could not write '{display}': {error}"));
+            continue;
+        }
+        changed_files += 1;
+        changes += count;
+    }
+    let verb = if dry_run { "Would change" } else { "Changed" };
+    println!("{verb} {changes} phrases in {changed_files} files.");
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}\n{} files could not be changed.",
+            errors.join("\n"),
+            errors.len()
+        ))
+    }
+}
+
+/// Minimal yargs-style option parsing: `--name value`, `--name=value`, `-n value`, boolean
+/// flags, and array options that consume values until the next option.
+struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    fn new(args: Vec<String>) -> Self {
+        Self { args }
+    }
+
+    fn position(&self, names: &[&str]) -> Option<(usize, Option<String>)> {
+        self.args
+            .iter()
+            .position(|arg| is_option(arg, names))
+            .map(|index| {
+                let inline = self.args[index]
+                    .split_once('=')
+                    .map(|(_, value)| value.to_string());
+                (index, inline)
+            })
+    }
+
+    fn flag(&mut self, names: &[&str]) -> Result<bool, String> {
+        let Some((index, inline)) = self.position(names) else {
+            return Ok(false);
+        };
+        self.args.remove(index);
+        match inline.as_deref() {
+            None | Some("true") => Ok(true),
+            Some("false") => Ok(false),
+            Some(value) => Err(format!(
+                "Option '--{}' does not take a value '{value}'.",
+                names[0]
+            )),
+        }
+    }
+
+    fn value(&mut self, names: &[&str]) -> Result<Option<String>, String> {
+        let Some((index, inline)) = self.position(names) else {
+            return Ok(None);
+        };
+        self.args.remove(index);
+        if let Some(value) = inline {
+            return Ok(Some(value));
+        }
+        if index < self.args.len() && !self.args[index].starts_with('-') {
+            return Ok(Some(self.args.remove(index)));
+        }
+        Err(format!("Option '--{}' needs a value.", names[0]))
+    }
+
+    fn values(&mut self, names: &[&str]) -> Vec<String> {
+        let mut values = vec![];
+        while let Some((index, inline)) = self.position(names) {
+            self.args.remove(index);
+            if let Some(value) = inline {
+                values.push(value);
+            }
+            while index < self.args.len() && !self.args[index].starts_with('-') {
+                values.push(self.args.remove(index));
+            }
+        }
+        values
+    }
+
+    /// Returns the remaining positional arguments, failing on unknown options.
+    fn rest(self) -> Result<Vec<String>, String> {
+        match self.args.iter().find(|arg| arg.starts_with('-')) {
+            Some(arg) => Err(format!("Unknown argument: {arg}")),
+            None => Ok(self.args),
+        }
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self.args.first() {
+            Some(arg) => Err(format!("Unknown argument: {arg}")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn is_option(arg: &str, names: &[&str]) -> bool {
+    let name = arg.split_once('=').map_or(arg, |(name, _)| name);
+    names.iter().any(|candidate| {
+        if candidate.len() == 1 {
+            name.strip_prefix('-') == Some(candidate)
+        } else {
+            name.strip_prefix("--") == Some(candidate)
+        }
+    })
+}
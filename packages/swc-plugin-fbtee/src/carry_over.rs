@@ -0,0 +1,242 @@
+use crate::translations::{pattern_tokens, HashToLeaf, Leaf, TranslationGroup};
+use indexmap::{IndexMap, IndexSet};
+use serde::Serialize;
+
+/// Status given to translations copied onto a changed phrase.
+pub const STATUS_NEEDS_REVIEW: &str = "needs-review";
+
+const TEXT_WEIGHT: f64 = 0.5;
+const DESC_WEIGHT: f64 = 0.3;
+const TOKEN_WEIGHT: f64 = 0.2;
+
+/// A removed leaf paired with the added leaf it most likely became.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarryOverMatch {
+    pub old_hash: String,
+    pub new_hash: String,
+    pub score: f64,
+}
+
+/// Pairs leaves that only exist in `old` with leaves that only exist in `new`. Candidates are
+/// scored by text, description and token similarity and paired greedily, best score first.
+pub fn match_changed_leaves(
+    old: &HashToLeaf,
+    new: &HashToLeaf,
+    threshold: f64,
+) -> Vec<CarryOverMatch> {
+    let removed: Vec<(&String, &Leaf)> = old
+        .iter()
+        .filter(|(hash, _)| !new.contains_key(*hash))
+        .collect();
+    let added: Vec<(&String, &Leaf)> = new
+        .iter()
+        .filter(|(hash, _)| !old.contains_key(*hash))
+        .collect();
+
+    let mut candidates = vec![];
+    for (old_index, (_, old_leaf)) in removed.iter().enumerate() {
+        for (new_index, (_, new_leaf)) in added.iter().enumerate() {
+            let score = similarity(old_leaf, new_leaf);
+            if score >= threshold {
+                candidates.push((score, old_index, new_index));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut used_old = IndexSet::new();
+    let mut used_new = IndexSet::new();
+    let mut matches = vec![];
+    for (score, old_index, new_index) in candidates {
+        if used_old.contains(&old_index) || used_new.contains(&new_index) {
+            continue;
+        }
+        used_old.insert(old_index);
+        used_new.insert(new_index);
+        matches.push(CarryOverMatch {
+            old_hash: removed[old_index].0.clone(),
+            new_hash: added[new_index].0.clone(),
+            score: (score * 1000.0).round() / 1000.0,
+        });
+    }
+    matches.sort_by_key(|carry_over| old.get_index_of(&carry_over.old_hash));
+    matches
+}
+
+fn similarity(old: &Leaf, new: &Leaf) -> f64 {
+    let old_tokens = pattern_tokens(&old.text);
+    let new_tokens = pattern_tokens(&new.text);
+    let union = old_tokens.union(&new_tokens).count();
+    let token_similarity = if union == 0 {
+        1.0
+    } else {
+        old_tokens.intersection(&new_tokens).count() as f64 / union as f64
+    };
+    TEXT_WEIGHT * string_similarity(&old.text, &new.text)
+        + DESC_WEIGHT * string_similarity(&old.desc, &new.desc)
+        + TOKEN_WEIGHT * token_similarity
+}
+
+/// One minus the Levenshtein distance over the longer length, compared by character.
+fn string_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Copies translations from old hashes onto their new hashes, marked for review. Existing
+/// translations of the new hash are kept unless they are untranslated placeholders. Returns the
+/// number of entries carried over.
+pub fn carry_over_translations(
+    group: &mut TranslationGroup,
+    matches: &[CarryOverMatch],
+    new: &HashToLeaf,
+) -> usize {
+    let mut carried = 0;
+    for carry_over in matches {
+        let Some(Some(old_entry)) = group.translations.get(&carry_over.old_hash) else {
+            continue;
+        };
+        if old_entry.status.as_deref() == Some("new") || old_entry.translations.is_empty() {
+            continue;
+        }
+        let is_placeholder = match group.translations.get(&carry_over.new_hash) {
+            Some(Some(entry)) => entry.status.as_deref() == Some("new"),
+            _ => true,
+        };
+        if !is_placeholder {
+            continue;
+        }
+        let mut entry = old_entry.clone();
+        entry.status = Some(STATUS_NEEDS_REVIEW.to_string());
+        if let Some(leaf) = new.get(&carry_over.new_hash) {
+            entry.description = Some(leaf.desc.clone());
+        }
+        group
+            .translations
+            .insert(carry_over.new_hash.clone(), Some(entry));
+        carried += 1;
+    }
+    carried
+}
+
+/// The `{oldHash: newHash}` mapping written next to the translation files.
+pub fn hash_mapping(matches: &[CarryOverMatch]) -> IndexMap<String, String> {
+    matches
+        .iter()
+        .map(|carry_over| (carry_over.old_hash.clone(), carry_over.new_hash.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translations::{Translation, TranslationEntry, Translations};
+
+    fn leaves(entries: &[(&str, &str, &str)]) -> HashToLeaf {
+        entries
+            .iter()
+            .map(|(hash, text, desc)| {
+                (
+                    hash.to_string(),
+                    Leaf {
+                        desc: desc.to_string(),
+                        text: text.to_string(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pairs_changed_leaves_by_similarity() {
+        let old = leaves(&[
+            ("same", "Unchanged", "d"),
+            ("typo", "Hello {name}", "A greting"),
+            ("text", "You have {count} new messages", "inbox count"),
+            ("gone", "Completely removed", "other"),
+        ]);
+        let new = leaves(&[
+            ("same", "Unchanged", "d"),
+            ("fixed", "Hello {name}", "A greeting"),
+            ("edited", "You have {count} unread messages", "inbox count"),
+            ("added", "Brand new string", "fresh"),
+        ]);
+        let matches = match_changed_leaves(&old, &new, 0.7);
+        assert_eq!(
+            hash_mapping(&matches),
+            IndexMap::from([
+                ("typo".to_string(), "fixed".to_string()),
+                ("text".to_string(), "edited".to_string()),
+            ])
+        );
+        assert!(matches[0].score > matches[1].score);
+        assert_eq!(string_similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+    }
+
+    #[test]
+    fn carries_translations_for_review() {
+        let entry = |status: Option<&str>, text: &str| {
+            Some(TranslationEntry {
+                status: status.map(str::to_string),
+                translations: vec![Translation {
+                    id: None,
+                    translation: text.to_string(),
+                    variations: Some(IndexMap::new()),
+                }],
+                ..TranslationEntry::default()
+            })
+        };
+        let mut group = TranslationGroup {
+            fb_locale: "de_DE".to_string(),
+            extra: IndexMap::new(),
+            translations: Translations::from([
+                ("typo".to_string(), entry(None, "Hallo {name}")),
+                ("fixed".to_string(), entry(Some("new"), "Hello {name}")),
+                ("untranslated".to_string(), entry(Some("new"), "Bye")),
+                ("reviewed".to_string(), entry(None, "Schon übersetzt")),
+                ("done".to_string(), entry(None, "Tschüss")),
+            ]),
+        };
+        let new = leaves(&[("fixed", "Hello {name}", "A greeting")]);
+        let matches = [
+            ("typo", "fixed"),
+            ("untranslated", "other"),
+            ("done", "reviewed"),
+        ]
+        .map(|(old_hash, new_hash)| CarryOverMatch {
+            old_hash: old_hash.to_string(),
+            new_hash: new_hash.to_string(),
+            score: 1.0,
+        });
+
+        assert_eq!(carry_over_translations(&mut group, &matches, &new), 1);
+        let fixed = group.translations["fixed"].as_ref().unwrap();
+        assert_eq!(fixed.status.as_deref(), Some(STATUS_NEEDS_REVIEW));
+        assert_eq!(fixed.description.as_deref(), Some("A greeting"));
+        assert_eq!(fixed.translations[0].translation, "Hallo {name}");
+        assert!(!group.translations.contains_key("other"));
+        assert_eq!(
+            group.translations["reviewed"]
+                .as_ref()
+                .unwrap()
+                .translations[0]
+                .translation,
+            "Schon übersetzt"
+        );
+    }
+}
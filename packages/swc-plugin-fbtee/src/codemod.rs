@@ -0,0 +1,686 @@
+//! Source-to-source migrations. Codemods parse a file, collect edits as byte ranges of the
+//! original text and splice them in, so code they do not touch keeps its formatting.
+
+use crate::translations::CollectedPhrase;
+use std::{
+    collections::HashMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+use swc_core::{
+    common::{sync::Lrc, BytePos, FileName, SourceMap, Span, Spanned},
+    ecma::{
+        ast::*,
+        parser::{lexer::Lexer, EsSyntax, Parser, StringInput, Syntax, TsSyntax},
+        utils::find_pat_ids,
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+pub mod fbt_form;
+pub mod i18next;
+pub mod react_intl;
+pub mod wrap_jsx_text;
+
+/// File extensions codemods read when given a directory.
+const SOURCE_EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts"];
+
+/// Lines of unchanged context around each hunk of a diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// A parsed source file and the offset of its first byte in the source map.
+pub struct SourceModule {
+    pub module: Module,
+    start: BytePos,
+}
+
+impl SourceModule {
+    pub fn parse(source: &str, filename: &str) -> Result<Self, String> {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(
+            FileName::Custom(filename.to_string()).into(),
+            source.to_string(),
+        );
+        let is_typescript = [".ts", ".tsx", ".mts", ".cts"]
+            .iter()
+            .any(|extension| filename.ends_with(extension));
+        let syntax = if is_typescript {
+            Syntax::Typescript(TsSyntax {
+                tsx: !filename.ends_with(".ts")
+                    && !filename.ends_with(".mts")
+                    && !filename.ends_with(".cts"),
+                ..Default::default()
+            })
+        } else {
+            Syntax::Es(EsSyntax {
+                jsx: true,
+                ..Default::default()
+            })
+        };
+        let lexer = Lexer::new(syntax, EsVersion::latest(), StringInput::from(&*fm), None);
+        let module = Parser::new_from(lexer)
+            .parse_module()
+            .map_err(|error| format!("Could not parse '{filename}': {:?}", error.kind()))?;
+        Ok(Self {
+            module,
+            start: fm.start_pos,
+        })
+    }
+
+    /// The byte range of `span` in the source text.
+    pub fn range(&self, span: Span) -> (usize, usize) {
+        (
+            (span.lo.0 - self.start.0) as usize,
+            (span.hi.0 - self.start.0) as usize,
+        )
+    }
+}
+
+/// Replaces `start..end` of the original source with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl Edit {
+    pub fn new(start: usize, end: usize, text: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+}
+
+/// Applies non-overlapping edits to `source`.
+pub fn apply_edits(source: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|edit| (edit.start, edit.end));
+    let mut output = String::with_capacity(source.len());
+    let mut offset = 0;
+    for edit in edits {
+        debug_assert!(edit.start >= offset, "codemod edits must not overlap");
+        output.push_str(&source[offset..edit.start]);
+        output.push_str(&edit.text);
+        offset = edit.end;
+    }
+    output.push_str(&source[offset..]);
+    output
+}
+
+fn line_number(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+/// Compiles `source` and returns the `hk` of every phrase, in output order.
+pub fn hash_keys(
+    source: &str,
+    filename: &str,
+    config: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let program = compile(source, filename, |module| {
+        crate::transform_program(Program::Module(module), config, Some(filename))
+    })?;
+    let mut collector = HashKeys(vec![]);
+    program.visit_with(&mut collector);
+    Ok(collector.0)
+}
+
+/// Compiles `source` and returns its phrases as `fbtee collect` would, inner phrases first.
+pub fn collect_phrases(
+    source: &str,
+    filename: &str,
+    config: Option<&str>,
+) -> Result<Vec<CollectedPhrase>, String> {
+    compile(source, filename, |module| {
+        crate::collect_phrases(Program::Module(module), config, Some(filename))
+    })
+}
+
+/// Runs the compiler on `source`, turning its errors into `Err`s.
+fn compile<T>(source: &str, filename: &str, run: impl FnOnce(Module) -> T) -> Result<T, String> {
+    let module = SourceModule::parse(source, filename)?.module;
+    panic::catch_unwind(AssertUnwindSafe(|| run(module))).map_err(|error| {
+        let message = error
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| error.downcast_ref::<&str>().copied())
+            .unwrap_or("unknown error");
+        format!("Could not compile '{filename}': {message}")
+    })
+}
+
+struct HashKeys(Vec<String>);
+
+impl Visit for HashKeys {
+    noop_visit_type!();
+
+    fn visit_key_value_prop(&mut self, prop: &KeyValueProp) {
+        if let (Some("hk"), Expr::Lit(Lit::Str(value))) = (
+            crate::prop_name_to_string(&prop.key).as_deref(),
+            prop.value.as_ref(),
+        ) {
+            self.0.push(value.value.to_atom_lossy().to_string());
+        }
+        prop.visit_children_with(self);
+    }
+}
+
+/// Whether the module imports a binding called `name`.
+pub fn binds(module: &Module, name: &str) -> bool {
+    module.body.iter().any(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+            import.specifiers.iter().any(|specifier| {
+                let local = match specifier {
+                    ImportSpecifier::Named(named) => &named.local,
+                    ImportSpecifier::Default(default) => &default.local,
+                    ImportSpecifier::Namespace(namespace) => &namespace.local,
+                };
+                local.sym == name
+            })
+        }
+        _ => false,
+    })
+}
+
+/// `import { fbt } from 'fbtee';` for the given bindings.
+pub fn fbtee_import_statement(names: &[&str]) -> String {
+    format!("import {{ {} }} from 'fbtee';", names.join(", "))
+}
+
+/// Imports `names` from fbtee after the last import, or before the first statement after any
+/// directives.
+pub fn fbtee_import(file: &SourceModule, names: &[&str]) -> Edit {
+    let statement = fbtee_import_statement(names);
+    let items = &file.module.body;
+    let last_import = items
+        .iter()
+        .rev()
+        .find(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))));
+    if let Some(import) = last_import {
+        let (_, end) = file.range(import.span());
+        return Edit::new(end, end, format!("\n{statement}"));
+    }
+    match items
+        .iter()
+        .find(|item| !crate::is_directive_module_item(item))
+    {
+        Some(item) => {
+            let (start, _) = file.range(item.span());
+            Edit::new(start, start, format!("{statement}\n\n"))
+        }
+        None => {
+            let (_, end) = file.range(file.module.span);
+            Edit::new(end, end, format!("\n{statement}\n"))
+        }
+    }
+}
+
+/// The exported name a named specifier imports.
+fn imported_name(named: &ImportNamedSpecifier) -> String {
+    match &named.imported {
+        Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+        Some(ModuleExportName::Str(value)) => value.value.to_atom_lossy().to_string(),
+        None => named.local.sym.to_string(),
+    }
+}
+
+/// How often each identifier occurs in a node, including its declaration.
+#[derive(Default)]
+struct References(HashMap<String, usize>);
+
+impl References {
+    fn count<N: VisitWith<Self>>(node: &N) -> Self {
+        let mut references = Self::default();
+        node.visit_with(&mut references);
+        references
+    }
+
+    fn is_unused(&self, name: &str) -> bool {
+        self.0.get(name).copied().unwrap_or_default() <= 1
+    }
+}
+
+impl Visit for References {
+    fn visit_ident(&mut self, ident: &Ident) {
+        *self.0.entry(ident.sym.to_string()).or_default() += 1;
+    }
+}
+
+/// Removes `start..end`. When it fills whole lines, they go with their indentation, along with
+/// a blank line if it would leave two.
+fn removal(source: &str, start: usize, end: usize) -> Edit {
+    let rest = &source[end..];
+    let Some(newline) = rest
+        .find('\n')
+        .filter(|&newline| rest[..newline].trim().is_empty())
+    else {
+        return Edit::new(start, end, "");
+    };
+    let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+    let start = if source[line_start..start].trim().is_empty() {
+        line_start
+    } else {
+        start
+    };
+    let mut end = end + newline + 1;
+    if source[..start].ends_with("\n\n") && source[end..].starts_with('\n') {
+        end += 1;
+    }
+    Edit::new(start, end, "")
+}
+
+/// Removes declarations initialized by a call to one of `calls`, like `const intl = useIntl();`,
+/// whose bindings a migration left unused.
+fn remove_unused_declarations(file: &SourceModule, source: &str, calls: &[String]) -> String {
+    let mut unused = UnusedBindings {
+        file,
+        source,
+        calls,
+        edits: vec![],
+    };
+    file.module.visit_with(&mut unused);
+    apply_edits(source, unused.edits)
+}
+
+/// Finds declarations initialized by a call to one of `calls` whose bindings are not referenced
+/// anywhere else in their block.
+struct UnusedBindings<'a> {
+    file: &'a SourceModule,
+    source: &'a str,
+    calls: &'a [String],
+    edits: Vec<Edit>,
+}
+
+impl UnusedBindings<'_> {
+    fn check<'s>(&mut self, stmts: impl Iterator<Item = &'s Stmt>, references: &References) {
+        for stmt in stmts {
+            let Stmt::Decl(Decl::Var(var)) = stmt else {
+                continue;
+            };
+            let [VarDeclarator {
+                name,
+                init: Some(init),
+                ..
+            }] = var.decls.as_slice()
+            else {
+                continue;
+            };
+            let is_call = matches!(
+                init.as_ref(),
+                Expr::Call(CallExpr { callee: Callee::Expr(callee), .. })
+                    if matches!(callee.as_ref(), Expr::Ident(callee)
+                        if self.calls.iter().any(|call| *call == *callee.sym))
+            );
+            let bindings: Vec<Ident> = find_pat_ids(name);
+            if is_call
+                && bindings
+                    .iter()
+                    .all(|binding| references.is_unused(&binding.sym))
+            {
+                let (start, end) = self.file.range(stmt.span());
+                self.edits.push(removal(self.source, start, end));
+            }
+        }
+    }
+}
+
+impl Visit for UnusedBindings<'_> {
+    noop_visit_type!();
+
+    fn visit_module(&mut self, module: &Module) {
+        let references = References::count(module);
+        self.check(
+            module.body.iter().filter_map(ModuleItem::as_stmt),
+            &references,
+        );
+        module.visit_children_with(self);
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        let references = References::count(block);
+        self.check(block.stmts.iter(), &references);
+        block.visit_children_with(self);
+    }
+}
+
+/// Drops the specifiers named in `migrated` that the migrated file no longer uses from its
+/// imports of `sources`, and imports the fbtee `modules`, reusing the place of a removed import
+/// when there is one. `default` and `*` in `migrated` name default and namespace imports.
+fn update_imports(
+    source: &str,
+    filename: &str,
+    sources: &[&str],
+    migrated: &[&str],
+    modules: &[&str],
+) -> Result<String, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let references = References::count(&file.module);
+    let text = |span: Span| {
+        let (start, end) = file.range(span);
+        &source[start..end]
+    };
+    let missing: Vec<&str> = modules
+        .iter()
+        .copied()
+        .filter(|module| !binds(&file.module, module))
+        .collect();
+    let mut imported = missing.is_empty();
+    let mut edits = vec![];
+    let imports = file.module.body.iter().filter_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+            if !import.type_only && sources.iter().any(|src| import.src.value == *src) =>
+        {
+            Some(import)
+        }
+        _ => None,
+    });
+    for import in imports {
+        let kept: Vec<&ImportSpecifier> = import
+            .specifiers
+            .iter()
+            .filter(|specifier| {
+                let name = match specifier {
+                    ImportSpecifier::Named(named) => imported_name(named),
+                    ImportSpecifier::Default(_) => "default".to_string(),
+                    ImportSpecifier::Namespace(_) => "*".to_string(),
+                };
+                !migrated.contains(&name.as_str()) || !references.is_unused(&specifier.local().sym)
+            })
+            .collect();
+        if kept.len() == import.specifiers.len() {
+            continue;
+        }
+        let (start, end) = file.range(import.span);
+        if kept.is_empty() {
+            if imported {
+                edits.push(removal(source, start, end));
+            } else {
+                edits.push(Edit::new(start, end, fbtee_import_statement(&missing)));
+                imported = true;
+            }
+            continue;
+        }
+        let mut clauses = vec![];
+        let mut named = vec![];
+        for specifier in kept {
+            match specifier {
+                ImportSpecifier::Named(specifier) => named.push(text(specifier.span)),
+                specifier => clauses.push(text(specifier.span()).to_string()),
+            }
+        }
+        if !named.is_empty() {
+            clauses.push(format!("{{ {} }}", named.join(", ")));
+        }
+        edits.push(Edit::new(
+            start,
+            end,
+            format!(
+                "import {} from {};",
+                clauses.join(", "),
+                text(import.src.span)
+            ),
+        ));
+    }
+    if !imported {
+        edits.push(fbtee_import(&file, &missing));
+    }
+    Ok(apply_edits(source, edits))
+}
+
+/// Quotes `value` as a single-quoted JavaScript string.
+pub fn js_string(value: &str) -> String {
+    let mut output = String::from("'");
+    for ch in value.chars() {
+        match ch {
+            '\\' => output.push_str("\\\\"),
+            '\'' => output.push_str("\\'"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            ch => output.push(ch),
+        }
+    }
+    output.push('\'');
+    output
+}
+
+/// JSX text that reads the same as `text`. Text JSX would collapse, drop or parse differently
+/// becomes a string literal child.
+pub fn jsx_text(text: &str) -> String {
+    let is_plain = !text.trim().is_empty()
+        && !text.contains("  ")
+        && !text.contains(['{', '}', '<', '>', '&'])
+        && !text.chars().any(|ch| ch.is_whitespace() && ch != ' ');
+    if is_plain || text.is_empty() {
+        text.to_string()
+    } else {
+        format!("{{{}}}", js_string(text))
+    }
+}
+
+/// `key` as an object literal key, quoted when it is not an identifier.
+pub fn object_key(key: &str) -> String {
+    if crate::is_valid_ident(key) {
+        key.to_string()
+    } else {
+        js_string(key)
+    }
+}
+
+/// Lists the source files under `paths`, skipping `node_modules` and hidden directories.
+pub fn source_files(paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = vec![];
+    for path in paths {
+        let path = Path::new(path);
+        if path.is_dir() {
+            collect_source_files(path, &mut files)?;
+        } else if path.is_file() {
+            files.push(path.to_path_buf());
+        } else {
+            return Err(format!("No such file or directory: '{}'", path.display()));
+        }
+    }
+    Ok(files)
+}
+
+fn collect_source_files(directory: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|error| format!("Could not read '{}': {error}", directory.display()))?;
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Could not read '{}': {error}", directory.display()))?;
+    paths.sort();
+    for path in paths {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if name != "node_modules" && !name.starts_with('.') {
+                collect_source_files(&path, files)?;
+            }
+        } else if !name.ends_with(".d.ts")
+            && path
+                .extension()
+                .is_some_and(|extension| SOURCE_EXTENSIONS.contains(&&*extension.to_string_lossy()))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// A unified diff between two versions of `path`, or an empty string if they are equal.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let path = path.trim_start_matches("./").trim_start_matches('/');
+    let mut output = format!("--- a/{path}\n+++ b/{path}\n");
+    let mut index = 0;
+    while let Some(first_change) = ops[index..]
+        .iter()
+        .position(|op| !matches!(op, DiffOp::Equal(_)))
+        .map(|position| index + position)
+    {
+        let start = first_change.saturating_sub(DIFF_CONTEXT);
+        // A hunk ends once more than two contexts' worth of unchanged lines follow a change.
+        let mut end = first_change;
+        let mut unchanged = 0;
+        while end < ops.len() && unchanged <= DIFF_CONTEXT * 2 {
+            if matches!(ops[end], DiffOp::Equal(_)) {
+                unchanged += 1;
+            } else {
+                unchanged = 0;
+            }
+            end += 1;
+        }
+        let end = end - unchanged.saturating_sub(DIFF_CONTEXT);
+        let hunk = &ops[start..end];
+        let (old_start, new_start) = hunk_start(&ops[..start]);
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Delete(_)))
+            .count();
+        output.push_str(&format!(
+            "@@ -{},{old_count} +{},{new_count} @@\n",
+            old_start + usize::from(old_count > 0),
+            new_start + usize::from(new_count > 0),
+        ));
+        for op in hunk {
+            match *op {
+                DiffOp::Equal(old) => output.push_str(&format!(" {}\n", old_lines[old])),
+                DiffOp::Delete(old) => output.push_str(&format!("-{}\n", old_lines[old])),
+                DiffOp::Insert(new) => output.push_str(&format!("+{}\n", new_lines[new])),
+            }
+        }
+        index = end;
+    }
+    output
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DiffOp {
+    Equal(usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Line numbers before the first line of a hunk, in the old and the new file.
+fn hunk_start(before: &[DiffOp]) -> (usize, usize) {
+    before.iter().fold((0, 0), |(old, new), op| match op {
+        DiffOp::Equal(_) => (old + 1, new + 1),
+        DiffOp::Delete(_) => (old + 1, new),
+        DiffOp::Insert(_) => (old, new + 1),
+    })
+}
+
+/// A longest-common-subsequence line diff. Shared leading and trailing lines are matched first,
+/// so the quadratic table only covers the changed middle of the file.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let columns = new_middle.len() + 1;
+    let mut lengths = vec![0u32; (old_middle.len() + 1) * columns];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lengths[i * columns + j] = if old_middle[i] == new_middle[j] {
+                lengths[(i + 1) * columns + j + 1] + 1
+            } else {
+                lengths[(i + 1) * columns + j].max(lengths[i * columns + j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(DiffOp::Equal).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() || j < new_middle.len() {
+        if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+            ops.push(DiffOp::Equal(prefix + i));
+            i += 1;
+            j += 1;
+        } else if i < old_middle.len()
+            && (j == new_middle.len()
+                || lengths[(i + 1) * columns + j] >= lengths[i * columns + j + 1])
+        {
+            ops.push(DiffOp::Delete(prefix + i));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(prefix + j));
+            j += 1;
+        }
+    }
+    let old_suffix = old.len() - suffix;
+    ops.extend((0..suffix).map(|line| DiffOp::Equal(old_suffix + line)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_edits_in_source_order() {
+        assert_eq!(
+            apply_edits(
+                "let a = 1; let b = 2;",
+                vec![Edit::new(19, 20, "3"), Edit::new(8, 9, "'x'")]
+            ),
+            "let a = 'x'; let b = 3;"
+        );
+    }
+
+    #[test]
+    fn writes_unified_diffs_with_context() {
+        let old = (1..=12)
+            .map(|line| format!("line {line}\n"))
+            .collect::<String>();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 11\n", "line 11\nline 11b\n");
+        assert_eq!(
+            unified_diff("src/a.js", &old, &new),
+            "--- a/src/a.js
++++ b/src/a.js
+@@ -1,5 +1,5 @@
+ line 1
+-line 2
++line two
+ line 3
+ line 4
+ line 5
+@@ -9,4 +9,5 @@
+ line 9
+ line 10
+ line 11
++line 11b
+ line 12
+"
+        );
+        assert_eq!(unified_diff("src/a.js", &old, &old), "");
+    }
+}
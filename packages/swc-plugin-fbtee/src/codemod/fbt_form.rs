@@ -0,0 +1,813 @@
+//! Converts phrases between the `fbt(...)` call form and the `<fbt>` JSX form. A callsite is only
+//! rewritten when the file still compiles to the same `hk`s, so existing translations keep working.
+
+use super::{
+    apply_edits, hash_keys, js_string, jsx_text, line_number, object_key, Edit, SourceModule,
+};
+use crate::{clean_jsx_text, normalize_spaces, unwrap_static_expr};
+use std::str::FromStr;
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::*,
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FbtForm {
+    Call,
+    Jsx,
+}
+
+impl FromStr for FbtForm {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "call" => Ok(Self::Call),
+            "jsx" => Ok(Self::Jsx),
+            _ => Err(format!(
+                "Invalid form '{value}'. Expected one of: call, jsx."
+            )),
+        }
+    }
+}
+
+/// The rewritten source, the number of converted phrases, and why others were left alone.
+#[derive(Debug)]
+pub struct ConvertedSource {
+    pub source: String,
+    pub converted: usize,
+    pub skipped: Vec<String>,
+}
+
+/// Rewrites every `fbt`/`fbs` phrase in `source` to `form`. `config` is the plugin config used to
+/// compile the file, which must include any `fbtCommon` strings or enum manifests it needs.
+pub fn convert_fbt_form(
+    source: &str,
+    filename: &str,
+    form: FbtForm,
+    config: Option<&str>,
+) -> Result<ConvertedSource, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let supports_jsx = ![".ts", ".mts", ".cts"]
+        .iter()
+        .any(|extension| filename.ends_with(extension));
+    if form == FbtForm::Jsx && !supports_jsx {
+        return Ok(ConvertedSource {
+            source: source.to_string(),
+            converted: 0,
+            skipped: vec![],
+        });
+    }
+
+    let mut converter = Converter {
+        source,
+        filename,
+        file: &file,
+        form,
+        edits: vec![],
+        skipped: vec![],
+    };
+    file.module.visit_with(&mut converter);
+    let Converter {
+        edits, mut skipped, ..
+    } = converter;
+    if edits.is_empty() {
+        return Ok(ConvertedSource {
+            source: source.to_string(),
+            converted: 0,
+            skipped,
+        });
+    }
+
+    let expected = hash_keys(source, filename, config)?;
+    let mut kept = vec![];
+    for edit in edits {
+        let output = apply_edits(source, vec![edit.clone()]);
+        match hash_keys(&output, filename, config) {
+            Ok(keys) if keys == expected => kept.push(edit),
+            Ok(_) => skipped.push(format!(
+                "{filename}:{}: converting this phrase would change its hk.",
+                line_number(source, edit.start)
+            )),
+            Err(error) => skipped.push(format!(
+                "{filename}:{}: the converted phrase does not compile. {error}",
+                line_number(source, edit.start)
+            )),
+        }
+    }
+    let output = apply_edits(source, kept.clone());
+    if hash_keys(&output, filename, config)? != expected {
+        return Err(format!(
+            "Converting '{filename}' would change its hks. No changes were made."
+        ));
+    }
+    Ok(ConvertedSource {
+        source: output,
+        converted: kept.len(),
+        skipped,
+    })
+}
+
+struct Converter<'a> {
+    source: &'a str,
+    filename: &'a str,
+    file: &'a SourceModule,
+    form: FbtForm,
+    edits: Vec<Edit>,
+    skipped: Vec<String>,
+}
+
+impl Converter<'_> {
+    fn text(&self, span: Span) -> &str {
+        let (start, end) = self.file.range(span);
+        &self.source[start..end]
+    }
+
+    fn skip(&mut self, span: Span, reason: &str) {
+        let (start, _) = self.file.range(span);
+        self.skipped.push(format!(
+            "{}:{}: {reason}",
+            self.filename,
+            line_number(self.source, start)
+        ));
+    }
+
+    fn replace(&mut self, span: Span, text: String) {
+        let (start, end) = self.file.range(span);
+        self.edits.push(Edit::new(start, end, text));
+    }
+
+    // Call form to JSX.
+
+    fn call_to_jsx(&self, call: &CallExpr, module: &str) -> Result<String, &'static str> {
+        if call.args.iter().any(|arg| arg.spread.is_some()) || !(2..=3).contains(&call.args.len()) {
+            return Err("spread or missing arguments cannot be converted.");
+        }
+        let mut attrs = vec![self.jsx_attr("desc", &call.args[1].expr)];
+        if let Some(options) = call.args.get(2) {
+            attrs.extend(self.jsx_option_attrs(&options.expr)?);
+        }
+        let children = self.jsx_children(&call.args[0].expr, module)?;
+        Ok(format!(
+            "<{module} {}>{}</{module}>",
+            attrs.join(" "),
+            children.trim()
+        ))
+    }
+
+    fn jsx_children(&self, expr: &Expr, module: &str) -> Result<String, &'static str> {
+        match expr {
+            Expr::Lit(Lit::Str(value)) => Ok(jsx_text(&value.value.to_atom_lossy())),
+            Expr::Paren(paren) => self.jsx_children(&paren.expr, module),
+            Expr::Array(array) => {
+                array
+                    .elems
+                    .iter()
+                    .try_fold(String::new(), |output, elem| match elem {
+                        Some(ExprOrSpread { spread: None, expr }) => {
+                            Ok(output + self.jsx_children(expr, module)?.as_str())
+                        }
+                        _ => Err("array holes and spreads cannot be converted."),
+                    })
+            }
+            Expr::Bin(binary) if binary.op == BinaryOp::Add => Ok(format!(
+                "{}{}",
+                self.jsx_children(&binary.left, module)?,
+                self.jsx_children(&binary.right, module)?
+            )),
+            Expr::Tpl(template) => {
+                let mut output = String::new();
+                for (index, quasi) in template.quasis.iter().enumerate() {
+                    let cooked = quasi
+                        .cooked
+                        .as_ref()
+                        .map(|cooked| cooked.to_atom_lossy().to_string())
+                        .unwrap_or_else(|| quasi.raw.to_string());
+                    output.push_str(&jsx_text(&cooked));
+                    if let Some(expr) = template.exprs.get(index) {
+                        output.push_str(&self.jsx_children(expr, module)?);
+                    }
+                }
+                Ok(output)
+            }
+            Expr::Call(call) => self.construct_to_jsx(call, module),
+            Expr::JSXElement(_) | Expr::JSXFragment(_) => Ok(self.text(expr.span()).to_string()),
+            _ => Err("the text contains syntax that JSX cannot express."),
+        }
+    }
+
+    fn construct_to_jsx(&self, call: &CallExpr, module: &str) -> Result<String, &'static str> {
+        let Some(method) = construct_method(call, module) else {
+            return Err("the text contains a function call that is not a construct.");
+        };
+        if call.args.iter().any(|arg| arg.spread.is_some()) {
+            return Err("spread arguments cannot be converted.");
+        }
+        let arg = |index: usize| call.args.get(index).map(|arg| arg.expr.as_ref());
+        let required = |index: usize| arg(index).ok_or("a construct is missing arguments.");
+        let value = |expr: &Expr| format!("{{{}}}", self.text(expr.span()));
+        let element = |name: &str, attrs: Vec<String>, children: Option<String>| match children {
+            Some(children) => {
+                format!(
+                    "<{module}:{name} {}>{children}</{module}:{name}>",
+                    attrs.join(" ")
+                )
+            }
+            None => format!("<{module}:{name} {} />", attrs.join(" ")),
+        };
+        let options = |index: usize| match arg(index) {
+            Some(options) => self.jsx_option_attrs(options),
+            None => Ok(vec![]),
+        };
+        Ok(match method {
+            "param" => {
+                let mut attrs = vec![self.jsx_attr("name", required(0)?)];
+                attrs.extend(options(2)?);
+                element("param", attrs, Some(value(required(1)?)))
+            }
+            "sameParam" => element(
+                "same-param",
+                vec![self.jsx_attr("name", required(0)?)],
+                None,
+            ),
+            "name" => element(
+                "name",
+                vec![
+                    self.jsx_attr("name", required(0)?),
+                    self.jsx_attr("gender", required(2)?),
+                ],
+                Some(value(required(1)?)),
+            ),
+            "enum" => element(
+                "enum",
+                vec![
+                    self.jsx_attr("enum-range", required(1)?),
+                    self.jsx_attr("value", required(0)?),
+                ],
+                None,
+            ),
+            "plural" => {
+                let singular = match unwrap_static_expr(required(0)?) {
+                    Expr::Lit(Lit::Str(value)) => jsx_text(&value.value.to_atom_lossy()),
+                    _ => return Err("plural text must be a string literal."),
+                };
+                let mut attrs = vec![self.jsx_attr("count", required(1)?)];
+                attrs.extend(options(2)?);
+                element("plural", attrs, Some(singular))
+            }
+            "pronoun" => {
+                let mut attrs = vec![
+                    self.jsx_attr("type", required(0)?),
+                    self.jsx_attr("gender", required(1)?),
+                ];
+                attrs.extend(options(2)?);
+                element("pronoun", attrs, None)
+            }
+            "list" => {
+                let mut attrs = vec![
+                    self.jsx_attr("name", required(0)?),
+                    self.jsx_attr("items", required(1)?),
+                ];
+                for (index, key) in [(2, "conjunction"), (3, "delimiter")] {
+                    if let Some(expr) = arg(index).filter(|expr| !is_undefined(expr)) {
+                        attrs.push(self.jsx_attr(key, expr));
+                    }
+                }
+                element("list", attrs, None)
+            }
+            _ => return Err("the text contains an unsupported construct."),
+        })
+    }
+
+    /// `key="value"` for plain strings, `key={expr}` for everything else.
+    fn jsx_attr(&self, key: &str, expr: &Expr) -> String {
+        match expr {
+            Expr::Lit(Lit::Str(value)) => {
+                let value = value.value.to_atom_lossy();
+                if value.contains(['"', '&', '\\', '\n', '\r']) {
+                    format!("{key}={{{}}}", self.text(expr.span()))
+                } else {
+                    format!("{key}=\"{value}\"")
+                }
+            }
+            _ => format!("{key}={{{}}}", self.text(expr.span())),
+        }
+    }
+
+    fn jsx_option_attrs(&self, expr: &Expr) -> Result<Vec<String>, &'static str> {
+        let Expr::Object(object) = expr else {
+            return Err("options must be an object literal.");
+        };
+        let mut attrs = vec![];
+        for prop in &object.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                return Err("options with spreads cannot be converted.");
+            };
+            match prop.as_ref() {
+                Prop::KeyValue(prop) => {
+                    let key = crate::prop_name_to_string(&prop.key)
+                        .ok_or("options with computed keys cannot be converted.")?;
+                    attrs.push(self.jsx_attr(&key, &prop.value));
+                }
+                Prop::Shorthand(ident) => attrs.push(format!("{0}={{{0}}}", ident.sym)),
+                _ => return Err("options with methods or accessors cannot be converted."),
+            }
+        }
+        Ok(attrs)
+    }
+
+    // JSX form to calls.
+
+    fn jsx_to_call(&self, element: &JSXElement, module: &str) -> Result<String, &'static str> {
+        let mut desc = None;
+        let mut options = vec![];
+        let mut preserve_whitespace = false;
+        for attr in &element.opening.attrs {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                return Err("spread attributes cannot be converted.");
+            };
+            let JSXAttrName::Ident(name) = &attr.name else {
+                return Err("namespaced attributes cannot be converted.");
+            };
+            let value = self.attr_value(attr.value.as_ref())?;
+            match name.sym.as_ref() {
+                "desc" => desc = Some(value),
+                "common" => return Err("common strings are only supported in JSX."),
+                key => {
+                    if key == "preserveWhitespace" {
+                        preserve_whitespace = value == "true" || value == "'true'";
+                    }
+                    options.push(format!("{}: {value}", object_key(key)));
+                }
+            }
+        }
+        let desc = desc.ok_or("the element has no description.")?;
+
+        let mut parts = vec![];
+        for child in &element.children {
+            self.call_parts(child, module, preserve_whitespace, &mut parts)?;
+        }
+        let parts = trim_call_parts(parts);
+        let text = match parts.as_slice() {
+            [] => "''".to_string(),
+            [part] => part.source(),
+            parts => format!(
+                "[{}]",
+                parts
+                    .iter()
+                    .map(CallPart::source)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut args = vec![text, desc];
+        if !options.is_empty() {
+            args.push(format!("{{ {} }}", options.join(", ")));
+        }
+        Ok(format!("{module}({})", args.join(", ")))
+    }
+
+    fn call_parts(
+        &self,
+        child: &JSXElementChild,
+        module: &str,
+        preserve_whitespace: bool,
+        parts: &mut Vec<CallPart>,
+    ) -> Result<(), &'static str> {
+        match child {
+            JSXElementChild::JSXText(text) => {
+                let text = if preserve_whitespace {
+                    clean_jsx_text(&text.value)
+                } else {
+                    normalize_spaces(&text.value, false)
+                };
+                if !text.trim().is_empty() {
+                    push_text(parts, &text);
+                }
+            }
+            JSXElementChild::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => match unwrap_static_expr(expr) {
+                    Expr::Lit(Lit::Str(value)) => push_text(parts, &value.value.to_atom_lossy()),
+                    _ => parts.push(CallPart::Code(self.text(expr.span()).to_string())),
+                },
+                JSXExpr::JSXEmptyExpr(_) => {}
+            },
+            JSXElementChild::JSXElement(element) => {
+                let code = match construct_element_kind(&element.opening.name, module) {
+                    Some(kind) => self.construct_to_call(element, module, kind)?,
+                    None => self.text(element.span).to_string(),
+                };
+                parts.push(CallPart::Code(code));
+            }
+            JSXElementChild::JSXFragment(fragment) => {
+                parts.push(CallPart::Code(self.text(fragment.span).to_string()))
+            }
+            JSXElementChild::JSXSpreadChild(_) => {
+                return Err("spread children cannot be converted.")
+            }
+        }
+        Ok(())
+    }
+
+    fn construct_to_call(
+        &self,
+        element: &JSXElement,
+        module: &str,
+        kind: &str,
+    ) -> Result<String, &'static str> {
+        let mut attrs = vec![];
+        for attr in &element.opening.attrs {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                return Err("spread attributes cannot be converted.");
+            };
+            let JSXAttrName::Ident(name) = &attr.name else {
+                return Err("namespaced attributes cannot be converted.");
+            };
+            attrs.push((name.sym.to_string(), self.attr_value(attr.value.as_ref())?));
+        }
+        let call =
+            |method: &str, args: Vec<String>| format!("{module}.{method}({})", args.join(", "));
+
+        let call = match kind {
+            "param" => {
+                let name = required_attr(&mut attrs, "name")?;
+                let value = self.param_value(&element.children)?;
+                with_options(call("param", vec![name, value]), attrs)
+            }
+            "same-param" | "sameParam" => {
+                call("sameParam", vec![required_attr(&mut attrs, "name")?])
+            }
+            "name" => {
+                let name = required_attr(&mut attrs, "name")?;
+                let gender = required_attr(&mut attrs, "gender")?;
+                call(
+                    "name",
+                    vec![name, self.param_value(&element.children)?, gender],
+                )
+            }
+            "enum" => {
+                let value = required_attr(&mut attrs, "value")?;
+                let range = required_attr(&mut attrs, "enum-range")?;
+                call("enum", vec![value, range])
+            }
+            "plural" => {
+                let count = required_attr(&mut attrs, "count")?;
+                let singular = normalize_spaces(&crate::jsx_text_content(&element.children), false)
+                    .trim()
+                    .to_string();
+                with_options(call("plural", vec![js_string(&singular), count]), attrs)
+            }
+            "pronoun" => {
+                let usage = required_attr(&mut attrs, "type")?;
+                let gender = required_attr(&mut attrs, "gender")?;
+                with_options(call("pronoun", vec![usage, gender]), attrs)
+            }
+            "list" => {
+                let name = required_attr(&mut attrs, "name")?;
+                let items = required_attr(&mut attrs, "items")?;
+                let conjunction = take_attr(&mut attrs, "conjunction");
+                let delimiter = take_attr(&mut attrs, "delimiter");
+                let mut args = vec![name, items];
+                match (conjunction, delimiter) {
+                    (conjunction, Some(delimiter)) => {
+                        args.push(conjunction.unwrap_or_else(|| "undefined".to_string()));
+                        args.push(delimiter);
+                    }
+                    (Some(conjunction), None) => args.push(conjunction),
+                    (None, None) => {}
+                }
+                call("list", args)
+            }
+            _ => return Err("the phrase contains an unsupported construct."),
+        };
+        Ok(call)
+    }
+
+    /// The value of a `<fbt:param>` or `<fbt:name>`: its only child, or a fragment of them all.
+    fn param_value(&self, children: &[JSXElementChild]) -> Result<String, &'static str> {
+        let mut meaningful = children.iter().filter(|child| match child {
+            JSXElementChild::JSXText(text) => !text.value.trim().is_empty(),
+            JSXElementChild::JSXExprContainer(container) => {
+                !matches!(container.expr, JSXExpr::JSXEmptyExpr(_))
+            }
+            _ => true,
+        });
+        match (meaningful.next(), meaningful.next()) {
+            (
+                Some(JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })),
+                None,
+            ) => Ok(self.text(expr.span()).to_string()),
+            (Some(JSXElementChild::JSXElement(element)), None) => {
+                Ok(self.text(element.span).to_string())
+            }
+            (None, _) => Err("a param has no value."),
+            _ => {
+                let children = children
+                    .iter()
+                    .map(|child| self.text(child.span()))
+                    .collect::<String>();
+                Ok(format!("<>{children}</>"))
+            }
+        }
+    }
+
+    fn attr_value(&self, value: Option<&JSXAttrValue>) -> Result<String, &'static str> {
+        match value {
+            None => Ok("true".to_string()),
+            Some(JSXAttrValue::Str(value)) => Ok(js_string(&value.value.to_atom_lossy())),
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })) => Ok(self.text(expr.span()).to_string()),
+            _ => Err("an attribute has an unsupported value."),
+        }
+    }
+}
+
+impl Visit for Converter<'_> {
+    noop_visit_type!();
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if self.form == FbtForm::Jsx {
+            if let Some(module) = phrase_call_module(call) {
+                match self.call_to_jsx(call, module) {
+                    Ok(element) => self.replace(call.span, element),
+                    Err(reason) => self.skip(call.span, reason),
+                }
+                return;
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        // `fbt(...).toString()` would need parentheses around JSX.
+        if let Expr::Call(call) = member.obj.as_ref() {
+            if phrase_call_module(call).is_some() {
+                call.args.visit_with(self);
+                member.prop.visit_with(self);
+                return;
+            }
+        }
+        member.visit_children_with(self);
+    }
+
+    fn visit_jsx_element_child(&mut self, child: &JSXElementChild) {
+        match (self.form, child) {
+            (FbtForm::Jsx, JSXElementChild::JSXExprContainer(container)) => {
+                if let JSXExpr::Expr(expr) = &container.expr {
+                    if let Expr::Call(call) = unwrap_parens(expr) {
+                        if let Some(module) = phrase_call_module(call) {
+                            match self.call_to_jsx(call, module) {
+                                Ok(element) => self.replace(container.span, element),
+                                Err(reason) => self.skip(call.span, reason),
+                            }
+                            return;
+                        }
+                    }
+                }
+            }
+            (FbtForm::Call, JSXElementChild::JSXElement(element)) => {
+                if let Some(module) = phrase_element_module(&element.opening.name) {
+                    match self.jsx_to_call(element, module) {
+                        Ok(call) => self.replace(element.span, format!("{{{call}}}")),
+                        Err(reason) => self.skip(element.span, reason),
+                    }
+                    return;
+                }
+            }
+            _ => {}
+        }
+        child.visit_children_with(self);
+    }
+
+    fn visit_jsx_element(&mut self, element: &JSXElement) {
+        if self.form == FbtForm::Call {
+            if let Some(module) = phrase_element_module(&element.opening.name) {
+                match self.jsx_to_call(element, module) {
+                    Ok(call) => self.replace(element.span, call),
+                    Err(reason) => self.skip(element.span, reason),
+                }
+                return;
+            }
+        }
+        element.visit_children_with(self);
+    }
+}
+
+enum CallPart {
+    Text(String),
+    Code(String),
+}
+
+impl CallPart {
+    fn source(&self) -> String {
+        match self {
+            Self::Text(text) => js_string(text),
+            Self::Code(code) => code.clone(),
+        }
+    }
+}
+
+fn push_text(parts: &mut Vec<CallPart>, text: &str) {
+    match parts.last_mut() {
+        Some(CallPart::Text(last)) => last.push_str(text),
+        _ => parts.push(CallPart::Text(text.to_string())),
+    }
+}
+
+/// Phrase text is trimmed when it is hashed, so the whitespace around it is only layout.
+fn trim_call_parts(mut parts: Vec<CallPart>) -> Vec<CallPart> {
+    if let Some(CallPart::Text(first)) = parts.first_mut() {
+        *first = first.trim_start().to_string();
+    }
+    if let Some(CallPart::Text(last)) = parts.last_mut() {
+        *last = last.trim_end().to_string();
+    }
+    parts.retain(|part| !matches!(part, CallPart::Text(text) if text.is_empty()));
+    parts
+}
+
+fn take_attr(attrs: &mut Vec<(String, String)>, key: &str) -> Option<String> {
+    attrs
+        .iter()
+        .position(|(name, _)| name == key)
+        .map(|index| attrs.remove(index).1)
+}
+
+fn required_attr(attrs: &mut Vec<(String, String)>, key: &str) -> Result<String, &'static str> {
+    take_attr(attrs, key).ok_or("a construct is missing attributes.")
+}
+
+fn with_options(call: String, options: Vec<(String, String)>) -> String {
+    if options.is_empty() {
+        return call;
+    }
+    let options = options
+        .iter()
+        .map(|(key, value)| format!("{}: {value}", object_key(key)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{}, {{ {options} }})", &call[..call.len() - 1])
+}
+
+fn is_undefined(expr: &Expr) -> bool {
+    matches!(expr, Expr::Ident(ident) if ident.sym == "undefined")
+}
+
+fn unwrap_parens(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => unwrap_parens(&paren.expr),
+        expr => expr,
+    }
+}
+
+/// `fbt(...)` or `fbs(...)`, but not `fbt.c(...)` or a construct.
+fn phrase_call_module(call: &CallExpr) -> Option<&'static str> {
+    match &call.callee {
+        Callee::Expr(callee) => match callee.as_ref() {
+            Expr::Ident(ident) if ident.sym == "fbt" => Some("fbt"),
+            Expr::Ident(ident) if ident.sym == "fbs" => Some("fbs"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn phrase_element_module(name: &JSXElementName) -> Option<&'static str> {
+    match name {
+        JSXElementName::Ident(ident) if ident.sym == "fbt" => Some("fbt"),
+        JSXElementName::Ident(ident) if ident.sym == "fbs" => Some("fbs"),
+        _ => None,
+    }
+}
+
+fn construct_method<'a>(call: &'a CallExpr, module: &str) -> Option<&'a str> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Member(member) = callee.as_ref() else {
+        return None;
+    };
+    match (member.obj.as_ref(), &member.prop) {
+        (Expr::Ident(object), MemberProp::Ident(method)) if object.sym == module => {
+            Some(method.sym.as_ref())
+        }
+        _ => None,
+    }
+}
+
+fn construct_element_kind<'a>(name: &'a JSXElementName, module: &str) -> Option<&'a str> {
+    match name {
+        JSXElementName::JSXNamespacedName(namespaced) if namespaced.ns.sym == module => {
+            Some(namespaced.name.sym.as_ref())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(source: &str, form: FbtForm) -> ConvertedSource {
+        convert_fbt_form(source, "App.tsx", form, None).unwrap()
+    }
+
+    #[test]
+    fn converts_calls_with_constructs_to_jsx() {
+        let source = "import { fbt } from 'fbtee';
+const a = fbt(
+  ['Hello ', fbt.param('name', user.name), ', you have ', fbt.plural('photo', count, { many: 'photos', showCount: 'yes' }), ' of ', fbt.pronoun('possessive', gender), ' {cat}.'],
+  'Greeting',
+  { project: 'home' },
+);
+";
+        let converted = convert(source, FbtForm::Jsx);
+        assert_eq!(converted.converted, 1, "{:?}", converted.skipped);
+        assert_eq!(
+            converted.source,
+            "import { fbt } from 'fbtee';
+const a = <fbt desc=\"Greeting\" project=\"home\">Hello <fbt:param name=\"name\">{user.name}</fbt:param>, you have <fbt:plural count={count} many=\"photos\" showCount=\"yes\">photo</fbt:plural> of <fbt:pronoun type=\"possessive\" gender={gender} />{' {cat}.'}</fbt>;
+"
+        );
+    }
+
+    #[test]
+    fn converts_jsx_with_constructs_to_calls() {
+        let source = "import { fbt } from 'fbtee';
+const a = (
+  <div>
+    <fbt desc=\"Shared items\" subject={viewer.gender}>
+      <fbt:name name=\"owner\" gender={owner.gender}>{owner.name}</fbt:name> shared
+      <fbt:list name=\"items\" items={items} conjunction=\"or\" /> as
+      <fbt:enum enum-range={['photos', 'videos']} value={kind} /> with
+      <fbt:same-param name=\"owner\" />.
+    </fbt>
+  </div>
+);
+";
+        let converted = convert(source, FbtForm::Call);
+        assert_eq!(converted.converted, 1, "{:?}", converted.skipped);
+        assert_eq!(
+            converted.source,
+            "import { fbt } from 'fbtee';
+const a = (
+  <div>
+    {fbt([fbt.name('owner', owner.name, owner.gender), ' shared ', fbt.list('items', items, 'or'), ' as ', fbt.enum(kind, ['photos', 'videos']), ' with ', fbt.sameParam('owner'), '.'], 'Shared items', { subject: viewer.gender })}
+  </div>
+);
+"
+        );
+    }
+
+    #[test]
+    fn round_trips_without_changing_hash_keys() {
+        let source = "import { fbt } from 'fbtee';
+const a = fbt('Save draft', 'Button label');
+const b = <p>{fbt(['Sent ', fbt.param('count', n, { number: true }), ' messages'], 'Status')}</p>;
+const c = fbt(`Hi ${fbt.param('name', name)}`, 'Greeting', { preserveWhitespace: true });
+";
+        let keys = hash_keys(source, "App.tsx", None).unwrap();
+        let jsx = convert(source, FbtForm::Jsx);
+        assert_eq!(jsx.converted, 3, "{:?}", jsx.skipped);
+        assert!(
+            jsx.source.contains("<p><fbt desc=\"Status\">"),
+            "{}",
+            jsx.source
+        );
+        let call = convert(&jsx.source, FbtForm::Call);
+        assert_eq!(call.converted, 3, "{:?}", call.skipped);
+        assert_eq!(hash_keys(&call.source, "App.tsx", None).unwrap(), keys);
+    }
+
+    #[test]
+    fn leaves_phrases_that_cannot_be_converted() {
+        let source = "import { fbt } from 'fbtee';
+const a = fbt('Hello', 'Greeting', options);
+const b = fbt('Hi', 'Greeting').toString();
+const c = <fbt common>Cancel</fbt>;
+";
+        let jsx = convert(source, FbtForm::Jsx);
+        assert_eq!(jsx.converted, 0);
+        assert_eq!(jsx.source, source);
+        assert_eq!(
+            jsx.skipped,
+            ["App.tsx:2: options must be an object literal."]
+        );
+
+        let ts = convert_fbt_form(
+            "const a = fbt('Hi', 'Greeting');",
+            "a.ts",
+            FbtForm::Jsx,
+            None,
+        );
+        assert_eq!(ts.unwrap().converted, 0);
+    }
+}
@@ -0,0 +1,1008 @@
+//! Migrates i18next `t('key')` calls to fbtee by inlining the source-language catalog. Each call
+//! becomes an `fbt()` phrase in JSX children and an `fbs()` phrase elsewhere, described by its
+//! key path. `{{var}}` interpolations become params and `_one`/`_other` keys become a plural.
+//! Only `t` functions and instances bound from `i18next` or `useTranslation()` are migrated.
+
+use super::{
+    apply_edits, collect_phrases, fbtee_import_statement, imported_name, js_string, line_number,
+    remove_unused_declarations, update_imports, Edit, SourceModule,
+};
+use crate::{
+    icu::{icu_nodes_to_translations, level_arguments, IcuNode},
+    translations::{CollectedPhrase, TranslationGroup, Translations},
+};
+use indexmap::IndexMap;
+use serde_json::Value;
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::*,
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+/// i18next resources flattened to `a.b.c` keys.
+pub type Catalog = IndexMap<String, String>;
+
+/// The plural suffixes i18next appends to keys, one per CLDR plural category.
+const PLURAL_SUFFIXES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+/// i18next options that change which resource `t()` returns.
+const UNSUPPORTED_OPTIONS: &[&str] = &["context", "returnObjects", "lng", "ns"];
+
+const I18NEXT: &str = "i18next";
+const REACT_I18NEXT: &str = "react-i18next";
+
+/// i18next imports the migration makes unnecessary, including default and namespace imports of
+/// an instance.
+const MIGRATED_IMPORTS: &[&str] = &["t", "useTranslation", "default", "*"];
+
+pub struct MigratedSource {
+    pub source: String,
+    /// The number of `t()` calls rewritten as phrases.
+    pub migrated: usize,
+    pub messages: Vec<MigratedMessage>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigratedMessage {
+    /// The catalog key, without a plural suffix.
+    pub key: String,
+    phrase: CollectedPhrase,
+    /// The source text by plural suffix, or under `""` for messages without plural forms.
+    forms: IndexMap<String, String>,
+}
+
+/// Flattens nested i18next resources into `a.b.c` keys. Values that are not strings are ignored.
+pub fn flatten_catalog(resources: &Value) -> Catalog {
+    let mut catalog = Catalog::new();
+    flatten(resources, "", &mut catalog);
+    catalog
+}
+
+fn flatten(value: &Value, prefix: &str, catalog: &mut Catalog) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(value, &key, catalog);
+            }
+        }
+        Value::String(text) => {
+            catalog.insert(prefix.to_string(), text.clone());
+        }
+        _ => {}
+    }
+}
+
+/// Rewrites the `t()` calls in `source` whose keys are in `catalog`, the source-language
+/// resources. Calls fbt cannot express are left alone and reported in `skipped`.
+pub fn migrate_i18next(
+    source: &str,
+    filename: &str,
+    catalog: &Catalog,
+    config: Option<&str>,
+) -> Result<MigratedSource, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let bindings = TBindings::find(&file.module);
+    let mut migrator = Migrator {
+        source,
+        filename,
+        config,
+        catalog,
+        file: &file,
+        bindings: &bindings,
+        edits: vec![],
+        modules: vec![],
+        messages: vec![],
+        skipped: vec![],
+    };
+    file.module.visit_with(&mut migrator);
+    let Migrator {
+        edits,
+        modules,
+        messages,
+        skipped,
+        ..
+    } = migrator;
+    if edits.is_empty() {
+        return Ok(MigratedSource {
+            source: source.to_string(),
+            migrated: 0,
+            messages,
+            skipped,
+        });
+    }
+
+    let migrated = edits.len();
+    let output = apply_edits(source, edits);
+    let output = remove_unused_bindings(&output, filename)?;
+    let modules: Vec<&str> = ["fbs", "fbt"]
+        .into_iter()
+        .filter(|module| modules.contains(module))
+        .collect();
+    let output = update_imports(
+        &output,
+        filename,
+        &[I18NEXT, REACT_I18NEXT],
+        MIGRATED_IMPORTS,
+        &modules,
+    )?;
+    Ok(MigratedSource {
+        source: output,
+        migrated,
+        messages,
+        skipped,
+    })
+}
+
+/// Removes `useTranslation()` declarations whose bindings the migration left unused.
+fn remove_unused_bindings(source: &str, filename: &str) -> Result<String, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let bindings = TBindings::find(&file.module);
+    Ok(remove_unused_declarations(
+        &file,
+        source,
+        &bindings.use_translation,
+    ))
+}
+
+/// Converts the i18next resources of another locale into translations of the phrases `messages`
+/// were migrated to. Keys that were not migrated are ignored, as are messages identical to their
+/// source text.
+pub fn migrate_translations(
+    messages: &[MigratedMessage],
+    catalog: &Catalog,
+    locale: &str,
+) -> Result<TranslationGroup, String> {
+    let mut translations = Translations::new();
+    for message in messages {
+        let plural = !message.forms.contains_key("");
+        let forms: IndexMap<String, String> = if plural {
+            PLURAL_SUFFIXES
+                .iter()
+                .filter_map(|suffix| {
+                    let text = catalog.get(&format!("{}_{suffix}", message.key))?;
+                    Some((suffix.to_string(), text.clone()))
+                })
+                .collect()
+        } else {
+            catalog
+                .get(&message.key)
+                .map(|text| (String::new(), text.clone()))
+                .into_iter()
+                .collect()
+        };
+        if forms.is_empty() || forms == message.forms {
+            continue;
+        }
+        let error = |error: String| format!("{error} (key '{}')", message.key);
+        let nodes = if plural {
+            let argument = message
+                .phrase
+                .jsfbt
+                .as_ref()
+                .map(level_arguments)
+                .and_then(|arguments| arguments.into_iter().next())
+                .ok_or_else(|| error("Phrase has no plural.".to_string()))?;
+            let cases = forms
+                .iter()
+                .map(|(category, text)| Ok((category.clone(), interpolation_nodes(text)?)))
+                .collect::<Result<_, String>>()
+                .map_err(error)?;
+            vec![IcuNode::Select {
+                argument,
+                plural: true,
+                cases,
+            }]
+        } else {
+            interpolation_nodes(&forms[""]).map_err(error)?
+        };
+        translations.extend(icu_nodes_to_translations(&message.phrase, &nodes).map_err(error)?);
+    }
+    Ok(TranslationGroup {
+        fb_locale: locale.to_string(),
+        extra: IndexMap::new(),
+        translations,
+    })
+}
+
+fn interpolation_nodes(text: &str) -> Result<Vec<IcuNode>, String> {
+    Ok(segments(text)?
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => IcuNode::Text(text),
+            Segment::Variable(name) => IcuNode::Argument(name),
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Variable(String),
+}
+
+/// Splits i18next text into literal text and `{{var}}` interpolations.
+fn segments(text: &str) -> Result<Vec<Segment>, String> {
+    if text.contains("$t(") {
+        return Err("nested $t() references have no fbt equivalent.".to_string());
+    }
+    let mut segments = vec![];
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+        if start > 0 {
+            segments.push(Segment::Text(rest[..start].to_string()));
+        }
+        let inner = rest[start + 2..end].trim();
+        let name = inner.strip_prefix('-').unwrap_or(inner).trim();
+        if name.contains(',') {
+            return Err(format!(
+                "interpolation '{{{{{inner}}}}}' has a format, which fbt has no equivalent for."
+            ));
+        }
+        segments.push(Segment::Variable(name.to_string()));
+        rest = &rest[end + 2..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest.to_string()));
+    }
+    Ok(segments)
+}
+
+/// Words, `{{var}}` interpolations and single other characters, for comparing plural forms.
+fn tokens(text: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        let length = if rest.starts_with("{{") {
+            rest.find("}}").map_or(rest.len(), |end| end + 2)
+        } else if is_word_char(ch) {
+            rest.find(|ch| !is_word_char(ch)).unwrap_or(rest.len())
+        } else {
+            ch.len_utf8()
+        };
+        tokens.push(&rest[..length]);
+        rest = &rest[length..];
+    }
+    tokens
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '\'' || ch == '_'
+}
+
+fn is_count(token: &str) -> bool {
+    matches!(segments(token).as_deref(), Ok([Segment::Variable(name)]) if name == "count")
+}
+
+/// How a piece of a message is written in fbt.
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Text(String),
+    Param(String),
+    Plural {
+        singular: String,
+        many: String,
+        show_count: bool,
+    },
+}
+
+fn text_pieces(text: &str) -> Result<Vec<Piece>, String> {
+    Ok(segments(text)?
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => Piece::Text(text),
+            Segment::Variable(name) => Piece::Param(name),
+        })
+        .collect())
+}
+
+/// The `one` and `other` forms as one message whose differing words are an `fbt.plural`, which
+/// optionally shows the count before them.
+fn plural_pieces(one: &str, other: &str) -> Result<Vec<Piece>, String> {
+    let (one, other) = (tokens(one), tokens(other));
+    let mut prefix = one
+        .iter()
+        .zip(&other)
+        .take_while(|(one, other)| one == other)
+        .count();
+    let suffix = one[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(one, other)| one == other)
+        .count();
+    if prefix >= 2 && is_count(one[prefix - 2]) && one[prefix - 1] == " " {
+        prefix -= 2;
+    }
+    let mut singular = &one[prefix..one.len() - suffix];
+    let mut many = &other[prefix..other.len() - suffix];
+    let show_count = [singular, many]
+        .iter()
+        .all(|form| form.len() > 2 && is_count(form[0]) && form[1] == " ");
+    if show_count {
+        singular = &singular[2..];
+        many = &many[2..];
+    }
+    if singular.is_empty()
+        || many.is_empty()
+        || singular
+            .iter()
+            .chain(many)
+            .any(|token| token.starts_with("{{"))
+    {
+        return Err(
+            "plural forms must differ only in their words, optionally after '{{count}} '."
+                .to_string(),
+        );
+    }
+    let mut pieces = text_pieces(&one[..prefix].concat())?;
+    pieces.push(Piece::Plural {
+        singular: singular.concat(),
+        many: many.concat(),
+        show_count,
+    });
+    pieces.extend(text_pieces(&one[one.len() - suffix..].concat())?);
+    pieces.retain(|piece| !matches!(piece, Piece::Text(text) if text.is_empty()));
+    Ok(pieces)
+}
+
+fn render_call(
+    module: &str,
+    pieces: &[Piece],
+    values: &IndexMap<String, String>,
+    desc: &str,
+) -> String {
+    let parts = pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => js_string(text),
+            Piece::Param(name) => {
+                format!("{module}.param({}, {})", js_string(name), values[name])
+            }
+            Piece::Plural {
+                singular,
+                many,
+                show_count,
+            } => {
+                let mut options = vec![format!("many: {}", js_string(many))];
+                if *show_count {
+                    options.push("showCount: 'yes'".to_string());
+                    options.push("name: 'count'".to_string());
+                }
+                format!(
+                    "{module}.plural({}, {}, {{ {} }})",
+                    js_string(singular),
+                    values["count"],
+                    options.join(", ")
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+    let text = match pieces {
+        [Piece::Text(_)] => parts[0].clone(),
+        _ => format!("[{}]", parts.join(", ")),
+    };
+    format!("{module}({text}, {})", js_string(desc))
+}
+
+struct Migrator<'a> {
+    source: &'a str,
+    filename: &'a str,
+    config: Option<&'a str>,
+    catalog: &'a Catalog,
+    file: &'a SourceModule,
+    bindings: &'a TBindings,
+    edits: Vec<Edit>,
+    /// The fbtee modules the migrated phrases use.
+    modules: Vec<&'static str>,
+    messages: Vec<MigratedMessage>,
+    skipped: Vec<String>,
+}
+
+impl Migrator<'_> {
+    fn text(&self, span: Span) -> &str {
+        let (start, end) = self.file.range(span);
+        &self.source[start..end]
+    }
+
+    fn skip(&mut self, span: Span, reason: &str) {
+        let (start, _) = self.file.range(span);
+        self.skipped.push(format!(
+            "{}:{}: {reason}",
+            self.filename,
+            line_number(self.source, start)
+        ));
+    }
+
+    fn is_known(&self, key: &str) -> bool {
+        self.catalog.contains_key(key)
+            || PLURAL_SUFFIXES
+                .iter()
+                .any(|suffix| self.catalog.contains_key(&format!("{key}_{suffix}")))
+    }
+
+    /// The source of each option of a `t()` call.
+    fn options(&self, expr: &Expr) -> Result<IndexMap<String, String>, String> {
+        let error = || "options must be an object literal.".to_string();
+        let Expr::Object(object) = crate::unwrap_static_expr(expr) else {
+            return Err(error());
+        };
+        let mut options = IndexMap::new();
+        for prop in &object.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                return Err(error());
+            };
+            let (key, value) = match prop.as_ref() {
+                Prop::KeyValue(prop) => (
+                    crate::prop_name_to_string(&prop.key).ok_or_else(error)?,
+                    self.text(prop.value.span()).to_string(),
+                ),
+                Prop::Shorthand(ident) => (ident.sym.to_string(), ident.sym.to_string()),
+                _ => return Err(error()),
+            };
+            if UNSUPPORTED_OPTIONS.contains(&key.as_str()) {
+                return Err(format!("the '{key}' option has no fbt equivalent."));
+            }
+            options.insert(key, value);
+        }
+        Ok(options)
+    }
+
+    fn migrate(&mut self, call: &CallExpr, module: &'static str) -> Result<(), String> {
+        if let Some(reason) = self.bindings.unresolved(call) {
+            return Err(reason.to_string());
+        }
+        if call.args.is_empty()
+            || call.args.len() > 2
+            || call.args.iter().any(|arg| arg.spread.is_some())
+        {
+            return Err("t() must be called with a key and optional options.".to_string());
+        }
+        let key = crate::expr_as_string(crate::unwrap_static_expr(&call.args[0].expr))
+            .ok_or("the key must be a string literal.")?;
+        let options = call
+            .args
+            .get(1)
+            .map(|arg| self.options(&arg.expr))
+            .transpose()?
+            .unwrap_or_default();
+        if key.contains(':') && !self.is_known(&key) {
+            return Err(format!(
+                "key '{key}' names a namespace, which the catalog is not resolved against."
+            ));
+        }
+        let catalog_key = key.clone();
+
+        let plural_forms: IndexMap<String, String> = PLURAL_SUFFIXES
+            .iter()
+            .filter_map(|suffix| {
+                let text = self.catalog.get(&format!("{catalog_key}_{suffix}"))?;
+                Some((suffix.to_string(), text.clone()))
+            })
+            .collect();
+        let (forms, pieces) = match self.catalog.get(&catalog_key) {
+            Some(text) if plural_forms.is_empty() || !options.contains_key("count") => {
+                let forms = IndexMap::from([(String::new(), text.clone())]);
+                (forms, text_pieces(text)?)
+            }
+            _ if plural_forms.is_empty() => {
+                return Err(format!("key '{key}' is not in the catalog."));
+            }
+            _ => {
+                if !options.contains_key("count") {
+                    return Err(format!("key '{key}' has plural forms but no count."));
+                }
+                let (Some(one), Some(other), 2) = (
+                    plural_forms.get("one"),
+                    plural_forms.get("other"),
+                    plural_forms.len(),
+                ) else {
+                    return Err(format!(
+                        "key '{key}' needs exactly a '_one' and an '_other' form."
+                    ));
+                };
+                let pieces = plural_pieces(one, other)?;
+                (plural_forms, pieces)
+            }
+        };
+
+        let mut values = IndexMap::new();
+        if let Some(count) = options.get("count") {
+            values.insert("count".to_string(), count.clone());
+        }
+        for piece in &pieces {
+            if let Piece::Param(name) = piece {
+                let value = variable_value(name, &options)
+                    .ok_or_else(|| format!("no value is given for '{{{{{name}}}}}'."))?;
+                values.insert(name.clone(), value);
+            }
+        }
+
+        let phrase = render_call(module, &pieces, &values, &key);
+        let compiled = collect_phrases(
+            &format!("{}\n{phrase};\n", fbtee_import_statement(&[module])),
+            self.filename,
+            self.config,
+        )
+        .map_err(|error| format!("the migrated phrase does not compile. {error}"))?
+        .pop()
+        .ok_or("the migrated phrase does not compile.")?;
+        self.messages.push(MigratedMessage {
+            key: catalog_key,
+            phrase: compiled,
+            forms,
+        });
+        if !self.modules.contains(&module) {
+            self.modules.push(module);
+        }
+        let (start, end) = self.file.range(call.span);
+        self.edits.push(Edit::new(start, end, phrase));
+        Ok(())
+    }
+
+    fn visit_t_call(&mut self, call: &CallExpr, module: &'static str) {
+        match self.migrate(call, module) {
+            Ok(()) => {}
+            Err(reason) => {
+                self.skip(call.span, &reason);
+                call.visit_children_with(self);
+            }
+        }
+    }
+}
+
+/// The source of an interpolated variable. `{{user.name}}` reads `name` from the `user` option.
+fn variable_value(name: &str, options: &IndexMap<String, String>) -> Option<String> {
+    if let Some(value) = options.get(name) {
+        return Some(value.clone());
+    }
+    let (option, path) = name.split_once('.')?;
+    let value = options.get(option)?;
+    if value
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$' || ch == '.')
+    {
+        Some(format!("{value}.{path}"))
+    } else {
+        Some(format!("({value}).{path}"))
+    }
+}
+
+impl Visit for Migrator<'_> {
+    noop_visit_type!();
+
+    fn visit_jsx_element_child(&mut self, child: &JSXElementChild) {
+        if let JSXElementChild::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        }) = child
+        {
+            if let Expr::Call(call) = expr.as_ref() {
+                if self.bindings.is_t_call(call) {
+                    self.visit_t_call(call, "fbt");
+                    return;
+                }
+            }
+        }
+        child.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if self.bindings.is_t_call(call) {
+            self.visit_t_call(call, "fbs");
+        } else {
+            call.visit_children_with(self);
+        }
+    }
+}
+
+/// The local names of `t` functions and i18next instances, by name like the other codemods.
+#[derive(Default)]
+struct TBindings {
+    /// Each `t` function, with the reason it cannot be migrated if it is bound to a namespace or
+    /// key prefix by `useTranslation()`. A name bound several times keeps the first reason.
+    functions: IndexMap<String, Option<&'static str>>,
+    /// The i18next instances whose `.t()` is migrated.
+    instances: Vec<String>,
+    use_translation: Vec<String>,
+}
+
+impl TBindings {
+    fn find(module: &Module) -> Self {
+        let mut bindings = Self::default();
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+                continue;
+            };
+            if import.type_only {
+                continue;
+            }
+            let src = import.src.value.to_atom_lossy();
+            for specifier in &import.specifiers {
+                let local = specifier.local().sym.to_string();
+                match (&**src, specifier) {
+                    (I18NEXT, ImportSpecifier::Default(_) | ImportSpecifier::Namespace(_)) => {
+                        bindings.instances.push(local);
+                    }
+                    (I18NEXT, ImportSpecifier::Named(named)) if imported_name(named) == "t" => {
+                        bindings.add_function(local, None);
+                    }
+                    (REACT_I18NEXT, ImportSpecifier::Named(named))
+                        if imported_name(named) == "useTranslation" =>
+                    {
+                        bindings.use_translation.push(local);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !bindings.use_translation.is_empty() {
+            module.visit_with(&mut bindings);
+        }
+        bindings
+    }
+
+    fn add_function(&mut self, name: String, reason: Option<&'static str>) {
+        let entry = self.functions.entry(name).or_default();
+        *entry = entry.or(reason);
+    }
+
+    /// `t(...)` or `i18n.t(...)` on a tracked binding.
+    fn is_t_call(&self, call: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+        match callee.as_ref() {
+            Expr::Ident(ident) => self.functions.contains_key(&*ident.sym),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => {
+                prop.sym == "t"
+                    && matches!(obj.as_ref(), Expr::Ident(obj) if self.instances.iter().any(|name| *name == *obj.sym))
+            }
+            _ => false,
+        }
+    }
+
+    /// Why the `t` function `call` uses cannot be resolved against the catalog, if it can't.
+    fn unresolved(&self, call: &CallExpr) -> Option<&'static str> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let Expr::Ident(ident) = callee.as_ref() else {
+            return None;
+        };
+        self.functions.get(&*ident.sym).copied().flatten()
+    }
+}
+
+impl Visit for TBindings {
+    noop_visit_type!();
+
+    /// `const { t, i18n } = useTranslation(...)` or `const [t, i18n] = useTranslation(...)`.
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        declarator.visit_children_with(self);
+        let Some(Expr::Call(call)) = declarator.init.as_deref() else {
+            return;
+        };
+        let Callee::Expr(callee) = &call.callee else {
+            return;
+        };
+        let Expr::Ident(callee) = callee.as_ref() else {
+            return;
+        };
+        if !self.use_translation.iter().any(|name| *name == *callee.sym) {
+            return;
+        }
+        let reason = use_translation_reason(call);
+        let mut bind = |name: &str, local: &Pat| {
+            let Pat::Ident(local) = local else {
+                return;
+            };
+            match name {
+                "t" => self.add_function(local.sym.to_string(), reason),
+                "i18n" => self.instances.push(local.sym.to_string()),
+                _ => {}
+            }
+        };
+        match &declarator.name {
+            Pat::Object(object) => {
+                for prop in &object.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(prop) => {
+                            if let Some(name) = crate::prop_name_to_string(&prop.key) {
+                                bind(&name, &prop.value);
+                            }
+                        }
+                        ObjectPatProp::Assign(prop) if prop.value.is_none() => {
+                            bind(&prop.key.sym, &Pat::Ident(prop.key.clone()));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Pat::Array(array) => {
+                for (name, element) in ["t", "i18n"].into_iter().zip(&array.elems) {
+                    if let Some(element) = element {
+                        bind(name, element);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `useTranslation(ns, { keyPrefix })` binds `t` to resources the flattened catalog does not
+/// describe.
+fn use_translation_reason(call: &CallExpr) -> Option<&'static str> {
+    if call.args.first().is_some_and(
+        |arg| !matches!(arg.expr.as_ref(), Expr::Ident(ident) if ident.sym == "undefined"),
+    ) {
+        return Some(
+            "t is bound to a namespace by useTranslation(), which the catalog is not resolved against.",
+        );
+    }
+    let options = call.args.get(1)?;
+    let resolved = match crate::unwrap_static_expr(&options.expr) {
+        Expr::Object(object) => object.props.iter().all(|prop| match prop {
+            PropOrSpread::Prop(prop) => match prop.as_ref() {
+                Prop::KeyValue(prop) => {
+                    crate::prop_name_to_string(&prop.key).is_some_and(|key| key != "keyPrefix")
+                }
+                Prop::Shorthand(ident) => ident.sym != "keyPrefix",
+                _ => false,
+            },
+            PropOrSpread::Spread(_) => false,
+        }),
+        _ => false,
+    };
+    (!resolved).then_some(
+        "t is bound to a keyPrefix by useTranslation(), which the catalog is not resolved against.",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(json: &str) -> Catalog {
+        flatten_catalog(&serde_json::from_str(json).unwrap())
+    }
+
+    fn english() -> Catalog {
+        catalog(
+            r#"{
+              "checkout": {
+                "title": "Checkout",
+                "greeting": "Welcome back, {{user.name}}!",
+                "items_one": "{{count}} item in your cart",
+                "items_other": "{{count}} items in your cart",
+                "search": "Search products"
+              }
+            }"#,
+        )
+    }
+
+    const CHECKOUT: &str = "import { useTranslation } from 'react-i18next';
+
+export function Checkout({ user, cart }) {
+  const { t } = useTranslation();
+  return (
+    <section>
+      <h1>{t('checkout.title')}</h1>
+      <p>{t('checkout.greeting', { user })}</p>
+      <p>{t('checkout.items', { count: cart.length })}</p>
+      <input placeholder={t('checkout.search')} />
+    </section>
+  );
+}
+";
+
+    #[test]
+    fn inlines_catalog_messages_as_phrases() {
+        let migrated = migrate_i18next(CHECKOUT, "Checkout.jsx", &english(), None).unwrap();
+        assert_eq!(migrated.migrated, 4, "{:?}", migrated.skipped);
+        assert_eq!(
+            migrated.source,
+            "import { fbs, fbt } from 'fbtee';
+
+export function Checkout({ user, cart }) {
+  return (
+    <section>
+      <h1>{fbt('Checkout', 'checkout.title')}</h1>
+      <p>{fbt(['Welcome back, ', fbt.param('user.name', user.name), '!'], 'checkout.greeting')}</p>
+      <p>{fbt([fbt.plural('item', cart.length, { many: 'items', showCount: 'yes', name: 'count' }), ' in your cart'], 'checkout.items')}</p>
+      <input placeholder={fbs('Search products', 'checkout.search')} />
+    </section>
+  );
+}
+"
+        );
+        assert_eq!(
+            collect_phrases(&migrated.source, "Checkout.jsx", None)
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn keeps_words_shared_by_plural_forms_outside_the_plural() {
+        assert_eq!(
+            plural_pieces(
+                "You have {{count}} new message",
+                "You have {{count}} new messages"
+            )
+            .unwrap(),
+            [
+                Piece::Text("You have ".to_string()),
+                Piece::Param("count".to_string()),
+                Piece::Text(" new ".to_string()),
+                Piece::Plural {
+                    singular: "message".to_string(),
+                    many: "messages".to_string(),
+                    show_count: false,
+                },
+            ]
+        );
+        assert!(plural_pieces("One file", "{{count}} files").is_err());
+    }
+
+    #[test]
+    fn leaves_calls_fbt_cannot_express() {
+        let catalog = catalog(
+            r#"{ "total": "Total: {{amount, currency}}", "friend": "A friend", "friend_male": "A boyfriend" }"#,
+        );
+        let source = "import { t } from 'i18next';
+const a = t('missing');
+const b = t('total', { amount });
+const c = t('friend', { context: gender });
+const d = t('friend', { ns: 'people' });
+const e = t('people:friend');
+";
+        let migrated = migrate_i18next(source, "a.js", &catalog, None).unwrap();
+        assert_eq!(migrated.source, source);
+        assert_eq!(
+            migrated.skipped,
+            [
+                "a.js:2: key 'missing' is not in the catalog.",
+                "a.js:3: interpolation '{{amount, currency}}' has a format, which fbt has no equivalent for.",
+                "a.js:4: the 'context' option has no fbt equivalent.",
+                "a.js:5: the 'ns' option has no fbt equivalent.",
+                "a.js:6: key 'people:friend' names a namespace, which the catalog is not resolved against.",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_migrates_t_bound_by_i18next() {
+        let catalog = catalog(r#"{ "title": "Checkout" }"#);
+        let source = "import i18n from 'i18next';
+import { useTranslation } from 'react-i18next';
+
+function Title() {
+  const { t: translate } = useTranslation();
+  return <h1>{translate('title')}</h1>;
+}
+
+function Label() {
+  const { t } = useTranslation('checkout');
+  return i18n.t('title') + t('title');
+}
+
+function Prefixed() {
+  const { t: tp } = useTranslation(undefined, { keyPrefix: 'checkout' });
+  return router.t('title') + tp('title');
+}
+";
+        let migrated = migrate_i18next(source, "a.jsx", &catalog, None).unwrap();
+        assert_eq!(migrated.migrated, 2, "{:?}", migrated.skipped);
+        assert!(migrated
+            .source
+            .contains("return <h1>{fbt('Checkout', 'title')}</h1>;"));
+        assert!(migrated
+            .source
+            .contains("return fbs('Checkout', 'title') + t('title');"));
+        assert!(migrated
+            .source
+            .contains("return router.t('title') + tp('title');"));
+        assert_eq!(
+            migrated.skipped,
+            [
+                "a.jsx:11: t is bound to a namespace by useTranslation(), which the catalog is not resolved against.",
+                "a.jsx:16: t is bound to a keyPrefix by useTranslation(), which the catalog is not resolved against.",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_bindings_and_imports_still_in_use() {
+        let catalog = catalog(r#"{ "title": "Cart" }"#);
+        let source = "import i18n, { t as translate } from 'i18next';
+import { Trans, useTranslation } from 'react-i18next';
+
+export function Cart() {
+  const { t } = useTranslation();
+  return <h1>{t('title')}</h1>;
+}
+
+export function Summary() {
+  const { t, i18n: instance } = useTranslation();
+  return <p lang={instance.language}>{t('title')}</p>;
+}
+
+export const label = translate('title') + i18n.t('missing');
+";
+        let migrated = migrate_i18next(source, "a.jsx", &catalog, None).unwrap();
+        assert_eq!(migrated.migrated, 3, "{:?}", migrated.skipped);
+        assert_eq!(
+            migrated.source,
+            "import i18n from 'i18next';
+import { Trans, useTranslation } from 'react-i18next';
+import { fbs, fbt } from 'fbtee';
+
+export function Cart() {
+  return <h1>{fbt('Cart', 'title')}</h1>;
+}
+
+export function Summary() {
+  const { t, i18n: instance } = useTranslation();
+  return <p lang={instance.language}>{fbt('Cart', 'title')}</p>;
+}
+
+export const label = fbs('Cart', 'title') + i18n.t('missing');
+"
+        );
+    }
+
+    #[test]
+    fn carries_translations_over_to_the_new_hashes() {
+        let migrated = migrate_i18next(CHECKOUT, "Checkout.jsx", &english(), None).unwrap();
+        let french = catalog(
+            r#"{
+              "checkout": {
+                "title": "Paiement",
+                "greeting": "Bon retour, {{user.name}} !",
+                "items_one": "{{count}} article dans votre panier",
+                "items_other": "{{count}} articles dans votre panier"
+              }
+            }"#,
+        );
+        let group = migrate_translations(&migrated.messages, &french, "fr_FR").unwrap();
+        let texts: Vec<_> = group
+            .translations
+            .values()
+            .flat_map(|entry| &entry.as_ref().unwrap().translations)
+            .map(|translation| translation.translation.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "Paiement",
+                "Bon retour, {user.name} !",
+                "{count} article dans votre panier",
+                "{count} articles dans votre panier",
+                "{count} article dans votre panier",
+            ]
+        );
+        assert!(
+            migrate_translations(&migrated.messages, &english(), "en_US")
+                .unwrap()
+                .translations
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,1144 @@
+//! Migrates react-intl messages to fbtee. `<FormattedMessage>` becomes `<fbt>` and
+//! `formatMessage()`, which returns a string, becomes `fbs()`, or `fbt()` in JSX children. ICU
+//! arguments, plurals and selects are turned into `fbt:param`, `fbt:plural` and `fbt:enum`. Migrated messages keep their react-intl ids so
+//! existing translations can be exported under the new hashes. Only `formatMessage` on an `intl`
+//! from `useIntl()`, `createIntl()` or `injectIntl` is migrated.
+
+use super::{
+    apply_edits, collect_phrases, fbtee_import_statement, imported_name, js_string, jsx_text,
+    line_number, object_key, remove_unused_declarations, update_imports, Edit, SourceModule,
+};
+use crate::{
+    icu::{icu_nodes_to_translations, level_arguments, message_text, parse_icu, IcuNode},
+    translations::{CollectedPhrase, TranslationGroup, Translations},
+};
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::HashMap;
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::*,
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+const REACT_INTL: &str = "react-intl";
+
+/// react-intl imports the migration makes unnecessary.
+const MIGRATED_IMPORTS: &[&str] = &[
+    "FormattedMessage",
+    "defineMessages",
+    "useIntl",
+    "createIntl",
+];
+
+pub struct MigratedSource {
+    pub source: String,
+    /// The number of messages rewritten as phrases.
+    pub migrated: usize,
+    /// The migrated messages that have an id, for carrying over their translations.
+    pub messages: Vec<MigratedMessage>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigratedMessage {
+    pub id: String,
+    pub default_message: String,
+    phrase: CollectedPhrase,
+    /// ICU select and plural argument names mapped to the names the phrase table uses.
+    arguments: IndexMap<String, String>,
+}
+
+/// Rewrites the react-intl messages in `source` as fbt phrases. Messages fbt cannot express,
+/// such as rich text or ordinal plurals, are left alone and reported in `skipped`.
+pub fn migrate_react_intl(
+    source: &str,
+    filename: &str,
+    config: Option<&str>,
+) -> Result<MigratedSource, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let imports = ReactIntlImports::find(&file.module);
+    let descriptors = message_descriptors(&file.module, &imports);
+    let intl = IntlBindings::find(&file.module, &imports);
+    let mut migrator = Migrator {
+        source,
+        filename,
+        config,
+        file: &file,
+        imports,
+        intl,
+        descriptors,
+        edits: vec![],
+        modules: vec![],
+        messages: vec![],
+        skipped: vec![],
+    };
+    file.module.visit_with(&mut migrator);
+    let Migrator {
+        edits,
+        modules,
+        messages,
+        skipped,
+        ..
+    } = migrator;
+    if edits.is_empty() {
+        return Ok(MigratedSource {
+            source: source.to_string(),
+            migrated: 0,
+            messages,
+            skipped,
+        });
+    }
+
+    let migrated = edits.len();
+    let output = apply_edits(source, edits);
+    let output = remove_unused_bindings(&output, filename)?;
+    let modules: Vec<&str> = ["fbs", "fbt"]
+        .into_iter()
+        .filter(|module| modules.contains(module))
+        .collect();
+    let output = update_imports(&output, filename, &[REACT_INTL], MIGRATED_IMPORTS, &modules)?;
+    Ok(MigratedSource {
+        source: output,
+        migrated,
+        messages,
+        skipped,
+    })
+}
+
+/// Converts a react-intl catalog for `locale`, mapping ids to ICU messages, into translations
+/// of the phrases `messages` were migrated to. Ids that were not migrated are ignored, as are
+/// messages identical to their source text.
+pub fn migrate_translations(
+    messages: &[MigratedMessage],
+    catalog: &IndexMap<String, Value>,
+    locale: &str,
+) -> Result<TranslationGroup, String> {
+    let mut translations = Translations::new();
+    for message in messages {
+        let Some(value) = catalog.get(&message.id) else {
+            continue;
+        };
+        let text = message_text(value)
+            .ok_or_else(|| format!("Message '{}' is not a string.", message.id))?;
+        if text == message.default_message {
+            continue;
+        }
+        let nodes = parse_icu(text)
+            .map(|nodes| rename_arguments(nodes, &message.arguments))
+            .and_then(|nodes| icu_nodes_to_translations(&message.phrase, &nodes))
+            .map_err(|error| format!("{error} (message '{}')", message.id))?;
+        translations.extend(nodes);
+    }
+    Ok(TranslationGroup {
+        fb_locale: locale.to_string(),
+        extra: IndexMap::new(),
+        translations,
+    })
+}
+
+fn rename_arguments(nodes: Vec<IcuNode>, arguments: &IndexMap<String, String>) -> Vec<IcuNode> {
+    nodes
+        .into_iter()
+        .map(|node| match node {
+            IcuNode::Select {
+                argument,
+                plural,
+                cases,
+            } => IcuNode::Select {
+                argument: arguments.get(&argument).cloned().unwrap_or(argument),
+                plural,
+                cases: cases
+                    .into_iter()
+                    .map(|(case, body)| (case, rename_arguments(body, arguments)))
+                    .collect(),
+            },
+            node => node,
+        })
+        .collect()
+}
+
+/// The local names react-intl exports are imported as.
+#[derive(Default)]
+struct ReactIntlImports {
+    formatted_message: Vec<String>,
+    define_messages: Vec<String>,
+    use_intl: Vec<String>,
+    create_intl: Vec<String>,
+    inject_intl: bool,
+}
+
+impl ReactIntlImports {
+    fn find(module: &Module) -> Self {
+        let mut imports = Self::default();
+        for import in react_intl_imports(module) {
+            for specifier in &import.specifiers {
+                let ImportSpecifier::Named(named) = specifier else {
+                    continue;
+                };
+                let local = named.local.sym.to_string();
+                match imported_name(named).as_str() {
+                    "FormattedMessage" => imports.formatted_message.push(local),
+                    "defineMessages" => imports.define_messages.push(local),
+                    "useIntl" => imports.use_intl.push(local),
+                    "createIntl" => imports.create_intl.push(local),
+                    "injectIntl" => imports.inject_intl = true,
+                    _ => {}
+                }
+            }
+        }
+        imports
+    }
+}
+
+fn react_intl_imports(module: &Module) -> impl Iterator<Item = &ImportDecl> {
+    module.body.iter().filter_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+            if !import.type_only && import.src.value == REACT_INTL =>
+        {
+            Some(import)
+        }
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, Default)]
+struct Descriptor {
+    id: Option<String>,
+    default_message: Option<String>,
+    description: Option<String>,
+}
+
+impl Descriptor {
+    fn from_object(object: &ObjectLit) -> Result<Self, String> {
+        let mut descriptor = Self::default();
+        for prop in &object.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                return Err("message descriptors must be object literals.".to_string());
+            };
+            let Prop::KeyValue(prop) = prop.as_ref() else {
+                continue;
+            };
+            let Some(key) = crate::prop_name_to_string(&prop.key) else {
+                continue;
+            };
+            let field = match key.as_str() {
+                "id" => &mut descriptor.id,
+                "defaultMessage" => &mut descriptor.default_message,
+                "description" => &mut descriptor.description,
+                _ => continue,
+            };
+            *field = Some(string_value(&prop.value, &key)?);
+        }
+        Ok(descriptor)
+    }
+}
+
+fn string_value(expr: &Expr, name: &str) -> Result<String, String> {
+    crate::expr_as_string(crate::unwrap_static_expr(expr))
+        .ok_or_else(|| format!("'{name}' must be a string literal."))
+}
+
+type Descriptors = HashMap<String, IndexMap<String, Result<Descriptor, String>>>;
+
+/// The `defineMessages` calls bound to top-level variables.
+fn message_descriptors(module: &Module, imports: &ReactIntlImports) -> Descriptors {
+    let mut descriptors = Descriptors::new();
+    for item in &module.body {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => var,
+            _ => continue,
+        };
+        for declarator in &var.decls {
+            let (Pat::Ident(name), Some(Expr::Call(call))) =
+                (&declarator.name, declarator.init.as_deref())
+            else {
+                continue;
+            };
+            let is_define_messages = matches!(
+                &call.callee,
+                Callee::Expr(callee) if matches!(callee.as_ref(), Expr::Ident(callee)
+                    if imports.define_messages.iter().any(|local| *local == *callee.sym))
+            );
+            let Some(Expr::Object(object)) = call
+                .args
+                .first()
+                .filter(|_| is_define_messages)
+                .map(|arg| crate::unwrap_static_expr(&arg.expr))
+            else {
+                continue;
+            };
+            let entries = object
+                .props
+                .iter()
+                .filter_map(|prop| match prop {
+                    PropOrSpread::Prop(prop) => match prop.as_ref() {
+                        Prop::KeyValue(prop) => Some(prop),
+                        _ => None,
+                    },
+                    PropOrSpread::Spread(_) => None,
+                })
+                .filter_map(|prop| {
+                    let key = crate::prop_name_to_string(&prop.key)?;
+                    let descriptor = match crate::unwrap_static_expr(&prop.value) {
+                        Expr::Object(object) => Descriptor::from_object(object),
+                        _ => Err("message descriptors must be object literals.".to_string()),
+                    };
+                    Some((key, descriptor))
+                })
+                .collect();
+            descriptors.insert(name.id.sym.to_string(), entries);
+        }
+    }
+    descriptors
+}
+
+/// How a migrated message is written: as an `<fbt>` element, or as a call to `fbt` in JSX
+/// children and `fbs` wherever react-intl returned a string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Form {
+    Element,
+    Call(&'static str),
+}
+
+impl Form {
+    fn module(self) -> &'static str {
+        match self {
+            Self::Element => "fbt",
+            Self::Call(module) => module,
+        }
+    }
+}
+
+/// How a piece of an ICU message is written in fbt.
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Text(String),
+    Param(String),
+    Plural {
+        argument: String,
+        singular: String,
+        many: String,
+        show_count: bool,
+    },
+    Enum {
+        argument: String,
+        cases: Vec<(String, String)>,
+    },
+}
+
+fn pieces(nodes: &[IcuNode]) -> Result<Vec<Piece>, String> {
+    let mut pieces = vec![];
+    for node in nodes {
+        let piece = match node {
+            IcuNode::Text(text) => {
+                if let Some(Piece::Text(previous)) = pieces.last_mut() {
+                    previous.push_str(text);
+                    continue;
+                }
+                Piece::Text(text.clone())
+            }
+            IcuNode::Argument(argument) => Piece::Param(argument.clone()),
+            IcuNode::Pound => return Err("'#' is only allowed inside a plural.".to_string()),
+            IcuNode::Select {
+                argument,
+                plural: false,
+                cases,
+            } => Piece::Enum {
+                argument: argument.clone(),
+                cases: cases
+                    .iter()
+                    .map(|(case, body)| {
+                        let text = plain_text(body).ok_or_else(|| {
+                            format!("select '{argument}' has a case that is not plain text.")
+                        })?;
+                        Ok((case.clone(), text))
+                    })
+                    .collect::<Result<_, String>>()?,
+            },
+            IcuNode::Select {
+                argument,
+                plural: true,
+                cases,
+            } => plural_piece(argument, cases)?,
+        };
+        pieces.push(piece);
+    }
+    Ok(pieces)
+}
+
+fn plain_text(nodes: &[IcuNode]) -> Option<String> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            IcuNode::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `{n, plural, one {# item} other {# items}}` as `fbt:plural`, which only knows a singular and
+/// a plural form and optionally shows the count before both.
+fn plural_piece(argument: &str, cases: &[(String, Vec<IcuNode>)]) -> Result<Piece, String> {
+    if let Some((case, _)) = cases
+        .iter()
+        .find(|(case, _)| !["=1", "one", "other"].contains(&case.as_str()))
+    {
+        return Err(format!(
+            "plural '{argument}' has a '{case}' case, but fbt:plural only has singular and plural forms."
+        ));
+    }
+    let case = |name: &str| cases.iter().find(|(case, _)| case == name);
+    let (Some((_, one)), Some((_, other))) = (case("=1").or_else(|| case("one")), case("other"))
+    else {
+        return Err(format!(
+            "plural '{argument}' needs a singular and an 'other' case."
+        ));
+    };
+    let form = |body: &[IcuNode]| match body {
+        [IcuNode::Pound, IcuNode::Text(text)] => {
+            text.strip_prefix(' ').map(|text| (true, text.to_string()))
+        }
+        [IcuNode::Text(text)] => Some((false, text.clone())),
+        _ => None,
+    };
+    let (Some((one_count, singular)), Some((show_count, many))) = (form(one), form(other)) else {
+        return Err(format!(
+            "plural '{argument}' cases must be a word, optionally after '# '."
+        ));
+    };
+    let singular = match singular.strip_prefix("1 ") {
+        Some(singular) if show_count && !one_count => singular.to_string(),
+        _ if one_count == show_count => singular,
+        _ => {
+            return Err(format!(
+                "plural '{argument}' shows the count in only some cases."
+            ))
+        }
+    };
+    Ok(Piece::Plural {
+        argument: argument.to_string(),
+        singular,
+        many,
+        show_count,
+    })
+}
+
+fn has_rich_text(message: &str) -> bool {
+    message.match_indices('<').any(|(index, _)| {
+        message[index + 1..].starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '/')
+    })
+}
+
+/// The value an `fbt:enum` switches on. ICU selects fall back to `other` for unknown values,
+/// which an enum has to spell out.
+fn enum_value(value: &str, cases: &[(String, String)]) -> String {
+    if !cases.iter().any(|(case, _)| case == "other") {
+        return value.to_string();
+    }
+    let keys = cases
+        .iter()
+        .filter(|(case, _)| case != "other")
+        .map(|(case, _)| js_string(case))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("[{keys}].includes({value}) ? {value} : 'other'")
+}
+
+fn enum_range(cases: &[(String, String)]) -> String {
+    let cases = cases
+        .iter()
+        .map(|(case, text)| format!("{}: {}", object_key(case), js_string(text)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{ {cases} }}")
+}
+
+fn render_call(
+    module: &str,
+    pieces: &[Piece],
+    values: &IndexMap<String, String>,
+    desc: &str,
+) -> String {
+    let parts = pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => js_string(text),
+            Piece::Param(argument) => {
+                format!(
+                    "{module}.param({}, {})",
+                    js_string(argument),
+                    values[argument]
+                )
+            }
+            Piece::Plural {
+                argument,
+                singular,
+                many,
+                show_count,
+            } => {
+                let mut options = vec![format!("many: {}", js_string(many))];
+                if *show_count {
+                    options.push("showCount: 'yes'".to_string());
+                    options.push(format!("name: {}", js_string(argument)));
+                }
+                format!(
+                    "{module}.plural({}, {}, {{ {} }})",
+                    js_string(singular),
+                    values[argument],
+                    options.join(", ")
+                )
+            }
+            Piece::Enum { argument, cases } => format!(
+                "{module}.enum({}, {})",
+                enum_value(&values[argument], cases),
+                enum_range(cases)
+            ),
+        })
+        .collect::<Vec<_>>();
+    let text = match pieces {
+        [Piece::Text(_)] => parts[0].clone(),
+        _ => format!("[{}]", parts.join(", ")),
+    };
+    format!("{module}({text}, {})", js_string(desc))
+}
+
+fn render_jsx(pieces: &[Piece], values: &IndexMap<String, String>, desc: &str) -> String {
+    let children = pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => jsx_text(text),
+            Piece::Param(argument) => format!(
+                "<fbt:param {}>{{{}}}</fbt:param>",
+                jsx_attr("name", argument),
+                values[argument]
+            ),
+            Piece::Plural {
+                argument,
+                singular,
+                many,
+                show_count,
+            } => {
+                let mut attrs =
+                    format!("count={{{}}} {}", values[argument], jsx_attr("many", many));
+                if *show_count {
+                    attrs.push_str(&format!(
+                        " showCount=\"yes\" {}",
+                        jsx_attr("name", argument)
+                    ));
+                }
+                format!("<fbt:plural {attrs}>{}</fbt:plural>", jsx_text(singular))
+            }
+            Piece::Enum { argument, cases } => format!(
+                "<fbt:enum enum-range={{{}}} value={{{}}} />",
+                enum_range(cases),
+                enum_value(&values[argument], cases)
+            ),
+        })
+        .collect::<String>();
+    format!("<fbt {}>{children}</fbt>", jsx_attr("desc", desc))
+}
+
+/// `key="value"`, or `key={'value'}` when the value cannot be a JSX attribute string.
+fn jsx_attr(key: &str, value: &str) -> String {
+    if value.contains(['"', '&', '\\', '\n', '\r']) {
+        format!("{key}={{{}}}", js_string(value))
+    } else {
+        format!("{key}=\"{value}\"")
+    }
+}
+
+struct Migrator<'a> {
+    source: &'a str,
+    filename: &'a str,
+    config: Option<&'a str>,
+    file: &'a SourceModule,
+    imports: ReactIntlImports,
+    intl: IntlBindings,
+    descriptors: Descriptors,
+    edits: Vec<Edit>,
+    /// The fbtee modules the migrated phrases use.
+    modules: Vec<&'static str>,
+    messages: Vec<MigratedMessage>,
+    skipped: Vec<String>,
+}
+
+impl Migrator<'_> {
+    fn text(&self, span: Span) -> &str {
+        let (start, end) = self.file.range(span);
+        &self.source[start..end]
+    }
+
+    fn skip(&mut self, span: Span, reason: &str) {
+        let (start, _) = self.file.range(span);
+        self.skipped.push(format!(
+            "{}:{}: {reason}",
+            self.filename,
+            line_number(self.source, start)
+        ));
+    }
+
+    fn replace(&mut self, span: Span, text: String) {
+        let (start, end) = self.file.range(span);
+        self.edits.push(Edit::new(start, end, text));
+    }
+
+    /// A `defineMessages` entry such as `messages.save`.
+    fn referenced_descriptor(&self, expr: &Expr) -> Result<Descriptor, String> {
+        let not_found = || {
+            "the message descriptor must be an object literal or a defineMessages entry."
+                .to_string()
+        };
+        let Expr::Member(member) = crate::unwrap_static_expr(expr) else {
+            return Err(not_found());
+        };
+        let (Expr::Ident(object), Some(key)) = (
+            member.obj.as_ref(),
+            match &member.prop {
+                MemberProp::Ident(ident) => Some(ident.sym.to_string()),
+                MemberProp::Computed(computed) => crate::expr_as_string(&computed.expr),
+                MemberProp::PrivateName(_) => None,
+            },
+        ) else {
+            return Err(not_found());
+        };
+        self.descriptors
+            .get(&*object.sym)
+            .and_then(|entries| entries.get(&key))
+            .cloned()
+            .unwrap_or_else(|| Err(not_found()))
+    }
+
+    /// The source of each value in a `values` object literal.
+    fn values(&self, expr: &Expr) -> Result<IndexMap<String, String>, String> {
+        let error = || "values must be an object literal.".to_string();
+        let Expr::Object(object) = crate::unwrap_static_expr(expr) else {
+            return Err(error());
+        };
+        let mut values = IndexMap::new();
+        for prop in &object.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                return Err(error());
+            };
+            match prop.as_ref() {
+                Prop::KeyValue(prop) => {
+                    let key = crate::prop_name_to_string(&prop.key).ok_or_else(error)?;
+                    values.insert(key, self.text(prop.value.span()).to_string());
+                }
+                Prop::Shorthand(ident) => {
+                    values.insert(ident.sym.to_string(), ident.sym.to_string());
+                }
+                _ => return Err(error()),
+            }
+        }
+        Ok(values)
+    }
+
+    fn migrate_element(&mut self, element: &JSXElement) -> Result<(), String> {
+        if !element.children.is_empty() {
+            return Err("FormattedMessage children have no fbt equivalent.".to_string());
+        }
+        let mut descriptor = Descriptor::default();
+        let mut values = None;
+        for attr in &element.opening.attrs {
+            let attr = match attr {
+                JSXAttrOrSpread::SpreadElement(spread) => {
+                    descriptor = self.referenced_descriptor(&spread.expr)?;
+                    continue;
+                }
+                JSXAttrOrSpread::JSXAttr(attr) => attr,
+            };
+            let JSXAttrName::Ident(name) = &attr.name else {
+                return Err("namespaced FormattedMessage props are not supported.".to_string());
+            };
+            let value = match &attr.value {
+                Some(JSXAttrValue::Str(value)) => Ok(value.value.to_atom_lossy().to_string()),
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) => Err(expr.as_ref()),
+                _ => return Err(format!("'{}' must have a value.", name.sym)),
+            };
+            let string = |value: Result<String, &Expr>| match value {
+                Ok(value) => Ok(value),
+                Err(expr) => string_value(expr, &name.sym),
+            };
+            match &*name.sym {
+                "id" => descriptor.id = Some(string(value)?),
+                "defaultMessage" => descriptor.default_message = Some(string(value)?),
+                "description" => descriptor.description = Some(string(value)?),
+                "values" => {
+                    let Err(expr) = value else {
+                        return Err("values must be an object literal.".to_string());
+                    };
+                    values = Some(self.values(expr)?);
+                }
+                prop => {
+                    return Err(format!(
+                        "FormattedMessage prop '{prop}' has no fbt equivalent."
+                    ))
+                }
+            }
+        }
+        let phrase = self.migrate(descriptor, values, Form::Element)?;
+        self.replace(element.span, phrase);
+        Ok(())
+    }
+
+    fn migrate_call(&mut self, call: &CallExpr, module: &'static str) -> Result<(), String> {
+        if call.args.is_empty()
+            || call.args.len() > 2
+            || call.args.iter().any(|arg| arg.spread.is_some())
+        {
+            return Err(
+                "formatMessage must be called with a descriptor and optional values.".to_string(),
+            );
+        }
+        let descriptor = match crate::unwrap_static_expr(&call.args[0].expr) {
+            Expr::Object(object) => Descriptor::from_object(object)?,
+            expr => self.referenced_descriptor(expr)?,
+        };
+        let values = call
+            .args
+            .get(1)
+            .map(|arg| self.values(&arg.expr))
+            .transpose()?;
+        let phrase = self.migrate(descriptor, values, Form::Call(module))?;
+        self.replace(call.span, phrase);
+        Ok(())
+    }
+
+    fn visit_format_message(&mut self, call: &CallExpr, module: &'static str) {
+        if let Err(reason) = self.migrate_call(call, module) {
+            self.skip(call.span, &reason);
+            call.visit_children_with(self);
+        }
+    }
+
+    /// The phrase for a message, in the given form.
+    fn migrate(
+        &mut self,
+        descriptor: Descriptor,
+        values: Option<IndexMap<String, String>>,
+        form: Form,
+    ) -> Result<String, String> {
+        let message = descriptor
+            .default_message
+            .ok_or("the message has no defaultMessage.")?;
+        if has_rich_text(&message) {
+            return Err("rich text tags have no fbt equivalent.".to_string());
+        }
+        if message.contains("selectordinal") {
+            return Err("ordinal plurals have no fbt equivalent.".to_string());
+        }
+        let pieces = pieces(&parse_icu(&message)?)?;
+        let values = values.unwrap_or_default();
+        for piece in &pieces {
+            if let Piece::Param(argument)
+            | Piece::Plural { argument, .. }
+            | Piece::Enum { argument, .. } = piece
+            {
+                if !values.contains_key(argument) {
+                    return Err(format!("no value is given for '{argument}'."));
+                }
+            }
+        }
+        let desc = descriptor
+            .description
+            .or_else(|| descriptor.id.clone())
+            .ok_or("the message has no description or id.")?;
+
+        let module = form.module();
+        let phrase = match form {
+            Form::Element => render_jsx(&pieces, &values, &desc),
+            Form::Call(module) => render_call(module, &pieces, &values, &desc),
+        };
+        let compiled = collect_phrases(
+            &format!("{}\n{phrase};\n", fbtee_import_statement(&[module])),
+            self.filename,
+            self.config,
+        )
+        .map_err(|error| format!("the migrated phrase does not compile. {error}"))?
+        .pop()
+        .ok_or("the migrated phrase does not compile.")?;
+        if let Some(id) = descriptor.id {
+            let level_arguments = compiled
+                .jsfbt
+                .as_ref()
+                .map(level_arguments)
+                .unwrap_or_default();
+            let mut arguments = IndexMap::new();
+            let variations = pieces.iter().filter_map(|piece| match piece {
+                Piece::Plural { argument, .. } | Piece::Enum { argument, .. } => Some(argument),
+                _ => None,
+            });
+            for (argument, level_argument) in variations.zip(level_arguments) {
+                arguments.entry(argument.clone()).or_insert(level_argument);
+            }
+            self.messages.push(MigratedMessage {
+                id,
+                default_message: message,
+                phrase: compiled,
+                arguments,
+            });
+        }
+        if !self.modules.contains(&module) {
+            self.modules.push(module);
+        }
+        Ok(phrase)
+    }
+}
+
+impl Visit for Migrator<'_> {
+    noop_visit_type!();
+
+    fn visit_jsx_element(&mut self, element: &JSXElement) {
+        if let JSXElementName::Ident(name) = &element.opening.name {
+            if self
+                .imports
+                .formatted_message
+                .iter()
+                .any(|local| *local == *name.sym)
+            {
+                match self.migrate_element(element) {
+                    Ok(()) => return,
+                    Err(reason) => self.skip(element.span, &reason),
+                }
+            }
+        }
+        element.visit_children_with(self);
+    }
+
+    fn visit_jsx_element_child(&mut self, child: &JSXElementChild) {
+        if let JSXElementChild::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        }) = child
+        {
+            if let Expr::Call(call) = expr.as_ref() {
+                if self.intl.is_format_message(call) {
+                    self.visit_format_message(call, "fbt");
+                    return;
+                }
+            }
+        }
+        child.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if self.intl.is_format_message(call) {
+            self.visit_format_message(call, "fbs");
+        } else {
+            call.visit_children_with(self);
+        }
+    }
+}
+
+/// The local names of react-intl `intl` objects and of `formatMessage` functions taken from them.
+#[derive(Default)]
+struct IntlBindings {
+    /// The locals `useIntl` and `createIntl` are imported as.
+    factories: Vec<String>,
+    /// Whether `injectIntl` is imported, making `intl` props and their destructured bindings
+    /// react-intl objects.
+    inject_intl: bool,
+    instances: Vec<String>,
+    formatters: Vec<String>,
+}
+
+impl IntlBindings {
+    fn find(module: &Module, imports: &ReactIntlImports) -> Self {
+        let mut bindings = Self {
+            factories: [imports.use_intl.clone(), imports.create_intl.clone()].concat(),
+            inject_intl: imports.inject_intl,
+            ..Self::default()
+        };
+        if !bindings.factories.is_empty() || bindings.inject_intl {
+            module.visit_with(&mut bindings);
+        }
+        bindings
+    }
+
+    fn is_intl(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Ident(ident) => self.instances.iter().any(|name| *name == *ident.sym),
+            // `props.intl` or `this.props.intl` in a component wrapped with `injectIntl`.
+            Expr::Member(MemberExpr {
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => self.inject_intl && prop.sym == "intl",
+            _ => false,
+        }
+    }
+
+    /// `formatMessage(...)` or `intl.formatMessage(...)` on a tracked binding.
+    fn is_format_message(&self, call: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+        match callee.as_ref() {
+            Expr::Ident(ident) => self.formatters.iter().any(|name| *name == *ident.sym),
+            Expr::Member(member) => {
+                matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == "formatMessage")
+                    && self.is_intl(&member.obj)
+            }
+            _ => false,
+        }
+    }
+
+    /// Tracks the `intl` and `formatMessage` bindings of an object pattern destructuring an
+    /// `intl` object, or, with `injectIntl`, props.
+    fn bind_object(&mut self, object: &ObjectPat, is_intl: bool) {
+        for prop in &object.props {
+            let (key, value) = match prop {
+                ObjectPatProp::KeyValue(prop) => {
+                    let Some(key) = crate::prop_name_to_string(&prop.key) else {
+                        continue;
+                    };
+                    (key, prop.value.as_ref())
+                }
+                ObjectPatProp::Assign(prop) => {
+                    (prop.key.sym.to_string(), &Pat::Ident(prop.key.clone()))
+                }
+                ObjectPatProp::Rest(_) => continue,
+            };
+            match (key.as_str(), value) {
+                ("formatMessage", Pat::Ident(local)) if is_intl => {
+                    self.formatters.push(local.sym.to_string());
+                }
+                ("intl", Pat::Ident(local)) if !is_intl && self.inject_intl => {
+                    self.instances.push(local.sym.to_string());
+                }
+                ("intl", Pat::Object(object)) if !is_intl && self.inject_intl => {
+                    self.bind_object(object, true);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Visit for IntlBindings {
+    noop_visit_type!();
+
+    /// `const intl = useIntl()`, `const { formatMessage } = createIntl(...)` or, with
+    /// `injectIntl`, `const { intl } = this.props`.
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        declarator.visit_children_with(self);
+        let is_intl = matches!(
+            declarator.init.as_deref(),
+            Some(Expr::Call(CallExpr { callee: Callee::Expr(callee), .. }))
+                if matches!(callee.as_ref(), Expr::Ident(callee)
+                    if self.factories.iter().any(|name| *name == *callee.sym))
+        );
+        match &declarator.name {
+            Pat::Ident(local) if is_intl => self.instances.push(local.sym.to_string()),
+            Pat::Object(object) => self.bind_object(object, is_intl),
+            _ => {}
+        }
+    }
+
+    /// `({ intl }) => ...` in a component wrapped with `injectIntl`.
+    fn visit_pat(&mut self, pat: &Pat) {
+        pat.visit_children_with(self);
+        if let Pat::Object(object) = pat {
+            self.bind_object(object, false);
+        }
+    }
+}
+
+/// Removes `defineMessages` and `useIntl` declarations whose bindings the migration left unused.
+fn remove_unused_bindings(source: &str, filename: &str) -> Result<String, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let imports = ReactIntlImports::find(&file.module);
+    let calls = [
+        imports.define_messages,
+        imports.use_intl,
+        imports.create_intl,
+    ]
+    .concat();
+    Ok(remove_unused_declarations(&file, source, &calls))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrate(source: &str) -> MigratedSource {
+        migrate_react_intl(source, "App.tsx", None).unwrap()
+    }
+
+    const INBOX: &str = "import { FormattedMessage } from 'react-intl';
+
+export const Inbox = ({ count, name, kind }) => (
+  <p>
+    <FormattedMessage
+      id=\"inbox.summary\"
+      defaultMessage=\"{name}, you have {count, plural, one {# message} other {# messages}} in {kind, select, work {Work} other {Personal}}.\"
+      description=\"Inbox summary\"
+      values={{ name, count, kind: folder.kind }}
+    />
+  </p>
+);
+";
+
+    #[test]
+    fn migrates_formatted_messages_with_plurals_and_selects() {
+        let migrated = migrate(INBOX);
+        assert_eq!(migrated.migrated, 1, "{:?}", migrated.skipped);
+        assert_eq!(
+            migrated.source,
+            "import { fbt } from 'fbtee';
+
+export const Inbox = ({ count, name, kind }) => (
+  <p>
+    <fbt desc=\"Inbox summary\"><fbt:param name=\"name\">{name}</fbt:param>, you have <fbt:plural count={count} many=\"messages\" showCount=\"yes\" name=\"count\">message</fbt:plural> in <fbt:enum enum-range={{ work: 'Work', other: 'Personal' }} value={['work'].includes(folder.kind) ? folder.kind : 'other'} />.</fbt>
+  </p>
+);
+"
+        );
+        assert_eq!(
+            collect_phrases(&migrated.source, "App.tsx", None)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn migrates_format_message_calls_with_defined_messages() {
+        let migrated = migrate(
+            "import { defineMessages, useIntl } from 'react-intl';
+
+const messages = defineMessages({
+  save: { id: 'editor.save', defaultMessage: 'Save', description: 'Save button' },
+  saved: { id: 'editor.saved', defaultMessage: 'Saved {time}' },
+});
+
+export function Toolbar({ time }) {
+  const intl = useIntl();
+  document.title = intl.formatMessage(messages.save).toUpperCase();
+  return <p>{intl.formatMessage(messages.saved, { time: format(time) })}</p>;
+}
+
+export function Status() {
+  const intl = useIntl();
+  return <Badge intl={intl} />;
+}
+",
+        );
+        assert_eq!(migrated.migrated, 2, "{:?}", migrated.skipped);
+        assert_eq!(
+            migrated.source,
+            "import { useIntl } from 'react-intl';
+import { fbs, fbt } from 'fbtee';
+
+export function Toolbar({ time }) {
+  document.title = fbs('Save', 'Save button').toUpperCase();
+  return <p>{fbt(['Saved ', fbt.param('time', format(time))], 'editor.saved')}</p>;
+}
+
+export function Status() {
+  const intl = useIntl();
+  return <Badge intl={intl} />;
+}
+"
+        );
+        let ids: Vec<_> = migrated
+            .messages
+            .iter()
+            .map(|message| &message.id)
+            .collect();
+        assert_eq!(ids, ["editor.save", "editor.saved"]);
+    }
+
+    #[test]
+    fn only_migrates_format_message_on_react_intl_objects() {
+        let source = "import { MessageFormatter } from './my-own-i18n';
+
+const m = new MessageFormatter();
+export const label = m.formatMessage({ defaultMessage: 'Save changes', description: 'Save' });
+";
+        let migrated = migrate(source);
+        assert_eq!(migrated.migrated, 0);
+        assert_eq!(migrated.source, source);
+        assert!(migrated.skipped.is_empty(), "{:?}", migrated.skipped);
+
+        let migrated = migrate(
+            "import { injectIntl, useIntl } from 'react-intl';
+
+const Title = injectIntl(({ intl }) => <h1>{intl.formatMessage({ defaultMessage: 'Inbox', description: 'Title' })}</h1>);
+
+function Label() {
+  const { formatMessage } = useIntl();
+  return formatMessage({ defaultMessage: 'Label', description: 'Label' }) + router.formatMessage('x');
+}
+",
+        );
+        assert_eq!(migrated.migrated, 2, "{:?}", migrated.skipped);
+        assert!(migrated.source.contains("<h1>{fbt('Inbox', 'Title')}</h1>"));
+        assert!(migrated
+            .source
+            .contains("return fbs('Label', 'Label') + router.formatMessage('x');"));
+        assert!(!migrated.source.contains("useIntl"), "{}", migrated.source);
+    }
+
+    #[test]
+    fn leaves_messages_fbt_cannot_express() {
+        let source = "import { FormattedMessage, useIntl } from 'react-intl';
+const intl = useIntl();
+const a = <FormattedMessage defaultMessage=\"Read <a>the docs</a>\" values={{ a: link }} />;
+const b = intl.formatMessage({ defaultMessage: 'Hi {name}', description: 'Greeting' });
+const c = intl.formatMessage({ defaultMessage: '{n, plural, =0 {None} other {# items}}', description: 'Count' }, { n });
+";
+        let migrated = migrate(source);
+        assert_eq!(migrated.migrated, 0);
+        assert_eq!(migrated.source, source);
+        assert_eq!(
+            migrated.skipped,
+            [
+                "App.tsx:3: rich text tags have no fbt equivalent.",
+                "App.tsx:4: no value is given for 'name'.",
+                "App.tsx:5: plural 'n' has a '=0' case, but fbt:plural only has singular and plural forms.",
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_translations_under_the_new_hashes() {
+        let migrated = migrate(INBOX);
+        let catalog: IndexMap<String, Value> = serde_json::from_str(
+            r#"{
+              "inbox.summary": "{name}, vous avez {count, plural, one {# message} other {# messages}} dans {kind, select, work {Travail} other {Personnel}}.",
+              "unused": "Inutilisé"
+            }"#,
+        )
+        .unwrap();
+        let group = migrate_translations(&migrated.messages, &catalog, "fr_FR").unwrap();
+        let hash_to_leaf = migrated.messages[0].phrase.hash_to_leaf.as_ref().unwrap();
+        assert_eq!(group.translations.len(), hash_to_leaf.len());
+        let work = hash_to_leaf
+            .iter()
+            .find(|(_, leaf)| leaf.text == "{name}, you have {count} messages in Work.")
+            .map(|(hash, _)| hash)
+            .unwrap();
+        let translations = &group.translations[work].as_ref().unwrap().translations;
+        let texts: Vec<_> = translations
+            .iter()
+            .map(|translation| translation.translation.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "{name}, vous avez {count} message dans Travail.",
+                "{name}, vous avez {count} messages dans Travail."
+            ]
+        );
+    }
+}
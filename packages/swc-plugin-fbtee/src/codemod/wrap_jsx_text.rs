@@ -0,0 +1,502 @@
+//! Wraps JSX text outside of fbt in `<fbt desc="TODO: ...">`, and user-facing attributes in
+//! `fbs()`, as a first pass at translating an app. Descriptions are placeholders to be rewritten.
+
+use super::{apply_edits, binds, fbtee_import, js_string, Edit, SourceModule};
+use crate::lint::{is_fbt_call, is_fbt_element, TRANSLATABLE_ATTRIBUTES};
+use std::collections::HashMap;
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::*,
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+/// Elements whose text is code or markup rather than user-facing copy.
+const VERBATIM_ELEMENTS: &[&str] = &["code", "pre", "script", "style"];
+
+/// Elements that sit inside a sentence. Between two pieces of text they stay in the phrase as
+/// implicit params, so translators can reorder the sentence around them.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "cite", "data", "dfn", "em", "i", "kbd", "mark", "q",
+    "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var",
+];
+
+/// The rewritten source and the number of phrases that were wrapped.
+#[derive(Debug)]
+pub struct WrappedSource {
+    pub source: String,
+    pub phrases: usize,
+}
+
+pub fn wrap_jsx_text(source: &str, filename: &str) -> Result<WrappedSource, String> {
+    let file = SourceModule::parse(source, filename)?;
+    let mut wrapper = Wrapper {
+        source,
+        file: &file,
+        edits: vec![],
+        inlined: vec![],
+        modules: vec![],
+        phrases: 0,
+    };
+    file.module.visit_with(&mut wrapper);
+    let Wrapper {
+        mut edits,
+        modules,
+        phrases,
+        ..
+    } = wrapper;
+    let imports: Vec<&str> = ["fbs", "fbt"]
+        .into_iter()
+        .filter(|module| modules.contains(module) && !binds(&file.module, module))
+        .collect();
+    if !imports.is_empty() {
+        edits.push(fbtee_import(&file, &imports));
+    }
+    Ok(WrappedSource {
+        source: apply_edits(source, edits),
+        phrases,
+    })
+}
+
+struct Wrapper<'a> {
+    source: &'a str,
+    file: &'a SourceModule,
+    edits: Vec<Edit>,
+    /// Elements that were wrapped as part of a surrounding phrase.
+    inlined: Vec<Span>,
+    /// The fbtee modules the wrapped phrases use.
+    modules: Vec<&'static str>,
+    phrases: usize,
+}
+
+impl Wrapper<'_> {
+    fn add_phrase(&mut self, module: &'static str) {
+        if !self.modules.contains(&module) {
+            self.modules.push(module);
+        }
+        self.phrases += 1;
+    }
+
+    fn text(&self, span: Span) -> &str {
+        let (start, end) = self.file.range(span);
+        &self.source[start..end]
+    }
+
+    fn wrap_attrs(&mut self, attrs: &[JSXAttrOrSpread]) {
+        for attr in attrs {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                continue;
+            };
+            let Some((span, text, braced)) = translatable_attr(attr) else {
+                continue;
+            };
+            let text = collapse_whitespace(&text);
+            let call = format!(
+                "fbs({}, {})",
+                js_string(&text),
+                js_string(&format!("TODO: {text}"))
+            );
+            let (start, end) = self.file.range(span);
+            self.edits.push(Edit::new(
+                start,
+                end,
+                if braced { call } else { format!("{{{call}}}") },
+            ));
+            self.add_phrase("fbs");
+        }
+    }
+
+    fn wrap_children(&mut self, children: &[JSXElementChild]) {
+        let mut run = vec![];
+        for child in children {
+            if is_phrase_child(child) {
+                run.push(child);
+            } else {
+                self.wrap_run(&run);
+                run.clear();
+            }
+        }
+        self.wrap_run(&run);
+    }
+
+    /// Wraps a run of adjacent text, expression and inline element children, leaving surrounding
+    /// whitespace and any leading or trailing expressions or elements outside of the phrase.
+    fn wrap_run(&mut self, run: &[&JSXElementChild]) {
+        let Some(first) = run.iter().position(|child| is_text(child)) else {
+            return;
+        };
+        let last = run.iter().rposition(|child| is_text(child)).unwrap();
+        if !run[first..=last]
+            .iter()
+            .any(|child| has_words(&child_text(child)))
+        {
+            return;
+        }
+
+        let mut params = Params::default();
+        let mut body = String::new();
+        let mut desc = String::new();
+        for child in &run[first..=last] {
+            self.push_phrase_child(child, &mut params, &mut body, &mut desc);
+        }
+
+        let leading = body.len() - body.trim_start().len();
+        let trailing = body.len() - body.trim_end().len();
+        let (start, _) = self.file.range(run[first].span());
+        let (_, end) = self.file.range(run[last].span());
+        let desc = format!("TODO: {}", collapse_whitespace(&desc));
+        let desc = if desc.contains('"') {
+            format!("{{{}}}", js_string(&desc))
+        } else {
+            format!("\"{desc}\"")
+        };
+        self.edits.push(Edit::new(
+            start + leading,
+            end - trailing,
+            format!(
+                "<fbt desc={desc}>{}</fbt>",
+                &body[leading..body.len() - trailing]
+            ),
+        ));
+        self.add_phrase("fbt");
+    }
+
+    /// Appends a child to the phrase body and its description. Expressions become params, and
+    /// inline elements keep their tags around their own children.
+    fn push_phrase_child(
+        &mut self,
+        child: &JSXElementChild,
+        params: &mut Params,
+        body: &mut String,
+        desc: &mut String,
+    ) {
+        match child {
+            JSXElementChild::JSXText(text) => {
+                let value = self.text(text.span);
+                body.push_str(value);
+                desc.push_str(value);
+            }
+            JSXElementChild::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) if string_literal(expr).is_none() => {
+                    let expr_source = self.text(expr.span());
+                    let name = params.name(expr, expr_source);
+                    body.push_str(&format!(
+                        "<fbt:param name=\"{name}\">{{{expr_source}}}</fbt:param>"
+                    ));
+                    desc.push_str(&format!("{{{name}}}"));
+                }
+                expr => {
+                    body.push_str(self.text(container.span));
+                    if let JSXExpr::Expr(expr) = expr {
+                        desc.push_str(&string_literal(expr).unwrap_or_default());
+                    }
+                }
+            },
+            JSXElementChild::JSXElement(element) => {
+                self.inlined.push(element.span);
+                body.push_str(self.text(element.opening.span));
+                for child in &element.children {
+                    self.push_phrase_child(child, params, body, desc);
+                }
+                if let Some(closing) = &element.closing {
+                    body.push_str(self.text(closing.span));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Visit for Wrapper<'_> {
+    noop_visit_type!();
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if is_fbt_call(call) {
+            return;
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_jsx_element(&mut self, element: &JSXElement) {
+        if is_fbt_element(&element.opening.name) || self.inlined.contains(&element.span) {
+            return;
+        }
+        self.wrap_attrs(&element.opening.attrs);
+        let is_verbatim = matches!(
+            &element.opening.name,
+            JSXElementName::Ident(ident) if VERBATIM_ELEMENTS.contains(&ident.sym.as_ref())
+        );
+        if !is_verbatim {
+            self.wrap_children(&element.children);
+        }
+        element.visit_children_with(self);
+    }
+
+    fn visit_jsx_fragment(&mut self, fragment: &JSXFragment) {
+        self.wrap_children(&fragment.children);
+        fragment.visit_children_with(self);
+    }
+}
+
+/// Token names for the expressions of one phrase. The same expression reuses its name, and
+/// different expressions that would share a name are numbered.
+#[derive(Default)]
+struct Params {
+    names: HashMap<String, String>,
+}
+
+impl Params {
+    fn name(&mut self, expr: &Expr, source: &str) -> String {
+        if let Some(name) = self.names.get(source) {
+            return name.clone();
+        }
+        let base = param_name(expr).unwrap_or_else(|| "param".to_string());
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.names.values().any(|existing| *existing == name) {
+            name = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        self.names.insert(source.to_string(), name.clone());
+        name
+    }
+}
+
+/// Infers a token name from the identifier, property or function an expression reads.
+fn param_name(expr: &Expr) -> Option<String> {
+    match crate::unwrap_static_expr(expr) {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(ident) => Some(ident.sym.to_string()),
+            MemberProp::Computed(computed) => match computed.expr.as_ref() {
+                Expr::Lit(Lit::Str(value)) => Some(value.value.to_atom_lossy().to_string())
+                    .filter(|name| !name.trim().is_empty()),
+                _ => param_name(&member.obj),
+            },
+            MemberProp::PrivateName(name) => Some(name.name.to_string()),
+        },
+        Expr::OptChain(chain) => match chain.base.as_ref() {
+            OptChainBase::Member(member) => param_name(&Expr::Member(member.clone())),
+            OptChainBase::Call(call) => call_name(&call.callee),
+        },
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => call_name(callee),
+        _ => None,
+    }
+}
+
+/// `getUserName()` and `user.getName()` read a user name and a name.
+fn call_name(callee: &Expr) -> Option<String> {
+    let name = param_name(callee)?;
+    match name.strip_prefix("get") {
+        Some(rest) if rest.starts_with(|ch: char| ch.is_ascii_uppercase()) => {
+            let mut chars = rest.chars();
+            let first = chars.next()?.to_ascii_lowercase();
+            Some(std::iter::once(first).chain(chars).collect())
+        }
+        _ => Some(name),
+    }
+}
+
+/// A translatable attribute with words: its value's span, the text and whether it is braced.
+fn translatable_attr(attr: &JSXAttr) -> Option<(Span, String, bool)> {
+    let JSXAttrName::Ident(name) = &attr.name else {
+        return None;
+    };
+    if !TRANSLATABLE_ATTRIBUTES.contains(&name.sym.as_ref()) {
+        return None;
+    }
+    let (span, text, braced) = match &attr.value {
+        Some(JSXAttrValue::Str(value)) => {
+            (value.span, value.value.to_atom_lossy().to_string(), false)
+        }
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) => match expr.as_ref() {
+            Expr::Lit(Lit::Str(value)) => {
+                (value.span, value.value.to_atom_lossy().to_string(), true)
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    has_words(&text).then_some((span, text, braced))
+}
+
+fn is_phrase_child(child: &JSXElementChild) -> bool {
+    match child {
+        JSXElementChild::JSXText(_) => true,
+        JSXElementChild::JSXExprContainer(JSXExprContainer { expr, .. }) => match expr {
+            JSXExpr::JSXEmptyExpr(_) => true,
+            JSXExpr::Expr(expr) => {
+                !crate::contains_jsx(expr)
+                    && !matches!(crate::unwrap_static_expr(expr), Expr::Call(call) if is_fbt_call(call))
+            }
+        },
+        JSXElementChild::JSXElement(element) => is_inline_element(element),
+        _ => false,
+    }
+}
+
+/// Inline elements whose children can all go in the phrase. Translatable attributes would need
+/// an `fbs()` of their own, which is not compiled inside of an `<fbt>`.
+fn is_inline_element(element: &JSXElement) -> bool {
+    matches!(
+        &element.opening.name,
+        JSXElementName::Ident(ident) if INLINE_ELEMENTS.contains(&ident.sym.as_ref())
+    ) && !element.opening.attrs.iter().any(
+        |attr| matches!(attr, JSXAttrOrSpread::JSXAttr(attr) if translatable_attr(attr).is_some()),
+    ) && element.children.iter().all(is_phrase_child)
+}
+
+/// Whether a child belongs inside the phrase: text and expressions, but not whitespace or comments.
+fn is_text(child: &JSXElementChild) -> bool {
+    match child {
+        JSXElementChild::JSXText(text) => !text.value.trim().is_empty(),
+        JSXElementChild::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(_),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+fn child_text(child: &JSXElementChild) -> String {
+    match child {
+        JSXElementChild::JSXText(text) => text.value.to_string(),
+        JSXElementChild::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        }) => string_literal(expr).unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match crate::unwrap_static_expr(expr) {
+        Expr::Lit(Lit::Str(value)) => Some(value.value.to_atom_lossy().to_string()),
+        _ => None,
+    }
+}
+
+fn has_words(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrap(source: &str) -> WrappedSource {
+        let wrapped = wrap_jsx_text(source, "App.tsx").unwrap();
+        let module = SourceModule::parse(&wrapped.source, "App.tsx")
+            .unwrap()
+            .module;
+        crate::transform_program(Program::Module(module), None, None);
+        wrapped
+    }
+
+    #[test]
+    fn wraps_text_and_turns_expressions_into_params() {
+        let wrapped = wrap(
+            "import React from 'react';
+
+export function Greeting({ user }) {
+  return (
+    <div>
+      Hello {user.name}, you have {getCount()} new {'messages'}.
+      <span>{count}</span>
+    </div>
+  );
+}
+",
+        );
+        assert_eq!(wrapped.phrases, 1);
+        assert_eq!(
+            wrapped.source,
+            "import React from 'react';
+import { fbt } from 'fbtee';
+
+export function Greeting({ user }) {
+  return (
+    <div>
+      <fbt desc=\"TODO: Hello {name}, you have {count} new messages.\">Hello <fbt:param name=\"name\">{user.name}</fbt:param>, you have <fbt:param name=\"count\">{getCount()}</fbt:param> new {'messages'}.</fbt>
+      <span>{count}</span>
+    </div>
+  );
+}
+"
+        );
+    }
+
+    #[test]
+    fn wraps_translatable_attributes_in_fbs_calls() {
+        let wrapped = wrap(
+            "'use client';
+const button = <button title=\"Close  dialog\" aria-label={'Close'} className=\"close\" alt=\"-\">Close</button>;
+",
+        );
+        assert_eq!(wrapped.phrases, 3);
+        assert_eq!(
+            wrapped.source,
+            "'use client';
+import { fbs, fbt } from 'fbtee';
+
+const button = <button title={fbs('Close dialog', 'TODO: Close dialog')} aria-label={fbs('Close', 'TODO: Close')} className=\"close\" alt=\"-\"><fbt desc=\"TODO: Close\">Close</fbt></button>;
+"
+        );
+    }
+
+    #[test]
+    fn keeps_inline_elements_inside_the_sentence() {
+        let wrapped = wrap(
+            "import { fbs, fbt } from 'fbtee';
+const a = <p>Click <a href=\"/x\">here</a> to continue.</p>;
+const b = <p>Signed in as <b>{user.name}</b>. <a title=\"Sign out\">Not you?</a></p>;
+",
+        );
+        assert_eq!(wrapped.phrases, 4);
+        assert_eq!(
+            wrapped.source,
+            "import { fbs, fbt } from 'fbtee';
+const a = <p><fbt desc=\"TODO: Click here to continue.\">Click <a href=\"/x\">here</a> to continue.</fbt></p>;
+const b = <p><fbt desc=\"TODO: Signed in as {name}.\">Signed in as <b><fbt:param name=\"name\">{user.name}</fbt:param></b>.</fbt> <a title={fbs('Sign out', 'TODO: Sign out')}><fbt desc=\"TODO: Not you?\">Not you?</fbt></a></p>;
+"
+        );
+    }
+
+    #[test]
+    fn leaves_fbt_code_and_punctuation_alone() {
+        let source = "import { fbt } from 'fbtee';
+const a = <fbt desc=\"greeting\">Hello</fbt>;
+const b = <p>{fbt('Hi', 'greeting')} - {value}</p>;
+const c = <pre>npm install</pre>;
+";
+        let wrapped = wrap(source);
+        assert_eq!(wrapped.phrases, 0);
+        assert_eq!(wrapped.source, source);
+    }
+
+    #[test]
+    fn numbers_conflicting_param_names() {
+        let wrapped = wrap(
+            "import { fbt } from 'fbtee';
+const a = <>From {from.name} to {to.name}, \"{from.name}\"</>;
+",
+        );
+        assert_eq!(
+            wrapped.source,
+            "import { fbt } from 'fbtee';
+const a = <><fbt desc={'TODO: From {name} to {name2}, \"{name}\"'}>From <fbt:param name=\"name\">{from.name}</fbt:param> to <fbt:param name=\"name2\">{to.name}</fbt:param>, \"<fbt:param name=\"name\">{from.name}</fbt:param>\"</fbt></>;
+"
+        );
+    }
+}
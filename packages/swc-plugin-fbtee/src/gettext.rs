@@ -0,0 +1,638 @@
+use crate::{
+    plural_rules::{Operands, PluralCategory, PluralRules},
+    translations::{
+        CollectFbtOutput, Translation, TranslationEntry, TranslationGroup, Translations,
+        MASK_NUMBER, VARIATION_TYPE_NUMBER,
+    },
+};
+use indexmap::{IndexMap, IndexSet};
+use serde_json::Value;
+
+/// Extracted comment naming the number token that `msgstr[n]` forms vary by.
+const NUMBER_TOKEN_COMMENT: &str = "fbtee-number-token: ";
+
+#[derive(Debug, Default)]
+struct PotEntry {
+    desc: String,
+    references: IndexSet<String>,
+    msgid: String,
+    plural: Option<(String, String)>,
+}
+
+/// Writes a POT template with one message per collected hash. `msgctxt` is `desc|hash`, and
+/// leaves that vary by a number token become `msgid_plural` messages.
+pub fn export_pot(source: &CollectFbtOutput) -> String {
+    let mut entries: IndexMap<String, PotEntry> = IndexMap::new();
+    for phrase in &source.phrases {
+        let Some(hash_to_leaf) = &phrase.hash_to_leaf else {
+            continue;
+        };
+        let reference = phrase.source_reference();
+        let leaves = phrase
+            .jsfbt
+            .as_ref()
+            .map(|jsfbt| jsfbt.leaves())
+            .unwrap_or_default();
+        let number_depth = phrase.jsfbt.as_ref().and_then(|jsfbt| {
+            jsfbt.m.iter().position(|meta| {
+                meta.as_ref().is_some_and(|meta| {
+                    meta.variation_type == Some(VARIATION_TYPE_NUMBER) && meta.token.is_some()
+                })
+            })
+        });
+
+        for (hash, leaf) in hash_to_leaf {
+            let entry = entries.entry(hash.clone()).or_insert_with(|| PotEntry {
+                desc: leaf.desc.clone(),
+                msgid: leaf.text.clone(),
+                ..PotEntry::default()
+            });
+            entry.references.extend(reference.clone());
+
+            let (Some(depth), Some(jsfbt)) = (number_depth, &phrase.jsfbt) else {
+                continue;
+            };
+            let Some((path, _)) = leaves.iter().find(|(_, candidate)| candidate == leaf) else {
+                continue;
+            };
+            if path.get(depth).map(String::as_str) != Some("*") {
+                continue;
+            }
+            let token = jsfbt.m[depth]
+                .as_ref()
+                .and_then(|meta| meta.token.clone())
+                .unwrap_or_default();
+            let singular = leaves
+                .iter()
+                .find(|(candidate, _)| {
+                    candidate.len() == path.len()
+                        && candidate
+                            .iter()
+                            .zip(path)
+                            .enumerate()
+                            .all(
+                                |(index, (a, b))| {
+                                    if index == depth {
+                                        a == "_1"
+                                    } else {
+                                        a == b
+                                    }
+                                },
+                            )
+                })
+                .map(|(_, singular)| singular.text.clone())
+                .unwrap_or_else(|| leaf.text.clone());
+            entry.msgid = singular;
+            entry.plural = Some((token, leaf.text.clone()));
+        }
+    }
+
+    let mut po = String::from(
+        "msgid \"\"\nmsgstr \"\"\n\"MIME-Version: 1.0\\n\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n\"Content-Transfer-Encoding: 8bit\\n\"\n\"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\\n\"\n\"X-Generator: fbtee\\n\"\n",
+    );
+    for (hash, entry) in entries {
+        po.push('\n');
+        if let Some((token, _)) = &entry.plural {
+            po.push_str(&format!("#. {NUMBER_TOKEN_COMMENT}{token}\n"));
+        }
+        for reference in &entry.references {
+            po.push_str(&format!("#: {reference}\n"));
+        }
+        po.push_str(&po_field("msgctxt", &format!("{}|{hash}", entry.desc)));
+        po.push_str(&po_field("msgid", &entry.msgid));
+        match &entry.plural {
+            Some((_, plural)) => {
+                po.push_str(&po_field("msgid_plural", plural));
+                po.push_str(&po_field("msgstr[0]", ""));
+                po.push_str(&po_field("msgstr[1]", ""));
+            }
+            None => po.push_str(&po_field("msgstr", "")),
+        }
+    }
+    po
+}
+
+fn po_field(keyword: &str, value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r");
+    if !value.trim_end_matches('\n').contains('\n') {
+        return format!("{keyword} \"{}\"\n", escaped.replace('\n', "\\n"));
+    }
+    let mut field = format!("{keyword} \"\"\n");
+    for line in escaped.split_inclusive('\n') {
+        field.push_str(&format!("\"{}\"\n", line.replace('\n', "\\n")));
+    }
+    field
+}
+
+#[derive(Debug, Default)]
+struct PoMessage {
+    comments: Vec<String>,
+    flags: Vec<String>,
+    msgctxt: Option<String>,
+    msgid: String,
+    msgid_plural: Option<String>,
+    msgstr: IndexMap<usize, String>,
+}
+
+fn parse_po(po: &str) -> Result<Vec<PoMessage>, String> {
+    let mut messages = vec![];
+    let mut message = PoMessage::default();
+    // A comment or new `msgctxt`/`msgid` after a `msgstr` starts the next message.
+    let mut seen_msgstr = false;
+    let mut field: Option<(&str, Option<usize>)> = None;
+
+    for (index, line) in po.lines().enumerate() {
+        let line = line.trim();
+        let error = |message: &str| format!("Invalid PO file, line {}: {message}", index + 1);
+        if line.is_empty() || line.starts_with("#~") {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            if seen_msgstr {
+                messages.push(std::mem::take(&mut message));
+                seen_msgstr = false;
+            }
+            field = None;
+            if let Some(flags) = comment.strip_prefix(',') {
+                message
+                    .flags
+                    .extend(flags.split(',').map(|flag| flag.trim().to_string()));
+            } else if let Some(extracted) = comment.strip_prefix('.') {
+                message.comments.push(extracted.trim().to_string());
+            }
+            continue;
+        }
+        if line.starts_with('"') {
+            let value = unquote(line).ok_or_else(|| error("unterminated string"))?;
+            let (name, form) = field.ok_or_else(|| error("string without keyword"))?;
+            append_field(&mut message, name, form, &value);
+            continue;
+        }
+
+        let (keyword, rest) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| error("expected a keyword and a string"))?;
+        let value = unquote(rest.trim()).ok_or_else(|| error("unterminated string"))?;
+        let (name, form) = match keyword.split_once('[') {
+            Some((name, form)) => (
+                name,
+                Some(
+                    form.trim_end_matches(']')
+                        .parse::<usize>()
+                        .map_err(|_| error("invalid plural form index"))?,
+                ),
+            ),
+            None => (keyword, None),
+        };
+        let name = match name {
+            "msgctxt" => "msgctxt",
+            "msgid" => "msgid",
+            "msgid_plural" => "msgid_plural",
+            "msgstr" => "msgstr",
+            _ => return Err(error(&format!("unknown keyword '{keyword}'"))),
+        };
+        if matches!(name, "msgctxt" | "msgid") && seen_msgstr {
+            messages.push(std::mem::take(&mut message));
+            seen_msgstr = false;
+        }
+        seen_msgstr |= name == "msgstr";
+        append_field(&mut message, name, form, &value);
+        field = Some((name, form));
+    }
+    if seen_msgstr {
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+fn append_field(message: &mut PoMessage, name: &str, form: Option<usize>, value: &str) {
+    match name {
+        "msgctxt" => message.msgctxt.get_or_insert_default().push_str(value),
+        "msgid" => message.msgid.push_str(value),
+        "msgid_plural" => message.msgid_plural.get_or_insert_default().push_str(value),
+        _ => message
+            .msgstr
+            .entry(form.unwrap_or(0))
+            .or_default()
+            .push_str(value),
+    }
+}
+
+fn unquote(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut output = String::new();
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            output.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            'n' => output.push('\n'),
+            't' => output.push('\t'),
+            'r' => output.push('\r'),
+            other => output.push(other),
+        }
+    }
+    Some(output)
+}
+
+/// Reads a translated PO file into translation entries keyed by the hash in `msgctxt`. Empty
+/// and fuzzy messages are skipped. `locale` overrides the `Language` header.
+pub fn import_po(po: &str, locale: Option<&str>) -> Result<TranslationGroup, String> {
+    let messages = parse_po(po)?;
+    let headers: IndexMap<String, String> = messages
+        .iter()
+        .find(|message| message.msgid.is_empty() && message.msgctxt.is_none())
+        .and_then(|header| header.msgstr.get(&0))
+        .map(|header| {
+            header
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let locale = locale
+        .map(str::to_string)
+        .or_else(|| headers.get("Language").cloned())
+        .filter(|locale| !locale.is_empty())
+        .ok_or_else(|| "PO file has no 'Language' header. Pass a locale explicitly.".to_string())?;
+
+    let mut plural_categories = None;
+    let mut translations = Translations::new();
+    for message in &messages {
+        let Some((desc, hash)) = message
+            .msgctxt
+            .as_deref()
+            .and_then(|context| context.rsplit_once('|'))
+        else {
+            continue;
+        };
+        if message.flags.iter().any(|flag| flag == "fuzzy") {
+            continue;
+        }
+
+        let entry = if message.msgid_plural.is_some() {
+            let token = message
+                .comments
+                .iter()
+                .find_map(|comment| comment.strip_prefix(NUMBER_TOKEN_COMMENT))
+                .ok_or_else(|| {
+                    format!("Plural message '{hash}' is missing its '#. {NUMBER_TOKEN_COMMENT}' comment.")
+                })?;
+            if plural_categories.is_none() {
+                let plural_forms = headers.get("Plural-Forms").ok_or_else(|| {
+                    "PO file has plural messages but no 'Plural-Forms' header.".to_string()
+                })?;
+                plural_categories = Some(plural_form_categories(plural_forms, &locale)?);
+            }
+            let categories = plural_categories.as_deref().unwrap_or_default();
+            let mut seen = IndexSet::new();
+            let translations: Vec<Translation> = message
+                .msgstr
+                .iter()
+                .filter(|(_, value)| !value.is_empty())
+                .filter_map(|(form, value)| {
+                    let category = *categories.get(*form)?;
+                    seen.insert(category).then(|| Translation {
+                        id: None,
+                        translation: value.clone(),
+                        variations: Some(IndexMap::from([(
+                            "0".to_string(),
+                            Value::from(category.variation()),
+                        )])),
+                    })
+                })
+                .collect();
+            TranslationEntry {
+                description: Some(desc.to_string()),
+                tokens: Some(vec![token.to_string()]),
+                translations,
+                types: Some(vec![MASK_NUMBER]),
+                ..TranslationEntry::default()
+            }
+        } else {
+            TranslationEntry {
+                description: Some(desc.to_string()),
+                tokens: Some(vec![]),
+                translations: message
+                    .msgstr
+                    .get(&0)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| Translation {
+                        id: None,
+                        translation: value.clone(),
+                        variations: Some(IndexMap::new()),
+                    })
+                    .into_iter()
+                    .collect(),
+                ..TranslationEntry::default()
+            }
+        };
+        if !entry.translations.is_empty() {
+            translations.insert(hash.to_string(), Some(entry));
+        }
+    }
+
+    Ok(TranslationGroup {
+        fb_locale: locale,
+        extra: IndexMap::new(),
+        translations,
+    })
+}
+
+/// Maps each gettext plural form to the CLDR category most integers selecting it share.
+fn plural_form_categories(plural_forms: &str, locale: &str) -> Result<Vec<PluralCategory>, String> {
+    let field = |name: &str| {
+        plural_forms
+            .split(';')
+            .filter_map(|part| part.split_once('='))
+            .find(|(key, _)| key.trim() == name)
+            .map(|(_, value)| value.trim())
+    };
+    let invalid = || format!("Invalid Plural-Forms header '{plural_forms}'.");
+    let count = field("nplurals")
+        .and_then(|count| count.parse::<usize>().ok())
+        .ok_or_else(invalid)?;
+    let expression = PluralExpression::parse(field("plural").ok_or_else(invalid)?)?;
+    let rules = PluralRules::cardinal(locale);
+
+    let mut votes = vec![IndexMap::<PluralCategory, usize>::new(); count];
+    for number in (0u64..=1000).chain([10_000, 100_000, 1_000_000, 10_000_000]) {
+        let form = expression.evaluate(number as i64) as usize;
+        if let Some(votes) = votes.get_mut(form) {
+            let operands = Operands::from(number);
+            *votes.entry(rules.select_operands(&operands)).or_default() += 1;
+        }
+    }
+    Ok(votes
+        .into_iter()
+        .map(|votes| {
+            votes
+                .into_iter()
+                .max_by_key(|(_, count)| *count)
+                .map_or(PluralCategory::Other, |(category, _)| category)
+        })
+        .collect())
+}
+
+/// A parsed gettext `plural=` C expression over the integer `n`.
+#[derive(Debug)]
+enum PluralExpression {
+    N,
+    Number(i64),
+    Not(Box<PluralExpression>),
+    Binary(Box<PluralExpression>, String, Box<PluralExpression>),
+    Conditional(
+        Box<PluralExpression>,
+        Box<PluralExpression>,
+        Box<PluralExpression>,
+    ),
+}
+
+impl PluralExpression {
+    fn parse(source: &str) -> Result<Self, String> {
+        let mut tokens = vec![];
+        let mut chars = source.chars().peekable();
+        while let Some(&ch) = chars.peek() {
+            if ch.is_whitespace() {
+                chars.next();
+            } else if ch.is_ascii_digit() {
+                let mut number = String::new();
+                while let Some(&digit) = chars.peek().filter(|ch| ch.is_ascii_digit()) {
+                    number.push(digit);
+                    chars.next();
+                }
+                tokens.push(number);
+            } else {
+                chars.next();
+                let pair = chars.peek().map(|next| format!("{ch}{next}"));
+                match pair.as_deref() {
+                    Some("==" | "!=" | "<=" | ">=" | "&&" | "||") => {
+                        chars.next();
+                        tokens.push(pair.unwrap_or_default());
+                    }
+                    _ => tokens.push(ch.to_string()),
+                }
+            }
+        }
+        let mut parser = ExpressionParser { tokens, index: 0 };
+        let expression = parser.conditional()?;
+        match parser.tokens.get(parser.index) {
+            None => Ok(expression),
+            Some(token) => Err(format!(
+                "Unexpected '{token}' in plural expression '{source}'."
+            )),
+        }
+    }
+
+    fn evaluate(&self, n: i64) -> i64 {
+        match self {
+            Self::N => n,
+            Self::Number(value) => *value,
+            Self::Not(value) => i64::from(value.evaluate(n) == 0),
+            Self::Conditional(test, consequent, alternate) => {
+                if test.evaluate(n) != 0 {
+                    consequent.evaluate(n)
+                } else {
+                    alternate.evaluate(n)
+                }
+            }
+            Self::Binary(left, operator, right) => {
+                let (left, right) = (left.evaluate(n), right.evaluate(n));
+                match operator.as_str() {
+                    "||" => i64::from(left != 0 || right != 0),
+                    "&&" => i64::from(left != 0 && right != 0),
+                    "==" => i64::from(left == right),
+                    "!=" => i64::from(left != right),
+                    "<" => i64::from(left < right),
+                    "<=" => i64::from(left <= right),
+                    ">" => i64::from(left > right),
+                    ">=" => i64::from(left >= right),
+                    "+" => left.wrapping_add(right),
+                    "-" => left.wrapping_sub(right),
+                    "*" => left.wrapping_mul(right),
+                    "/" => left.checked_div(right).unwrap_or(0),
+                    _ => left.checked_rem(right).unwrap_or(0),
+                }
+            }
+        }
+    }
+}
+
+struct ExpressionParser {
+    tokens: Vec<String>,
+    index: usize,
+}
+
+const BINARY_PRECEDENCE: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["==", "!="],
+    &["<", "<=", ">", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+impl ExpressionParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.index).map(String::as_str)
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(format!("Expected '{token}' in plural expression."))
+        }
+    }
+
+    fn conditional(&mut self) -> Result<PluralExpression, String> {
+        let test = self.binary(0)?;
+        if self.peek() != Some("?") {
+            return Ok(test);
+        }
+        self.index += 1;
+        let consequent = self.conditional()?;
+        self.expect(":")?;
+        let alternate = self.conditional()?;
+        Ok(PluralExpression::Conditional(
+            Box::new(test),
+            Box::new(consequent),
+            Box::new(alternate),
+        ))
+    }
+
+    fn binary(&mut self, level: usize) -> Result<PluralExpression, String> {
+        let Some(operators) = BINARY_PRECEDENCE.get(level) else {
+            return self.unary();
+        };
+        let mut left = self.binary(level + 1)?;
+        while let Some(operator) = self.peek().filter(|token| operators.contains(token)) {
+            let operator = operator.to_string();
+            self.index += 1;
+            let right = self.binary(level + 1)?;
+            left = PluralExpression::Binary(Box::new(left), operator, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<PluralExpression, String> {
+        let token = self
+            .peek()
+            .ok_or_else(|| "Unexpected end of plural expression.".to_string())?
+            .to_string();
+        self.index += 1;
+        match token.as_str() {
+            "n" => Ok(PluralExpression::N),
+            "!" => Ok(PluralExpression::Not(Box::new(self.unary()?))),
+            "(" => {
+                let expression = self.conditional()?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            _ => token
+                .parse::<i64>()
+                .map(PluralExpression::Number)
+                .map_err(|_| format!("Unexpected '{token}' in plural expression.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> CollectFbtOutput {
+        serde_json::from_str(
+            r#"{"phrases":[{
+              "filename":"src/App.tsx",
+              "loc":{"start":{"line":12,"column":4},"end":{"line":12,"column":40}},
+              "hashToLeaf":{
+                "one==":{"desc":"likes","text":"1 like"},
+                "many==":{"desc":"likes","text":"{number} likes"}
+              },
+              "jsfbt":{"m":[{"token":"number","type":2,"singular":true}],"t":{
+                "*":{"desc":"likes","text":"{number} likes"},
+                "_1":{"desc":"likes","text":"1 like"}
+              }}
+            },{
+              "filename":"src/Other.tsx",
+              "hashToLeaf":{"plain==":{"desc":"Say \"hi\"","text":"Hi\nthere"}},
+              "jsfbt":{"m":[],"t":{"desc":"Say \"hi\"","text":"Hi\nthere"}}
+            }]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn exports_contexts_references_and_plurals() {
+        let pot = export_pot(&source());
+        assert!(pot.contains(
+            "#. fbtee-number-token: number\n#: src/App.tsx:12\nmsgctxt \"likes|many==\"\nmsgid \"1 like\"\nmsgid_plural \"{number} likes\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"\n"
+        ), "{pot}");
+        assert!(pot.contains(
+            "#: src/App.tsx:12\nmsgctxt \"likes|one==\"\nmsgid \"1 like\"\nmsgstr \"\"\n"
+        ));
+        assert!(pot.contains(
+            "#: src/Other.tsx\nmsgctxt \"Say \\\"hi\\\"|plain==\"\nmsgid \"\"\n\"Hi\\n\"\n\"there\"\nmsgstr \"\"\n"
+        ), "{pot}");
+    }
+
+    #[test]
+    fn imports_plural_forms_by_cldr_category() {
+        let pot = export_pot(&source());
+        let po = pot
+            .replace(
+                "\"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\\n\"",
+                "\"Language: ru_RU\\n\"\n\"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\\n\"",
+            )
+            .replace(
+                "msgstr[0] \"\"\nmsgstr[1] \"\"\n",
+                "msgstr[0] \"{number} лайк\"\nmsgstr[1] \"{number} лайка\"\nmsgstr[2] \"{number} лайков\"\n",
+            )
+            .replace("msgid \"1 like\"\nmsgstr \"\"", "msgid \"1 like\"\nmsgstr \"1 лайк\"");
+        let group = import_po(&po, None).unwrap();
+        assert_eq!(group.fb_locale, "ru_RU");
+        assert_eq!(
+            group.translations.keys().collect::<Vec<_>>(),
+            vec!["one==", "many=="]
+        );
+        let entry = group.translations["many=="].as_ref().unwrap();
+        assert_eq!(entry.tokens, Some(vec!["number".to_string()]));
+        assert_eq!(entry.types, Some(vec![MASK_NUMBER]));
+        let variations: Vec<(String, Value)> = entry
+            .translations
+            .iter()
+            .map(|translation| {
+                (
+                    translation.translation.clone(),
+                    translation.variations.as_ref().unwrap()["0"].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            variations,
+            vec![
+                ("{number} лайк".to_string(), Value::from(4)),
+                ("{number} лайка".to_string(), Value::from(20)),
+                ("{number} лайков".to_string(), Value::from(12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_fuzzy_messages_and_requires_a_locale() {
+        let po = "msgid \"\"\nmsgstr \"\"\n\n#, fuzzy\nmsgctxt \"d|h1\"\nmsgid \"A\"\nmsgstr \"B\"\n\nmsgctxt \"d|h2\"\nmsgid \"C\"\nmsgstr \"D\"\n";
+        assert!(import_po(po, None).is_err());
+        let group = import_po(po, Some("de_DE")).unwrap();
+        assert_eq!(group.translations.keys().collect::<Vec<_>>(), vec!["h2"]);
+        assert_eq!(PluralExpression::parse("n != 1").unwrap().evaluate(1), 0);
+    }
+}
@@ -0,0 +1,136 @@
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Algorithm used to derive phrase hash keys (`hk`) from the hash input of a phrase table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashScheme {
+    /// 32-bit jenkins one-at-a-time hash in base 62, as computed by the Babel plugin.
+    #[default]
+    Jenkins,
+    /// Base64 md5 digest, as computed by the collector's `TextPackager`.
+    Md5,
+    /// 64-bit FNV-1a hash in base 62.
+    Fnv1a64,
+}
+
+impl HashScheme {
+    pub const ALL: [HashScheme; 3] = [HashScheme::Jenkins, HashScheme::Md5, HashScheme::Fnv1a64];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashScheme::Jenkins => "jenkins",
+            HashScheme::Md5 => "md5",
+            HashScheme::Fnv1a64 => "fnv1a64",
+        }
+    }
+
+    pub fn hash(self, input: &str) -> String {
+        match self {
+            HashScheme::Jenkins => uint_to_base_n(u64::from(jenkins_hash(input)), 62),
+            HashScheme::Md5 => base64(&Md5::digest(input.as_bytes())),
+            HashScheme::Fnv1a64 => uint_to_base_n(fnv1a64_hash(input), 62),
+        }
+    }
+}
+
+impl fmt::Display for HashScheme {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HashScheme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        HashScheme::ALL
+            .into_iter()
+            .find(|scheme| scheme.as_str() == value)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid hash scheme '{value}'. Expected one of: {}.",
+                    HashScheme::ALL.map(HashScheme::as_str).join(", ")
+                )
+            })
+    }
+}
+
+pub fn jenkins_hash(value: &str) -> u32 {
+    if value.is_empty() {
+        return 0;
+    }
+    let mut hash = 0u32;
+    for byte in value.as_bytes() {
+        hash = hash.wrapping_add(*byte as u32);
+        hash = hash.wrapping_add(hash << 10);
+        hash ^= hash >> 6;
+    }
+    hash = hash.wrapping_add(hash << 3);
+    hash ^= hash >> 11;
+    hash = hash.wrapping_add(hash << 15);
+    hash
+}
+
+fn fnv1a64_hash(value: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    value.as_bytes().iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+pub fn uint_to_base_n(mut value: u64, base: u64) -> String {
+    const SYMBOLS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    if !(2..=62).contains(&base) {
+        return String::new();
+    }
+    let mut output = vec![];
+    loop {
+        output.push(SYMBOLS[(value % base) as usize] as char);
+        value /= base;
+        if value == 0 {
+            break;
+        }
+    }
+    output.into_iter().rev().collect()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const SYMBOLS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::new();
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (index, byte)| {
+            value | (u32::from(*byte) << (16 - 8 * index))
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                output.push(SYMBOLS[((value >> (18 - 6 * index)) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_with_each_scheme() {
+        assert_eq!(HashScheme::Jenkins.hash("hello"), "3GcH2j");
+        assert_eq!(HashScheme::Md5.hash("hello"), "XUFAKrxLKna5cZ2REBfFkg==");
+        assert_eq!(HashScheme::Fnv1a64.hash("hello"), "e5YZDpEaKgz");
+        assert_eq!(HashScheme::Md5.hash(""), "1B2M2Y8AsgTpgAmY7PhCfg==");
+    }
+
+    #[test]
+    fn parses_scheme_names() {
+        assert_eq!("md5".parse::<HashScheme>(), Ok(HashScheme::Md5));
+        assert_eq!(HashScheme::Fnv1a64.to_string(), "fnv1a64");
+        assert!("sha1".parse::<HashScheme>().is_err());
+    }
+}
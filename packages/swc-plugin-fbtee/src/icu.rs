@@ -0,0 +1,773 @@
+use crate::{
+    plural_rules::PluralCategory,
+    translations::{
+        pattern_tokens, CollectFbtOutput, CollectedPhrase, JsFbt, Leaf, Translation,
+        TranslationEntry, TranslationGroup, Translations, MASK_GENDER, MASK_NUMBER,
+        VARIATION_TYPE_GENDER, VARIATION_TYPE_NUMBER,
+    },
+};
+use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// `IntlVariations` gender values used in translation `variations`.
+const GENDER_MALE: i64 = 1;
+const GENDER_FEMALE: i64 = 2;
+const GENDER_UNKNOWN: i64 = 3;
+
+/// How one level of the `jsfbt.t` table is expressed in ICU.
+#[derive(Debug, Clone, PartialEq)]
+enum Level {
+    Enum,
+    Pronoun,
+    Plural(Option<String>),
+    Gender(String),
+}
+
+fn levels(jsfbt: &JsFbt) -> Vec<Level> {
+    let mut levels = vec![];
+    let mut node = &jsfbt.t;
+    let mut depth = 0;
+    while let Value::Object(object) = node {
+        if object.contains_key("text") && object.contains_key("desc") {
+            break;
+        }
+        let meta = jsfbt.m.get(depth).and_then(Option::as_ref);
+        let token = meta.and_then(|meta| meta.token.clone());
+        levels.push(
+            match meta.map(|meta| (meta.range.is_some(), meta.variation_type)) {
+                Some((true, _)) => Level::Enum,
+                Some((_, Some(VARIATION_TYPE_NUMBER))) => Level::Plural(token),
+                Some((_, Some(VARIATION_TYPE_GENDER))) => Level::Gender(token.unwrap_or_default()),
+                _ if object.keys().all(|key| key == "*" || key == "_1") => Level::Plural(None),
+                _ => Level::Pronoun,
+            },
+        );
+        let Some(child) = object.values().next() else {
+            break;
+        };
+        node = child;
+        depth += 1;
+    }
+    levels
+}
+
+/// The ICU argument name of a level: its token, or a synthetic `_enum0`-style name.
+fn level_argument(level: &Level, depth: usize) -> String {
+    match level {
+        Level::Enum => format!("_enum{depth}"),
+        Level::Pronoun => format!("_pronoun{depth}"),
+        Level::Plural(None) => format!("_count{depth}"),
+        Level::Plural(Some(token)) | Level::Gender(token) => argument_name(token),
+    }
+}
+
+/// The ICU argument name of each level of a phrase table, outermost first.
+pub(crate) fn level_arguments(jsfbt: &JsFbt) -> Vec<String> {
+    levels(jsfbt)
+        .iter()
+        .enumerate()
+        .map(|(depth, level)| level_argument(level, depth))
+        .collect()
+}
+
+/// fbt token names such as `=m1` or `=a photo` are not valid ICU argument names.
+fn argument_name(token: &str) -> String {
+    token
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Converts a collected phrase table into one ICU MessageFormat string.
+pub fn phrase_to_icu(jsfbt: &JsFbt) -> String {
+    let levels = levels(jsfbt);
+    node_to_icu(&jsfbt.t, &levels, 0, false)
+}
+
+fn node_to_icu(node: &Value, levels: &[Level], depth: usize, in_plural: bool) -> String {
+    let Value::Object(object) = node else {
+        return String::new();
+    };
+    let Some(level) = levels.get(depth) else {
+        let text = object
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        return text_to_icu(text, in_plural);
+    };
+
+    let is_plural = matches!(level, Level::Plural(_));
+    let mut cases: Vec<(String, String)> = object
+        .iter()
+        .map(|(key, child)| {
+            let key = match (key.as_str(), is_plural) {
+                ("*", _) => "other".to_string(),
+                ("_1", true) => "=1".to_string(),
+                (key, _) => key.to_string(),
+            };
+            (
+                key,
+                node_to_icu(child, levels, depth + 1, in_plural || is_plural),
+            )
+        })
+        .collect();
+    if !cases.iter().any(|(key, _)| key == "other") {
+        if let Some((_, first)) = cases.first() {
+            cases.push(("other".to_string(), first.clone()));
+        }
+    }
+    let cases: Vec<String> = cases
+        .into_iter()
+        .map(|(key, message)| format!("{key} {{{message}}}"))
+        .collect();
+    format!(
+        "{{{}, {}, {}}}",
+        level_argument(level, depth),
+        if is_plural { "plural" } else { "select" },
+        cases.join(" ")
+    )
+}
+
+fn text_to_icu(text: &str, in_plural: bool) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start + 1..].find('}').map(|end| start + 1 + end) else {
+            break;
+        };
+        output.push_str(&escape_literal(&rest[..start], in_plural));
+        if end > start + 1 {
+            output.push_str(&format!("{{{}}}", argument_name(&rest[start + 1..end])));
+        } else {
+            output.push_str("'{}'");
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(&escape_literal(rest, in_plural));
+    output
+}
+
+fn escape_literal(text: &str, in_plural: bool) -> String {
+    let mut output = String::new();
+    for ch in text.chars() {
+        match ch {
+            '\'' => output.push_str("''"),
+            '{' | '}' => output.push_str(&format!("'{ch}'")),
+            '#' if in_plural => output.push_str("'#'"),
+            ch => output.push(ch),
+        }
+    }
+    output
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum IcuNode {
+    Text(String),
+    Argument(String),
+    Pound,
+    Select {
+        argument: String,
+        plural: bool,
+        cases: Vec<(String, Vec<IcuNode>)>,
+    },
+}
+
+struct IcuParser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl IcuParser {
+    fn error(&self, message: &str) -> String {
+        format!("Invalid ICU message at offset {}: {message}", self.index)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(ch) = self
+            .peek()
+            .filter(|ch| !ch.is_whitespace() && !matches!(ch, ',' | '{' | '}'))
+        {
+            word.push(ch);
+            self.index += 1;
+        }
+        word
+    }
+
+    fn message(&mut self, in_plural: bool, nested: bool) -> Result<Vec<IcuNode>, String> {
+        let mut nodes = vec![];
+        let mut text = String::new();
+        while let Some(ch) = self.peek() {
+            match ch {
+                '\'' => {
+                    self.index += 1;
+                    match self.peek() {
+                        Some('\'') => {
+                            text.push('\'');
+                            self.index += 1;
+                        }
+                        Some(next)
+                            if matches!(next, '{' | '}' | '|') || (next == '#' && in_plural) =>
+                        {
+                            while let Some(ch) = self.peek() {
+                                self.index += 1;
+                                if ch != '\'' {
+                                    text.push(ch);
+                                } else if self.peek() == Some('\'') {
+                                    text.push('\'');
+                                    self.index += 1;
+                                } else {
+                                    break;
+                                }
+                            }
+                        }
+                        _ => text.push('\''),
+                    }
+                }
+                '{' => {
+                    if !text.is_empty() {
+                        nodes.push(IcuNode::Text(std::mem::take(&mut text)));
+                    }
+                    self.index += 1;
+                    nodes.push(self.argument()?);
+                }
+                '}' if nested => break,
+                '}' => return Err(self.error("unexpected '}'")),
+                '#' if in_plural => {
+                    if !text.is_empty() {
+                        nodes.push(IcuNode::Text(std::mem::take(&mut text)));
+                    }
+                    nodes.push(IcuNode::Pound);
+                    self.index += 1;
+                }
+                ch => {
+                    text.push(ch);
+                    self.index += 1;
+                }
+            }
+        }
+        if !text.is_empty() {
+            nodes.push(IcuNode::Text(text));
+        }
+        Ok(nodes)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{expected}'")))
+        }
+    }
+
+    fn argument(&mut self) -> Result<IcuNode, String> {
+        self.skip_whitespace();
+        let argument = self.word();
+        if argument.is_empty() {
+            return Err(self.error("expected an argument name"));
+        }
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.index += 1;
+            return Ok(IcuNode::Argument(argument));
+        }
+        self.expect(',')?;
+        self.skip_whitespace();
+        let kind = self.word();
+        self.skip_whitespace();
+        if !matches!(kind.as_str(), "select" | "plural" | "selectordinal") {
+            // Formatted arguments such as `{count, number}` keep only their value.
+            let mut depth = 0;
+            while let Some(ch) = self.peek() {
+                self.index += 1;
+                match ch {
+                    '{' => depth += 1,
+                    '}' if depth == 0 => return Ok(IcuNode::Argument(argument)),
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+            }
+            return Err(self.error("unterminated argument"));
+        }
+        self.expect(',')?;
+
+        let plural = kind != "select";
+        let mut cases = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.index += 1;
+                    break;
+                }
+                None => return Err(self.error("unterminated select")),
+                _ => {}
+            }
+            let key = self.word();
+            if key.starts_with("offset:") {
+                continue;
+            }
+            self.expect('{')?;
+            let message = self.message(plural, true)?;
+            self.expect('}')?;
+            cases.push((key, message));
+        }
+        Ok(IcuNode::Select {
+            argument,
+            plural,
+            cases,
+        })
+    }
+}
+
+pub(crate) fn parse_icu(message: &str) -> Result<Vec<IcuNode>, String> {
+    let mut parser = IcuParser {
+        chars: message.chars().collect(),
+        index: 0,
+    };
+    parser.message(false, false)
+}
+
+/// One translated variant of a leaf: its variation values by token, and its text.
+type Variant = (IndexMap<String, Value>, String);
+
+/// A select case to expand, with the variation it assigns when the level varies.
+type Branch<'a> = (Option<(String, Value)>, &'a [IcuNode]);
+
+struct Expander<'a> {
+    levels: &'a [Level],
+    path: &'a [String],
+    /// ICU argument names mapped back to fbt token names.
+    tokens: IndexMap<String, String>,
+}
+
+impl Expander<'_> {
+    fn level(&self, argument: &str) -> Option<(usize, &Level)> {
+        self.levels
+            .iter()
+            .enumerate()
+            .find(|(depth, level)| level_argument(level, *depth) == argument)
+    }
+
+    fn token(&self, argument: &str) -> String {
+        self.tokens
+            .get(argument)
+            .cloned()
+            .unwrap_or_else(|| argument.to_string())
+    }
+
+    fn expand(&self, nodes: &[IcuNode], plural_token: Option<&str>) -> Vec<Variant> {
+        let mut variants: Vec<Variant> = vec![(IndexMap::new(), String::new())];
+        for node in nodes {
+            match node {
+                IcuNode::Text(text) => variants
+                    .iter_mut()
+                    .for_each(|(_, output)| output.push_str(text)),
+                IcuNode::Argument(argument) => {
+                    let token = format!("{{{}}}", self.token(argument));
+                    variants
+                        .iter_mut()
+                        .for_each(|(_, output)| output.push_str(&token));
+                }
+                IcuNode::Pound => {
+                    let pound =
+                        plural_token.map_or("#".to_string(), |token| format!("{{{token}}}"));
+                    variants
+                        .iter_mut()
+                        .for_each(|(_, output)| output.push_str(&pound));
+                }
+                IcuNode::Select {
+                    argument,
+                    plural,
+                    cases,
+                } => {
+                    let level = self.level(argument);
+                    let token = match level {
+                        Some((_, Level::Plural(Some(token)) | Level::Gender(token))) => {
+                            Some(token.as_str())
+                        }
+                        _ => None,
+                    };
+                    let inner_token = if *plural {
+                        token.or(plural_token)
+                    } else {
+                        plural_token
+                    };
+                    let key = level
+                        .and_then(|(depth, _)| self.path.get(depth))
+                        .map(String::as_str);
+
+                    let branches: Vec<Branch> = match (level, key, token) {
+                        (Some((_, level)), Some("*"), Some(token)) => cases
+                            .iter()
+                            .filter_map(|(case, body)| {
+                                let value = variation_value(level, case)?;
+                                Some((Some((token.to_string(), value)), body.as_slice()))
+                            })
+                            .collect(),
+                        _ => {
+                            let preferred: &[&str] = match key {
+                                Some("*") | None => &["other"],
+                                Some("_1") => &["=1", "one", "other"],
+                                Some(key) => &[key, "other"],
+                            };
+                            preferred
+                                .iter()
+                                .find_map(|preferred| {
+                                    cases.iter().find(|(case, _)| case == preferred)
+                                })
+                                .map(|(_, body)| vec![(None, body.as_slice())])
+                                .unwrap_or_default()
+                        }
+                    };
+
+                    let mut next = vec![];
+                    for (assignments, output) in &variants {
+                        for (assignment, body) in &branches {
+                            for (inner, text) in self.expand(body, inner_token) {
+                                let mut assignments = assignments.clone();
+                                if let Some((token, value)) = assignment {
+                                    assignments.insert(token.clone(), value.clone());
+                                }
+                                assignments.extend(inner);
+                                next.push((assignments, format!("{output}{text}")));
+                            }
+                        }
+                    }
+                    variants = next;
+                }
+            }
+        }
+        variants
+    }
+}
+
+fn variation_value(level: &Level, case: &str) -> Option<Value> {
+    match level {
+        Level::Gender(_) => match case {
+            "male" => Some(GENDER_MALE),
+            "female" => Some(GENDER_FEMALE),
+            "other" => Some(GENDER_UNKNOWN),
+            _ => None,
+        },
+        Level::Plural(_) => case
+            .parse::<PluralCategory>()
+            .ok()
+            .map(|category| i64::from(category.variation())),
+        _ => None,
+    }
+    .map(Value::from)
+}
+
+/// Converts a translated ICU message for `phrase` back into one translation entry per leaf hash.
+pub fn icu_to_translations(
+    phrase: &CollectedPhrase,
+    message: &str,
+) -> Result<Translations, String> {
+    icu_nodes_to_translations(phrase, &parse_icu(message)?)
+}
+
+/// Converts a parsed ICU message whose select and plural arguments are named like
+/// `phrase_to_icu` names them.
+pub(crate) fn icu_nodes_to_translations(
+    phrase: &CollectedPhrase,
+    nodes: &[IcuNode],
+) -> Result<Translations, String> {
+    let (Some(jsfbt), Some(hash_to_leaf)) = (&phrase.jsfbt, &phrase.hash_to_leaf) else {
+        return Err("Phrase has no `jsfbt` table or `hashToLeaf` map.".to_string());
+    };
+    let levels = levels(jsfbt);
+
+    let mut tokens = IndexMap::new();
+    for (depth, level) in levels.iter().enumerate() {
+        if let Level::Plural(Some(token)) | Level::Gender(token) = level {
+            tokens.insert(level_argument(level, depth), token.clone());
+        }
+    }
+    for leaf in hash_to_leaf.values() {
+        for token in pattern_tokens(&leaf.text) {
+            tokens.insert(argument_name(&token), token);
+        }
+    }
+
+    let mut translations = Translations::new();
+    for (path, leaf) in jsfbt.leaves() {
+        let Some(hash) = hash_for_leaf(hash_to_leaf, &leaf) else {
+            continue;
+        };
+        let expander = Expander {
+            levels: &levels,
+            path: &path,
+            tokens: tokens.clone(),
+        };
+        let variants = expander.expand(nodes, None);
+        let variation_tokens: IndexSet<String> = variants
+            .iter()
+            .flat_map(|(assignments, _)| assignments.keys().cloned())
+            .collect();
+        let types = variation_tokens
+            .iter()
+            .map(|token| {
+                let is_gender = levels
+                    .iter()
+                    .any(|level| level == &Level::Gender(token.clone()));
+                if is_gender {
+                    MASK_GENDER
+                } else {
+                    MASK_NUMBER
+                }
+            })
+            .collect();
+        let mut seen = IndexSet::new();
+        let entry_translations = variants
+            .into_iter()
+            .filter_map(|(assignments, translation)| {
+                let variations: IndexMap<String, Value> = variation_tokens
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, token)| {
+                        Some((index.to_string(), assignments.get(token)?.clone()))
+                    })
+                    .collect();
+                let key = serde_json::to_string(&variations).unwrap_or_default();
+                seen.insert(key).then_some(Translation {
+                    id: None,
+                    translation,
+                    variations: Some(variations),
+                })
+            })
+            .collect();
+        translations.insert(
+            hash.to_string(),
+            Some(TranslationEntry {
+                description: Some(leaf.desc.clone()),
+                tokens: Some(variation_tokens.into_iter().collect()),
+                translations: entry_translations,
+                types: Some(types),
+                ..TranslationEntry::default()
+            }),
+        );
+    }
+    Ok(translations)
+}
+
+/// A phrase as an ICU message, in the `{id: {defaultMessage, description}}` shape
+/// FormatJS tooling extracts.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IcuMessage {
+    pub default_message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Exports each collected phrase as an ICU message keyed by its first leaf hash.
+pub fn export_icu(source: &CollectFbtOutput) -> IndexMap<String, IcuMessage> {
+    let mut messages = IndexMap::new();
+    for phrase in &source.phrases {
+        let (Some(jsfbt), Some(hash_to_leaf)) = (&phrase.jsfbt, &phrase.hash_to_leaf) else {
+            continue;
+        };
+        let Some((hash, leaf)) = hash_to_leaf.first() else {
+            continue;
+        };
+        messages.entry(hash.clone()).or_insert_with(|| IcuMessage {
+            default_message: phrase_to_icu(jsfbt),
+            description: Some(leaf.desc.clone()),
+        });
+    }
+    messages
+}
+
+/// Imports translated ICU messages keyed by the ids `export_icu` wrote. Values may be
+/// plain strings or `{defaultMessage}` / `{message}` objects.
+pub fn import_icu(
+    source: &CollectFbtOutput,
+    messages: &IndexMap<String, Value>,
+    locale: &str,
+) -> Result<TranslationGroup, String> {
+    let mut translations = Translations::new();
+    for (id, value) in messages {
+        let message =
+            message_text(value).ok_or_else(|| format!("Message '{id}' is not a string."))?;
+        let phrase = source
+            .phrases
+            .iter()
+            .find(|phrase| {
+                phrase
+                    .hash_to_leaf
+                    .as_ref()
+                    .and_then(IndexMap::first)
+                    .is_some_and(|(hash, _)| hash == id)
+            })
+            .ok_or_else(|| format!("Unknown message id '{id}'."))?;
+        let entries = icu_to_translations(phrase, message)
+            .map_err(|error| format!("{error} (message '{id}')"))?;
+        translations.extend(entries);
+    }
+    Ok(TranslationGroup {
+        fb_locale: locale.to_string(),
+        extra: IndexMap::new(),
+        translations,
+    })
+}
+
+/// The message of a FormatJS-style catalog entry: a string, or an object with a
+/// `defaultMessage` or `message`.
+pub(crate) fn message_text(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(message) => Some(message),
+        Value::Object(object) => object
+            .get("defaultMessage")
+            .or_else(|| object.get("message"))
+            .and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+fn hash_for_leaf<'a>(hash_to_leaf: &'a IndexMap<String, Leaf>, leaf: &Leaf) -> Option<&'a str> {
+    hash_to_leaf
+        .iter()
+        .find(|(_, candidate)| *candidate == leaf)
+        .map(|(hash, _)| hash.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_phrase(json: &str) -> CollectedPhrase {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn plural_phrase() -> CollectedPhrase {
+        parse_phrase(
+            r#"{
+              "hashToLeaf":{
+                "one":{"desc":"d","text":"{name} has 1 photo of {=m2}"},
+                "many":{"desc":"d","text":"{name} has {count} photos of {=m2}"}
+              },
+              "jsfbt":{"m":[{"token":"name","type":1},{"token":"count","type":2,"singular":true}],"t":{"*":{
+                "*":{"desc":"d","text":"{name} has {count} photos of {=m2}"},
+                "_1":{"desc":"d","text":"{name} has 1 photo of {=m2}"}
+              }}}
+            }"#,
+        )
+    }
+
+    #[test]
+    fn converts_tables_to_icu() {
+        let phrase = plural_phrase();
+        assert_eq!(
+            phrase_to_icu(phrase.jsfbt.as_ref().unwrap()),
+            "{name, select, other {{count, plural, other {{name} has {count} photos of {_m2}} =1 {{name} has 1 photo of {_m2}}}}}"
+        );
+
+        let phrase = parse_phrase(
+            r#"{"jsfbt":{"m":[{"range":["a","b"]},null],"t":{
+              "a":{"1":{"desc":"d","text":"A her #1's"},"*":{"desc":"d","text":"A them"}},
+              "b":{"1":{"desc":"d","text":"B her"},"*":{"desc":"d","text":"B them"}}
+            }}}"#,
+        );
+        assert_eq!(
+            phrase_to_icu(phrase.jsfbt.as_ref().unwrap()),
+            "{_enum0, select, a {{_pronoun1, select, 1 {A her #1''s} other {A them}}} b {{_pronoun1, select, 1 {B her} other {B them}}} other {{_pronoun1, select, 1 {A her #1''s} other {A them}}}}"
+        );
+    }
+
+    #[test]
+    fn converts_translated_icu_into_variations() {
+        let phrase = plural_phrase();
+        let translations = icu_to_translations(
+            &phrase,
+            "{name, select, male {{count, plural, =1 {{name} hat ein Foto von {_m2}} one {{name} hat # Foto} other {{name} hat # Fotos von {_m2}}}} other {{count, plural, =1 {{name} hat 1 Foto von {_m2}} other {{name} hat {count} Fotos von {_m2}}}}}",
+        )
+        .unwrap();
+
+        let many = translations["many"].as_ref().unwrap();
+        assert_eq!(
+            many.tokens,
+            Some(vec!["name".to_string(), "count".to_string()])
+        );
+        assert_eq!(many.types, Some(vec![MASK_GENDER, MASK_NUMBER]));
+        let variants: Vec<(String, String)> = many
+            .translations
+            .iter()
+            .map(|translation| {
+                (
+                    serde_json::to_string(translation.variations.as_ref().unwrap()).unwrap(),
+                    translation.translation.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            variants,
+            vec![
+                (
+                    r#"{"0":1,"1":4}"#.to_string(),
+                    "{name} hat {count} Foto".to_string()
+                ),
+                (
+                    r#"{"0":1,"1":24}"#.to_string(),
+                    "{name} hat {count} Fotos von {=m2}".to_string()
+                ),
+                (
+                    r#"{"0":3,"1":24}"#.to_string(),
+                    "{name} hat {count} Fotos von {=m2}".to_string()
+                ),
+            ]
+        );
+
+        let one = translations["one"].as_ref().unwrap();
+        assert_eq!(one.tokens, Some(vec!["name".to_string()]));
+        assert_eq!(
+            one.translations[0].translation,
+            "{name} hat ein Foto von {=m2}"
+        );
+        assert_eq!(
+            one.translations[1].translation,
+            "{name} hat 1 Foto von {=m2}"
+        );
+    }
+
+    #[test]
+    fn round_trips_source_messages() {
+        let phrase = plural_phrase();
+        let message = phrase_to_icu(phrase.jsfbt.as_ref().unwrap());
+        let translations = icu_to_translations(&phrase, &message).unwrap();
+        for (hash, leaf) in phrase.hash_to_leaf.as_ref().unwrap() {
+            let entry = translations[hash].as_ref().unwrap();
+            assert!(entry
+                .translations
+                .iter()
+                .all(|translation| translation.translation == leaf.text));
+        }
+        assert!(parse_icu("{a, select, other {x}").is_err());
+        assert_eq!(
+            parse_icu("It''s '{literal}' {n, number}").unwrap(),
+            vec![
+                IcuNode::Text("It's {literal} ".to_string()),
+                IcuNode::Argument("n".to_string())
+            ]
+        );
+    }
+}
@@ -1,14 +1,40 @@
+use hashing::HashScheme;
+use lint::{empty_phrase_message, unhelpful_description_message, LintLevel, LintOptions};
+use pseudo_locale::{pseudo_localize, PseudoLocaleOptions};
 use serde::Deserialize;
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
 use swc_core::{
-    common::DUMMY_SP,
+    common::{errors::HANDLER, util::take::Take, EqIgnoreSpan, DUMMY_SP},
     ecma::{
         ast::*,
         atoms::Wtf8Atom,
-        visit::{noop_visit_mut_type, VisitMut, VisitMutWith},
+        visit::{noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith},
+    },
+    plugin::{
+        metadata::TransformPluginMetadataContextKind, plugin_transform,
+        proxies::TransformPluginProgramMetadata,
     },
-    plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
 };
+use translations::{
+    CollectedPhrase, HashToLeaf, JsFbt, JsFbtMetaEntry, Leaf, VARIATION_TYPE_GENDER,
+    VARIATION_TYPE_NUMBER,
+};
+
+pub mod carry_over;
+pub mod codemod;
+pub mod gettext;
+pub mod hashing;
+pub mod icu;
+mod lint;
+pub mod locale;
+pub mod plural_rules;
+mod pseudo_locale;
+pub mod translations;
+pub mod validation;
+pub mod xliff;
 
 const GENDER: i32 = 1;
 const NUMBER: i32 = 0;
@@ -22,18 +48,47 @@ struct PluginOptions {
     fbt_common: BTreeMap<String, String>,
     #[serde(default)]
     fbt_enum_manifest: BTreeMap<String, BTreeMap<String, String>>,
+    #[serde(default)]
+    hash_scheme: HashScheme,
+    /// Maximum number of table leaves one phrase may expand to.
+    #[serde(default)]
+    max_phrase_leaves: Option<usize>,
+    /// Leaf count above which a phrase compiles with a warning.
+    #[serde(default)]
+    warn_phrase_leaves: Option<usize>,
+    /// Emit static tables and options objects as module-level constants, shared per `hk`.
+    #[serde(default)]
+    hoist_tables: bool,
+    #[serde(default)]
+    pseudo_locale: Option<PseudoLocaleOptions>,
+    /// Drop table branches that a constant enum value, plural count or pronoun gender can never
+    /// select. `hk` is still computed over the full table.
+    #[serde(default)]
+    fold_constant_variations: bool,
+    #[serde(default)]
+    lint: LintOptions,
 }
 
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    let options = match metadata.get_transform_plugin_config() {
-        Some(config) => serde_json::from_str::<PluginOptions>(&config).unwrap_or_else(|error| {
-            compile_error(&format!(
-                "Invalid fbtee SWC plugin config. Received '{error}'."
-            ))
-        }),
-        None => PluginOptions::default(),
-    };
+    transform_program(
+        program,
+        metadata.get_transform_plugin_config().as_deref(),
+        metadata
+            .get_context(&TransformPluginMetadataContextKind::Filename)
+            .as_deref(),
+    )
+}
+
+/// Applies the plugin to `program` with the JSON config the plugin accepts, outside of the SWC
+/// plugin host. `filename` is only used to scope lint rules. Errors panic, as they do in the
+/// plugin.
+pub fn transform_program(
+    program: Program,
+    config: Option<&str>,
+    filename: Option<&str>,
+) -> Program {
+    let options = plugin_options(config);
     if options.collect_fbt {
         compile_error(
             "Option 'collectFbt' is not supported by the fbtee SWC runtime compiler. Use the Babel collector to extract phrases.",
@@ -41,13 +96,46 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
     }
 
     let mut program = program;
-    program.visit_mut_with(&mut FbteeTransform::new(options));
+    program.visit_mut_with(&mut FbteeTransform::new(
+        options,
+        filename.map(str::to_string),
+    ));
     program
 }
 
+fn plugin_options(config: Option<&str>) -> PluginOptions {
+    match config {
+        Some(config) => serde_json::from_str::<PluginOptions>(config).unwrap_or_else(|error| {
+            compile_error(&format!(
+                "Invalid fbtee SWC plugin config. Received '{error}'."
+            ))
+        }),
+        None => PluginOptions::default(),
+    }
+}
+
+/// Compiles `program` like `transform_program` and returns its phrases in the shape `fbtee
+/// collect` writes them, in the order they were compiled. Inner phrases come before the phrases
+/// that contain them.
+pub(crate) fn collect_phrases(
+    program: Program,
+    config: Option<&str>,
+    filename: Option<&str>,
+) -> Vec<CollectedPhrase> {
+    let mut transform = FbteeTransform::new(plugin_options(config), filename.map(str::to_string));
+    transform.collected = Some(vec![]);
+    let mut program = program;
+    program.visit_mut_with(&mut transform);
+    transform.collected.unwrap_or_default()
+}
+
 struct FbteeTransform {
     options: PluginOptions,
-    imported_enums: BTreeMap<String, BTreeMap<String, String>>,
+    filename: Option<String>,
+    /// Local enum bindings, mapped to their module key in `fbt_enum_manifest`.
+    imported_enums: BTreeMap<String, String>,
+    /// Module-level `const` bindings with a static string value.
+    module_strings: BTreeMap<String, String>,
     local_bindings: Vec<BTreeMap<String, LocalBinding>>,
     seen_fbs_import: bool,
     seen_fbt_import: bool,
@@ -55,6 +143,10 @@ struct FbteeTransform {
     fbt_ident: Option<Ident>,
     used_fbs: bool,
     used_fbt: bool,
+    warnings: Vec<String>,
+    hoisted: Option<HoistedConstants>,
+    /// Phrases in collector format, when collecting for a codemod.
+    collected: Option<Vec<CollectedPhrase>>,
 }
 
 #[derive(Clone)]
@@ -64,10 +156,12 @@ enum LocalBinding {
 }
 
 impl FbteeTransform {
-    fn new(options: PluginOptions) -> Self {
+    fn new(options: PluginOptions, filename: Option<String>) -> Self {
         Self {
             options,
+            filename,
             imported_enums: BTreeMap::new(),
+            module_strings: BTreeMap::new(),
             local_bindings: Vec::new(),
             seen_fbs_import: false,
             seen_fbt_import: false,
@@ -75,11 +169,91 @@ impl FbteeTransform {
             fbt_ident: None,
             used_fbs: false,
             used_fbt: false,
+            warnings: Vec::new(),
+            hoisted: None,
+            collected: None,
+        }
+    }
+
+    fn warn(&mut self, message: String) {
+        if HANDLER.is_set() {
+            HANDLER.with(|handler| handler.warn(&format!("fbtee SWC plugin warning: {message}")));
+        }
+        self.warnings.push(message);
+    }
+
+    fn report(&mut self, level: LintLevel, message: String) {
+        match level {
+            LintLevel::Off => {}
+            LintLevel::Warn => self.warn(message),
+            LintLevel::Error => compile_error(&message),
+        }
+    }
+
+    /// Runs the `emptyStrings` and `unhelpfulDesc` lint rules on a phrase written by hand.
+    /// Phrases from `fbtCommon` have their descriptions checked where they are configured.
+    fn lint_phrase(&mut self, phrase: &Phrase, common: bool) {
+        let lint = &self.options.lint;
+        let (empty_strings, unhelpful_desc) = (lint.empty_strings, lint.unhelpful_desc);
+        let module = phrase.module.as_str();
+        if empty_strings != LintLevel::Off
+            && phrase
+                .parts
+                .iter()
+                .all(|part| matches!(part, Part::Text(text) if text.trim().is_empty()))
+        {
+            self.report(empty_strings, empty_phrase_message(module));
+        }
+        if unhelpful_desc != LintLevel::Off && !common {
+            let text = phrase
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    Part::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<String>();
+            if let Some(message) = unhelpful_description_message(module, &phrase.desc, &text) {
+                self.report(unhelpful_desc, message);
+            }
+        }
+    }
+
+    fn check_leaf_budget(&mut self, builder: &RuntimeBuilder) {
+        let (max, warn) = (
+            self.options.max_phrase_leaves,
+            self.options.warn_phrase_leaves,
+        );
+        if max.is_none() && warn.is_none() {
+            return;
+        }
+        let dimensions = builder.variation_dimensions();
+        let leaves = dimensions
+            .iter()
+            .fold(1usize, |leaves, (_, size)| leaves.saturating_mul(*size));
+        let describe = |limit: usize| {
+            let dimensions = dimensions
+                .iter()
+                .map(|(label, size)| format!("{label} ({size})"))
+                .collect::<Vec<_>>()
+                .join(" x ");
+            format!(
+                "Phrase '{}' expands to {leaves} table leaves, over the limit of {limit}. Variation dimensions: {dimensions}.",
+                builder.phrase.desc
+            )
+        };
+        if let Some(max) = max.filter(|max| leaves > *max) {
+            compile_error(&describe(max));
+        }
+        if let Some(warn) = warn.filter(|warn| leaves > *warn) {
+            self.warn(describe(warn));
         }
     }
 
     fn add_local_binding(&mut self, name: &str) {
-        if (name == "fbt" || name == "fbs") && !self.local_bindings.is_empty() {
+        if (name == "fbt" || name == "fbs" || self.module_strings.contains_key(name))
+            && !self.local_bindings.is_empty()
+        {
             self.local_bindings
                 .last_mut()
                 .expect("expected a local binding scope")
@@ -199,7 +373,140 @@ impl FbteeTransform {
                         }
                     }
                 }
-                ModuleItem::Stmt(stmt) => self.collect_stmt_bindings(stmt),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    if let Decl::Var(var) = &export.decl {
+                        self.collect_module_strings(var);
+                    }
+                }
+                ModuleItem::Stmt(stmt) => {
+                    if let Stmt::Decl(Decl::Var(var)) = stmt {
+                        self.collect_module_strings(var);
+                    }
+                    self.collect_stmt_bindings(stmt);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `const` bindings cannot be reassigned, so their static values can be read at compile time.
+    fn collect_module_strings(&mut self, var: &VarDecl) {
+        if var.kind != VarDeclKind::Const {
+            return;
+        }
+        for declarator in &var.decls {
+            if let (Pat::Ident(ident), Some(init)) = (&declarator.name, &declarator.init) {
+                if let Some(value) = self.static_string(init) {
+                    self.module_strings.insert(ident.id.sym.to_string(), value);
+                }
+            }
+        }
+    }
+
+    /// Evaluates descriptions, token names and other compile-time strings. Accepts string
+    /// literals, templates, `+` concatenation, parentheses, TypeScript type wrappers and
+    /// module-level string constants that are not shadowed.
+    fn static_string(&self, expr: &Expr) -> Option<String> {
+        match unwrap_static_expr(expr) {
+            Expr::Ident(ident) => {
+                let name = ident.sym.as_ref();
+                if self
+                    .local_bindings
+                    .iter()
+                    .skip(1)
+                    .any(|bindings| bindings.contains_key(name))
+                {
+                    return None;
+                }
+                self.module_strings.get(name).cloned()
+            }
+            Expr::Tpl(template) => {
+                let mut output = String::new();
+                for (index, quasi) in template.quasis.iter().enumerate() {
+                    match &quasi.cooked {
+                        Some(cooked) => output.push_str(&wtf8_to_string(cooked)),
+                        None => output.push_str(&quasi.raw),
+                    }
+                    if let Some(expr) = template.exprs.get(index) {
+                        output.push_str(&self.static_string(expr)?);
+                    }
+                }
+                Some(output)
+            }
+            Expr::Bin(binary) if binary.op == BinaryOp::Add => Some(format!(
+                "{}{}",
+                self.static_string(&binary.left)?,
+                self.static_string(&binary.right)?
+            )),
+            expr => expr_as_string(expr),
+        }
+    }
+
+    fn static_arg(&self, arg: &ExprOrSpread) -> Option<String> {
+        self.static_string(&arg.expr)
+    }
+
+    /// Replaces statically known option values with string literals, so `parse_object` reads
+    /// them like literals.
+    fn fold_static_options(&self, expr: &mut Expr) {
+        let Expr::Object(object) = unwrap_static_expr_mut(expr) else {
+            return;
+        };
+        for prop in &mut object.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(key_value) = prop.as_mut() {
+                    if prop_name_to_string(&key_value.key)
+                        .is_some_and(|key| STATIC_OPTION_KEYS.contains(&key.as_str()))
+                    {
+                        if let Some(value) = self.static_string(&key_value.value) {
+                            *key_value.value = string_expr(value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces statically known attribute values on `element` and the fbtee constructs under it
+    /// with string attributes. Descriptions of implicit elements read construct names before the
+    /// constructs are parsed, so this runs over the whole tree first.
+    fn fold_static_jsx_attrs(&self, element: &mut JSXElement) {
+        if jsx_element_kind(&element.opening.name).is_some() {
+            for attr in &mut element.opening.attrs {
+                let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                    continue;
+                };
+                if !jsx_attr_name(&attr.name)
+                    .is_some_and(|name| STATIC_OPTION_KEYS.contains(&name.as_str()))
+                {
+                    continue;
+                }
+                let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) = &attr.value
+                else {
+                    continue;
+                };
+                if let Some(value) = self.static_string(expr) {
+                    attr.value = Some(JSXAttrValue::Str(Str {
+                        span: DUMMY_SP,
+                        value: Wtf8Atom::from(value),
+                        raw: None,
+                    }));
+                }
+            }
+        }
+        self.fold_static_jsx_children(&mut element.children);
+    }
+
+    fn fold_static_jsx_children(&self, children: &mut [JSXElementChild]) {
+        for child in children {
+            match child {
+                JSXElementChild::JSXElement(element) => self.fold_static_jsx_attrs(element),
+                JSXElementChild::JSXFragment(fragment) => {
+                    self.fold_static_jsx_children(&mut fragment.children)
+                }
                 _ => {}
             }
         }
@@ -251,20 +558,22 @@ impl FbteeTransform {
     fn collect_var_declarator_binding(&mut self, declarator: &VarDeclarator) {
         if is_fbtee_require(declarator.init.as_deref()) {
             self.mark_fbtee_binding(&declarator.name);
-        } else if let Some(enum_map) = self.enum_manifest_from_require(declarator.init.as_deref()) {
+        } else if let Some(module) = self.enum_manifest_from_require(declarator.init.as_deref()) {
             if let Pat::Ident(ident) = &declarator.name {
-                self.imported_enums
-                    .insert(ident.id.sym.to_string(), enum_map);
+                self.imported_enums.insert(ident.id.sym.to_string(), module);
             }
         } else {
             self.add_pat_bindings(&declarator.name);
         }
     }
 
-    fn enum_manifest_from_require(&self, expr: Option<&Expr>) -> Option<BTreeMap<String, String>> {
+    fn enum_manifest_from_require(&self, expr: Option<&Expr>) -> Option<String> {
         let source = require_source(expr)?;
         let module = enum_manifest_key(&source)?;
-        self.options.fbt_enum_manifest.get(&module).cloned()
+        self.options
+            .fbt_enum_manifest
+            .contains_key(&module)
+            .then_some(module)
     }
 }
 
@@ -272,9 +581,39 @@ impl VisitMut for FbteeTransform {
     noop_visit_mut_type!();
 
     fn visit_mut_module(&mut self, module: &mut Module) {
+        if self
+            .options
+            .lint
+            .checks_untranslated_strings(self.filename.as_deref())
+        {
+            let level = self.options.lint.untranslated_strings;
+            for message in self.options.lint.untranslated_strings(module) {
+                self.report(level, message);
+            }
+        }
         self.local_bindings.push(BTreeMap::new());
         self.collect_module_bindings(module);
+        if self.options.hoist_tables {
+            self.hoisted = Some(HoistedConstants::new(module));
+        }
         module.visit_mut_children_with(self);
+        if let Some(hoisted) = self
+            .hoisted
+            .take()
+            .filter(|hoisted| !hoisted.items.is_empty())
+        {
+            let index = module
+                .body
+                .iter()
+                .position(|item| {
+                    !is_directive_module_item(item)
+                        && !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
+                })
+                .unwrap_or(module.body.len());
+            module
+                .body
+                .splice(index..index, hoisted.into_module_items());
+        }
         let mut specifiers = vec![];
         if self.used_fbt && !self.seen_fbt_import {
             specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
@@ -324,25 +663,19 @@ impl VisitMut for FbteeTransform {
                 }
             };
         }
-        if let Some(module) = enum_manifest_key(&source) {
-            if let Some(enum_map) = self.options.fbt_enum_manifest.get(&module) {
-                for specifier in &import.specifiers {
-                    match specifier {
-                        ImportSpecifier::Default(default) => {
-                            self.imported_enums
-                                .insert(default.local.sym.to_string(), enum_map.clone());
-                        }
-                        ImportSpecifier::Named(named) => {
-                            self.imported_enums
-                                .insert(named.local.sym.to_string(), enum_map.clone());
-                        }
-                        ImportSpecifier::Namespace(namespace) => {
-                            self.imported_enums
-                                .insert(namespace.local.sym.to_string(), enum_map.clone());
-                        }
-                    }
-                }
-            }
+        let Some(module) = enum_manifest_key(&source)
+            .filter(|module| self.options.fbt_enum_manifest.contains_key(module))
+        else {
+            return;
+        };
+        for specifier in &import.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Namespace(namespace) => &namespace.local,
+            };
+            self.imported_enums
+                .insert(local.sym.to_string(), module.clone());
         }
     }
 
@@ -383,10 +716,9 @@ impl VisitMut for FbteeTransform {
     fn visit_mut_var_declarator(&mut self, declarator: &mut VarDeclarator) {
         if is_fbtee_require(declarator.init.as_deref()) {
             self.mark_fbtee_binding(&declarator.name);
-        } else if let Some(enum_map) = self.enum_manifest_from_require(declarator.init.as_deref()) {
+        } else if let Some(module) = self.enum_manifest_from_require(declarator.init.as_deref()) {
             if let Pat::Ident(ident) = &declarator.name {
-                self.imported_enums
-                    .insert(ident.id.sym.to_string(), enum_map.clone());
+                self.imported_enums.insert(ident.id.sym.to_string(), module);
             }
         } else {
             self.add_pat_bindings(&declarator.name);
@@ -433,7 +765,7 @@ impl VisitMut for FbteeTransform {
     fn visit_mut_jsx_expr_container(&mut self, container: &mut JSXExprContainer) {
         if let JSXExpr::Expr(expr) = &mut container.expr {
             if let Some(next) = self.transform_expr(expr) {
-                *expr = Box::new(next);
+                **expr = next;
                 return;
             }
         }
@@ -443,16 +775,17 @@ impl VisitMut for FbteeTransform {
 }
 
 impl FbteeTransform {
-    fn transform_expr(&mut self, expr: &Expr) -> Option<Expr> {
+    fn transform_expr(&mut self, expr: &mut Expr) -> Option<Expr> {
         match expr {
             Expr::Call(call) => self.transform_call(call),
+            Expr::TaggedTpl(tagged) => self.transform_tagged_template(tagged),
             Expr::JSXElement(element) => self.transform_jsx_element(element),
             Expr::JSXFragment(fragment) => self.transform_jsx_fragment(fragment),
             _ => None,
         }
     }
 
-    fn transform_call(&mut self, call: &CallExpr) -> Option<Expr> {
+    fn transform_call(&mut self, call: &mut CallExpr) -> Option<Expr> {
         let module = call_module_name(call)?;
         if self.is_shadowed(module) {
             return None;
@@ -463,6 +796,13 @@ impl FbteeTransform {
             return self.transform_common_call(call, module);
         }
 
+        if method == Some("t") {
+            compile_error(&format!(
+                "{0}.t(...) must tag a template literal, as in {0}.t('description')`Hello`.",
+                module.as_str()
+            ));
+        }
+
         if method.is_some() {
             if method.is_some_and(is_construct_method) {
                 compile_error(
@@ -502,57 +842,125 @@ impl FbteeTransform {
         Some(self.runtime_call(phrase))
     }
 
-    fn transform_fbt_call(&mut self, call: &CallExpr, module: ModuleName) -> Option<Expr> {
-        let raw_contents = call
-            .args
-            .first()
-            .map(|arg| arg.expr.as_ref())
-            .unwrap_or_else(|| {
+    /// ``fbt.t(desc, options)`...` `` compiles like `fbt(`...`, desc, options)`. A bare
+    /// ``fbt.t`...` `` tag is looked up in `fbtCommon`.
+    fn transform_tagged_template(&mut self, tagged: &mut TaggedTpl) -> Option<Expr> {
+        let module = tagged_template_module(&tagged.tag)?;
+        if self.is_shadowed(module) {
+            return None;
+        }
+        let name = module.as_str();
+        if let Some(expr) = tagged
+            .tpl
+            .exprs
+            .iter()
+            .find(|expr| !is_tagged_template_value(expr, module))
+        {
+            compile_error(&format!(
+                "{name}.t`...` interpolations must be {name} constructs or strings. Wrap the {} value in {name}.param('name', value).",
+                expr_type(expr)
+            ));
+        }
+
+        let Expr::Call(tag) = tagged.tag.as_mut() else {
+            let label = expr_as_string(&Expr::Tpl(*tagged.tpl.take())).unwrap_or_default();
+            let desc = self.options.fbt_common.get(&label).cloned().unwrap_or_else(|| {
                 compile_error(&format!(
-                    "{}(...) needs at least two arguments: text and description.",
-                    module.as_str()
+                    "{name}.t`...` needs a description. Use {name}.t('description')`...`, or add the text to 'fbtCommon'."
                 ))
             });
+            return Some(self.runtime_call(Phrase {
+                desc,
+                module,
+                options: CallOptions::default(),
+                parts: vec![Part::Text(label)],
+            }));
+        };
+        let options = tag
+            .args
+            .get_mut(1)
+            .map(|arg| {
+                self.fold_static_options(&mut arg.expr);
+                parse_call_options(&mut arg.expr)
+            })
+            .unwrap_or_default();
+        let desc = normalize_spaces(
+            &tag.args
+                .first()
+                .and_then(|arg| self.static_arg(arg))
+                .unwrap_or_else(|| {
+                    compile_error(&format!(
+                        "{name}.t(...) description must be a string literal."
+                    ))
+                }),
+            options.preserve_whitespace,
+        );
+        let mut contents = Expr::Tpl(*tagged.tpl.take());
+        let parts = self
+            .parse_expr_contents(&mut contents, module, &options)
+            .unwrap_or_else(|error| compile_error(&error));
+
+        let phrase = Phrase {
+            desc,
+            module,
+            options,
+            parts,
+        };
+        self.lint_phrase(&phrase, false);
+        Some(self.runtime_call(phrase))
+    }
+
+    fn transform_fbt_call(&mut self, call: &mut CallExpr, module: ModuleName) -> Option<Expr> {
+        if call.args.is_empty() {
+            compile_error(&format!(
+                "{}(...) needs at least two arguments: text and description.",
+                module.as_str()
+            ));
+        }
         let options = call
             .args
-            .get(2)
-            .map(|arg| parse_call_options(&arg.expr))
+            .get_mut(2)
+            .map(|arg| {
+                self.fold_static_options(&mut arg.expr);
+                parse_call_options(&mut arg.expr)
+            })
             .unwrap_or_default();
         let desc = normalize_spaces(
-            &call.args.get(1).and_then(arg_as_string).unwrap_or_else(|| {
-                compile_error(&format!(
-                    "{}(...) description must be a string literal.",
-                    module.as_str()
-                ))
-            }),
+            &call
+                .args
+                .get(1)
+                .and_then(|arg| self.static_arg(arg))
+                .unwrap_or_else(|| {
+                    compile_error(&format!(
+                        "{}(...) description must be a string literal.",
+                        module.as_str()
+                    ))
+                }),
             options.preserve_whitespace,
         );
         let parts = self
-            .parse_expr_contents(raw_contents, module, &options)
+            .parse_expr_contents(&mut call.args[0].expr, module, &options)
             .unwrap_or_else(|error| compile_error(&error));
 
-        Some(self.runtime_call(Phrase {
+        let phrase = Phrase {
             desc,
             module,
             options,
             parts,
-        }))
+        };
+        self.lint_phrase(&phrase, false);
+        Some(self.runtime_call(phrase))
     }
 
-    fn transform_jsx_fragment(&mut self, fragment: &JSXFragment) -> Option<Expr> {
-        let children = self.jsx_children_to_expr(&fragment.children);
-        children.map(Expr::JSXFragment).or_else(|| {
-            Some(Expr::JSXFragment(JSXFragment {
-                span: fragment.span,
-                opening: fragment.opening.clone(),
-                children: vec![],
-                closing: fragment.closing.clone(),
-            }))
-        })
+    fn transform_jsx_fragment(&mut self, fragment: &mut JSXFragment) -> Option<Expr> {
+        Some(Expr::JSXFragment(
+            self.jsx_children_to_expr(&mut fragment.children),
+        ))
     }
 
-    fn transform_jsx_element(&mut self, element: &JSXElement) -> Option<Expr> {
+    fn transform_jsx_element(&mut self, element: &mut JSXElement) -> Option<Expr> {
         let (module, node) = jsx_element_kind(&element.opening.name)?;
+        self.fold_static_jsx_attrs(element);
         if let Some(kind) = node {
             let options = CallOptions::default();
             let parts = self
@@ -566,14 +974,18 @@ impl FbteeTransform {
             }));
         }
 
-        let attrs = JsxAttrs::new(&element.opening.attrs);
+        let mut attrs = JsxAttrs::new(&mut element.opening.attrs);
+        attrs
+            .reject_spreads(module.as_str())
+            .unwrap_or_else(|error| compile_error(&error));
         let options = CallOptions {
             preserve_whitespace: attrs.boolish("preserveWhitespace").unwrap_or(false),
             project: attrs.string("project"),
             subject: attrs.expr("subject"),
         };
 
-        let desc = if attrs.boolish("common").unwrap_or(false) {
+        let common = attrs.boolish("common").unwrap_or(false);
+        let desc = if common {
             let text = normalize_spaces(
                 &jsx_text_content(&element.children),
                 options.preserve_whitespace,
@@ -594,21 +1006,24 @@ impl FbteeTransform {
             ))
         };
 
+        let mut descriptions = JsxDescriptions::new(&element.children, &options);
         let parts = self
-            .parse_jsx_children(&element.children, module, &options, &element.children)
+            .parse_jsx_children(&mut element.children, module, &options, &mut descriptions)
             .unwrap_or_else(|error| compile_error(&error));
 
-        Some(self.runtime_call(Phrase {
+        let phrase = Phrase {
             desc,
             module,
             options,
             parts,
-        }))
+        };
+        self.lint_phrase(&phrase, common);
+        Some(self.runtime_call(phrase))
     }
 
     fn parse_expr_contents(
         &mut self,
-        expr: &Expr,
+        expr: &mut Expr,
         module: ModuleName,
         options: &CallOptions,
     ) -> Result<Vec<Part>, String> {
@@ -619,14 +1034,14 @@ impl FbteeTransform {
             ))]),
             Expr::Array(array) => {
                 let mut parts = vec![];
-                for elem in array.elems.iter().flatten() {
-                    parts.extend(self.parse_expr_contents(&elem.expr, module, options)?);
+                for elem in array.elems.iter_mut().flatten() {
+                    parts.extend(self.parse_expr_contents(&mut elem.expr, module, options)?);
                 }
                 Ok(parts)
             }
             Expr::Bin(binary) if binary.op == BinaryOp::Add => {
-                let mut parts = self.parse_expr_contents(&binary.left, module, options)?;
-                parts.extend(self.parse_expr_contents(&binary.right, module, options)?);
+                let mut parts = self.parse_expr_contents(&mut binary.left, module, options)?;
+                parts.extend(self.parse_expr_contents(&mut binary.right, module, options)?);
                 Ok(parts)
             }
             Expr::Tpl(template) => {
@@ -643,7 +1058,7 @@ impl FbteeTransform {
                             options.preserve_whitespace,
                         )));
                     }
-                    if let Some(expr) = template.exprs.get(index) {
+                    if let Some(expr) = template.exprs.get_mut(index) {
                         parts.extend(self.parse_expr_contents(expr, module, options)?);
                     }
                 }
@@ -658,11 +1073,16 @@ impl FbteeTransform {
                         module.as_str()
                     ));
                 }
+                if module == ModuleName::Fbs {
+                    return Err(fbs_jsx_child_message(&jsx_element_display_name(
+                        &element.opening.name,
+                    )));
+                }
                 let token = format!(
                     "={}",
                     normalize_spaces(&jsx_text_content(&element.children), false)
                 );
-                let mut value = Expr::JSXElement(element.clone());
+                let mut value = Expr::JSXElement(element.take());
                 value.visit_mut_children_with(self);
                 Ok(vec![Part::Param {
                     name: token,
@@ -673,11 +1093,14 @@ impl FbteeTransform {
                 }])
             }
             Expr::JSXFragment(fragment) => {
+                if module == ModuleName::Fbs {
+                    return Err(fbs_jsx_child_message(""));
+                }
                 let token = format!(
                     "={}",
                     normalize_spaces(&jsx_fragment_text_content(fragment), false)
                 );
-                let mut value = Expr::JSXFragment(fragment.clone());
+                let mut value = Expr::JSXFragment(fragment.take());
                 value.visit_mut_children_with(self);
                 Ok(vec![Part::Param {
                     name: token,
@@ -687,7 +1110,7 @@ impl FbteeTransform {
                     runtime_kind: ParamRuntimeKind::Implicit,
                 }])
             }
-            Expr::Paren(paren) => self.parse_expr_contents(&paren.expr, module, options),
+            Expr::Paren(paren) => self.parse_expr_contents(&mut paren.expr, module, options),
             _ => Err(format!(
                 "{} text contains unsupported syntax '{}'. Use text, JSX, or {} constructs.",
                 module.as_str(),
@@ -699,7 +1122,7 @@ impl FbteeTransform {
 
     fn parse_construct_call(
         &mut self,
-        call: &CallExpr,
+        call: &mut CallExpr,
         module: ModuleName,
         _options: &CallOptions,
     ) -> Result<Vec<Part>, String> {
@@ -720,21 +1143,22 @@ impl FbteeTransform {
 
         match method {
             "param" => {
-                let name = arg_as_string(call.args.first().ok_or_else(|| {
-                    format!(
-                        "{}.param(...) needs a token name as the first argument.",
-                        module.as_str()
-                    )
-                })?)
-                .ok_or_else(|| {
-                    format!(
-                        "{}.param(...) token name must be a string literal.",
-                        module.as_str()
-                    )
-                })?;
+                let name = self
+                    .static_arg(call.args.first().ok_or_else(|| {
+                        format!(
+                            "{}.param(...) needs a token name as the first argument.",
+                            module.as_str()
+                        )
+                    })?)
+                    .ok_or_else(|| {
+                        format!(
+                            "{}.param(...) token name must be a string literal.",
+                            module.as_str()
+                        )
+                    })?;
                 let mut value = call
                     .args
-                    .get(1)
+                    .get_mut(1)
                     .ok_or_else(|| {
                         format!(
                             "{}.param(...) needs a value as the second argument.",
@@ -742,12 +1166,16 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
+                check_fbs_value(module, "fbs.param(...)", &value)?;
                 value.visit_mut_with(self);
-                let options = call
+                let mut options = call
                     .args
-                    .get(2)
-                    .map(|arg| parse_object(&arg.expr))
+                    .get_mut(2)
+                    .map(|arg| {
+                        self.fold_static_options(&mut arg.expr);
+                        parse_object(&mut arg.expr)
+                    })
                     .unwrap_or_default();
                 let variation = if let Some(number) = options.number_expr() {
                     ParamVariation::Number(number)
@@ -765,20 +1193,21 @@ impl FbteeTransform {
                 }])
             }
             "sameParam" => {
-                let name = arg_as_string(call.args.first().ok_or_else(|| {
-                    format!("{}.sameParam(...) needs a token name.", module.as_str())
-                })?)
-                .ok_or_else(|| {
-                    format!(
-                        "{}.sameParam(...) token name must be a string literal.",
-                        module.as_str()
-                    )
-                })?;
+                let name = self
+                    .static_arg(call.args.first().ok_or_else(|| {
+                        format!("{}.sameParam(...) needs a token name.", module.as_str())
+                    })?)
+                    .ok_or_else(|| {
+                        format!(
+                            "{}.sameParam(...) token name must be a string literal.",
+                            module.as_str()
+                        )
+                    })?;
                 Ok(vec![Part::SameParam { name }])
             }
             "name" => {
-                let name =
-                    arg_as_string(call.args.first().ok_or_else(|| {
+                let name = self
+                    .static_arg(call.args.first().ok_or_else(|| {
                         format!("{}.name(...) needs a token name.", module.as_str())
                     })?)
                     .ok_or_else(|| {
@@ -789,7 +1218,7 @@ impl FbteeTransform {
                     })?;
                 let mut value = call
                     .args
-                    .get(1)
+                    .get_mut(1)
                     .ok_or_else(|| {
                         format!(
                             "{}.name(...) needs a value as the second argument.",
@@ -797,11 +1226,12 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
+                check_fbs_value(module, "fbs.name(...)", &value)?;
                 value.visit_mut_with(self);
                 let gender = call
                     .args
-                    .get(2)
+                    .get_mut(2)
                     .ok_or_else(|| {
                         format!(
                             "{}.name(...) needs a gender as the third argument.",
@@ -809,7 +1239,7 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
                 Ok(vec![Part::Name {
                     name,
                     value,
@@ -819,7 +1249,7 @@ impl FbteeTransform {
             "enum" => {
                 let value = call
                     .args
-                    .first()
+                    .first_mut()
                     .ok_or_else(|| {
                         format!(
                             "{}.enum(...) needs a value as the first argument.",
@@ -827,10 +1257,10 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
                 let range_expr = call
                     .args
-                    .get(1)
+                    .get_mut(1)
                     .ok_or_else(|| {
                         format!(
                             "{}.enum(...) needs a range as the second argument.",
@@ -838,7 +1268,7 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
                 let range = self.enum_range_from_expr(&range_expr)?;
                 Ok(vec![Part::Enum {
                     value,
@@ -847,18 +1277,19 @@ impl FbteeTransform {
                 }])
             }
             "plural" => {
-                let singular = arg_as_string(call.args.first().ok_or_else(|| {
-                    format!("{}.plural(...) needs singular text.", module.as_str())
-                })?)
-                .ok_or_else(|| {
-                    format!(
-                        "{}.plural(...) singular text must be a string literal.",
-                        module.as_str()
-                    )
-                })?;
+                let singular = self
+                    .static_arg(call.args.first().ok_or_else(|| {
+                        format!("{}.plural(...) needs singular text.", module.as_str())
+                    })?)
+                    .ok_or_else(|| {
+                        format!(
+                            "{}.plural(...) singular text must be a string literal.",
+                            module.as_str()
+                        )
+                    })?;
                 let count = call
                     .args
-                    .get(1)
+                    .get_mut(1)
                     .ok_or_else(|| {
                         format!(
                             "{}.plural(...) needs a count as the second argument.",
@@ -866,11 +1297,14 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
-                let options = call
+                    .take();
+                let mut options = call
                     .args
-                    .get(2)
-                    .map(|arg| parse_object(&arg.expr))
+                    .get_mut(2)
+                    .map(|arg| {
+                        self.fold_static_options(&mut arg.expr);
+                        parse_object(&mut arg.expr)
+                    })
                     .unwrap_or_default();
                 let many = options
                     .string("many")
@@ -898,21 +1332,22 @@ impl FbteeTransform {
                 }])
             }
             "pronoun" => {
-                let usage = arg_as_string(call.args.first().ok_or_else(|| {
-                    format!(
-                        "{}.pronoun(...) needs a usage as the first argument.",
-                        module.as_str()
-                    )
-                })?)
-                .ok_or_else(|| {
-                    format!(
-                        "{}.pronoun(...) usage must be a string literal.",
-                        module.as_str()
-                    )
-                })?;
+                let usage = self
+                    .static_arg(call.args.first().ok_or_else(|| {
+                        format!(
+                            "{}.pronoun(...) needs a usage as the first argument.",
+                            module.as_str()
+                        )
+                    })?)
+                    .ok_or_else(|| {
+                        format!(
+                            "{}.pronoun(...) usage must be a string literal.",
+                            module.as_str()
+                        )
+                    })?;
                 let gender = call
                     .args
-                    .get(1)
+                    .get_mut(1)
                     .ok_or_else(|| {
                         format!(
                             "{}.pronoun(...) needs a gender as the second argument.",
@@ -920,11 +1355,14 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
                 let options = call
                     .args
-                    .get(2)
-                    .map(|arg| parse_object(&arg.expr))
+                    .get_mut(2)
+                    .map(|arg| {
+                        self.fold_static_options(&mut arg.expr);
+                        parse_object(&mut arg.expr)
+                    })
                     .unwrap_or_default();
                 Ok(vec![Part::Pronoun {
                     usage,
@@ -934,8 +1372,8 @@ impl FbteeTransform {
                 }])
             }
             "list" => {
-                let name =
-                    arg_as_string(call.args.first().ok_or_else(|| {
+                let name = self
+                    .static_arg(call.args.first().ok_or_else(|| {
                         format!("{}.list(...) needs a token name.", module.as_str())
                     })?)
                     .ok_or_else(|| {
@@ -946,7 +1384,7 @@ impl FbteeTransform {
                     })?;
                 let mut items = call
                     .args
-                    .get(1)
+                    .get_mut(1)
                     .ok_or_else(|| {
                         format!(
                             "{}.list(...) needs items as the second argument.",
@@ -954,10 +1392,11 @@ impl FbteeTransform {
                         )
                     })?
                     .expr
-                    .clone();
+                    .take();
+                check_fbs_value(module, "fbs.list(...) items", &items)?;
                 items.visit_mut_with(self);
-                let conjunction = call.args.get(2).and_then(arg_as_string);
-                let delimiter = call.args.get(3).and_then(arg_as_string);
+                let conjunction = call.args.get(2).and_then(|arg| self.static_arg(arg));
+                let delimiter = call.args.get(3).and_then(|arg| self.static_arg(arg));
                 Ok(vec![Part::List {
                     name,
                     items,
@@ -975,25 +1414,25 @@ impl FbteeTransform {
 
     fn parse_jsx_children(
         &mut self,
-        children: &[JSXElementChild],
+        children: &mut [JSXElementChild],
         module: ModuleName,
         options: &CallOptions,
-        description_children: &[JSXElementChild],
+        descriptions: &mut JsxDescriptions,
     ) -> Result<Vec<Part>, String> {
         let mut parts = vec![];
         for child in children {
             match child {
                 JSXElementChild::JSXText(text) => {
                     let normalized = if options.preserve_whitespace {
-                        clean_jsx_text(&text.value.to_string())
+                        clean_jsx_text(text.value.as_ref())
                     } else {
-                        normalize_spaces(&text.value.to_string(), false)
+                        normalize_spaces(text.value.as_ref(), false)
                     };
                     if !normalized.trim().is_empty() {
                         parts.push(Part::Text(normalized));
                     }
                 }
-                JSXElementChild::JSXExprContainer(container) => match &container.expr {
+                JSXElementChild::JSXExprContainer(container) => match &mut container.expr {
                     JSXExpr::Expr(expr) => {
                         parts.extend(self.parse_expr_contents(expr, module, options)?);
                     }
@@ -1018,26 +1457,25 @@ impl FbteeTransform {
                             ));
                         }
                         None => {
+                            if module == ModuleName::Fbs {
+                                return Err(fbs_jsx_child_message(&jsx_element_display_name(
+                                    &element.opening.name,
+                                )));
+                            }
                             let token = implicit_param_alias(parts.len());
-                            let description_text = jsx_description_text_for_target(
-                                description_children,
-                                element,
-                                options,
-                            );
+                            let hash_name = implicit_child_hash_name(&element.children, options);
+                            let description_text = descriptions.next_element();
                             let value = self.implicit_jsx_element_value(
-                                element,
+                                element.take(),
                                 module,
                                 options,
-                                description_children,
+                                descriptions,
                                 &description_text,
                             )?;
                             parts.push(Part::Param {
                                 name: token,
-                                hash_name: Some(implicit_child_hash_name(
-                                    &JSXElementChild::JSXElement(element.clone()),
-                                    options,
-                                )),
-                                value: Box::new(Expr::JSXElement(Box::new(value))),
+                                hash_name: Some(hash_name),
+                                value: Box::new(Expr::JSXElement(value)),
                                 variation: ParamVariation::None,
                                 runtime_kind: ParamRuntimeKind::Implicit,
                             });
@@ -1045,23 +1483,33 @@ impl FbteeTransform {
                     }
                 }
                 JSXElementChild::JSXFragment(fragment) => {
+                    if module == ModuleName::Fbs {
+                        return Err(fbs_jsx_child_message(""));
+                    }
                     let token = implicit_param_alias(parts.len());
+                    let hash_name = implicit_child_hash_name(&fragment.children, options);
+                    let description_text = descriptions.fragment.clone();
                     let value = self.implicit_jsx_fragment_value(
-                        fragment,
+                        fragment.take(),
                         module,
                         options,
-                        description_children,
-                        &jsx_description_text(description_children, options),
+                        descriptions,
+                        &description_text,
                     )?;
                     parts.push(Part::Param {
                         name: token,
-                        hash_name: Some(implicit_child_hash_name(child, options)),
+                        hash_name: Some(hash_name),
                         value: Box::new(Expr::JSXFragment(value)),
                         variation: ParamVariation::None,
                         runtime_kind: ParamRuntimeKind::Implicit,
                     });
                 }
-                JSXElementChild::JSXSpreadChild(_) => {}
+                JSXElementChild::JSXSpreadChild(_) => {
+                    return Err(format!(
+                        "Spread children are not supported inside <{}>. Its text is read at compile time, so list each child explicitly.",
+                        module.as_str()
+                    ));
+                }
             }
         }
         Ok(compact_text_parts(parts))
@@ -1069,20 +1517,19 @@ impl FbteeTransform {
 
     fn implicit_jsx_element_value(
         &mut self,
-        element: &JSXElement,
+        mut element: Box<JSXElement>,
         module: ModuleName,
         options: &CallOptions,
-        description_children: &[JSXElementChild],
+        descriptions: &mut JsxDescriptions,
         description_text: &str,
-    ) -> Result<JSXElement, String> {
+    ) -> Result<Box<JSXElement>, String> {
         let inner = self.implicit_children_runtime_expr(
-            &element.children,
+            &mut element.children,
             module,
             options,
-            description_children,
+            descriptions,
             description_text,
         )?;
-        let mut element = element.clone();
         if let Some(inner) = inner {
             element.children = vec![JSXElementChild::JSXExprContainer(JSXExprContainer {
                 span: DUMMY_SP,
@@ -1096,20 +1543,19 @@ impl FbteeTransform {
 
     fn implicit_jsx_fragment_value(
         &mut self,
-        fragment: &JSXFragment,
+        mut fragment: JSXFragment,
         module: ModuleName,
         options: &CallOptions,
-        description_children: &[JSXElementChild],
+        descriptions: &mut JsxDescriptions,
         description_text: &str,
     ) -> Result<JSXFragment, String> {
         let inner = self.implicit_children_runtime_expr(
-            &fragment.children,
+            &mut fragment.children,
             module,
             options,
-            description_children,
+            descriptions,
             description_text,
         )?;
-        let mut fragment = fragment.clone();
         if let Some(inner) = inner {
             fragment.children = vec![JSXElementChild::JSXExprContainer(JSXExprContainer {
                 span: DUMMY_SP,
@@ -1123,13 +1569,13 @@ impl FbteeTransform {
 
     fn implicit_children_runtime_expr(
         &mut self,
-        children: &[JSXElementChild],
+        children: &mut [JSXElementChild],
         module: ModuleName,
         options: &CallOptions,
-        description_children: &[JSXElementChild],
+        descriptions: &mut JsxDescriptions,
         description_text: &str,
     ) -> Result<Option<Expr>, String> {
-        let parts = self.parse_jsx_children(children, module, options, description_children)?;
+        let parts = self.parse_jsx_children(children, module, options, descriptions)?;
         if parts.is_empty() {
             return Ok(None);
         }
@@ -1143,24 +1589,20 @@ impl FbteeTransform {
 
     fn parse_jsx_construct(
         &mut self,
-        element: &JSXElement,
+        element: &mut JSXElement,
         module: ModuleName,
         kind: String,
         options: &CallOptions,
     ) -> Result<Vec<Part>, String> {
-        let attrs = JsxAttrs::new(&element.opening.attrs);
+        let mut attrs = JsxAttrs::new(&mut element.opening.attrs);
+        attrs.reject_spreads(&format!("{}:{kind}", module.as_str()))?;
         match kind.as_str() {
             "param" => {
                 let name = attrs.string("name").ok_or_else(|| {
                     format!("<{}:param> needs attribute 'name'.", module.as_str())
                 })?;
-                let value = self.jsx_param_value(&element.children).unwrap_or_else(|| {
-                    Expr::Lit(Lit::Str(Str {
-                        span: DUMMY_SP,
-                        value: Wtf8Atom::new(""),
-                        raw: None,
-                    }))
-                });
+                let value = self.jsx_param_value(&mut element.children);
+                check_fbs_value(module, "<fbs:param>", &value)?;
                 let variation = if let Some(number) = attrs.number_expr("number") {
                     ParamVariation::Number(number)
                 } else if let Some(gender) = attrs.expr("gender") {
@@ -1186,13 +1628,8 @@ impl FbteeTransform {
                 let name = attrs
                     .string("name")
                     .ok_or_else(|| format!("<{}:name> needs attribute 'name'.", module.as_str()))?;
-                let value = self.jsx_param_value(&element.children).unwrap_or_else(|| {
-                    Expr::Lit(Lit::Str(Str {
-                        span: DUMMY_SP,
-                        value: Wtf8Atom::new(""),
-                        raw: None,
-                    }))
-                });
+                let value = self.jsx_param_value(&mut element.children);
+                check_fbs_value(module, "<fbs:name>", &value)?;
                 let gender = attrs.expr("gender").ok_or_else(|| {
                     format!("<{}:name> needs attribute 'gender'.", module.as_str())
                 })?;
@@ -1272,6 +1709,7 @@ impl FbteeTransform {
                 let mut items = attrs.expr("items").ok_or_else(|| {
                     format!("<{}:list> needs attribute 'items'.", module.as_str())
                 })?;
+                check_fbs_value(module, "<fbs:list> items", &items)?;
                 items.visit_mut_with(self);
                 Ok(vec![Part::List {
                     name,
@@ -1288,102 +1726,72 @@ impl FbteeTransform {
         }
     }
 
-    fn jsx_children_to_expr(&mut self, children: &[JSXElementChild]) -> Option<JSXFragment> {
-        let mut transformed = vec![];
-        for child in children {
-            let next = match child {
-                JSXElementChild::JSXExprContainer(container) => {
-                    if let JSXExpr::Expr(expr) = &container.expr {
-                        let mut expr = expr.clone();
-                        if let Some(next) = self.transform_expr(&expr) {
-                            expr = Box::new(next);
-                        } else {
-                            expr.visit_mut_children_with(self);
-                        }
-                        JSXElementChild::JSXExprContainer(JSXExprContainer {
-                            span: container.span,
-                            expr: JSXExpr::Expr(expr),
-                        })
-                    } else {
-                        child.clone()
-                    }
-                }
-                JSXElementChild::JSXElement(element) => {
-                    if let Some(next) = self.transform_jsx_element(element) {
-                        JSXElementChild::JSXExprContainer(JSXExprContainer {
-                            span: DUMMY_SP,
-                            expr: JSXExpr::Expr(Box::new(next)),
-                        })
-                    } else {
-                        let mut element = element.clone();
-                        element.visit_mut_children_with(self);
-                        JSXElementChild::JSXElement(element)
-                    }
-                }
-                JSXElementChild::JSXFragment(fragment) => {
-                    let mut fragment = fragment.clone();
-                    fragment.visit_mut_children_with(self);
-                    JSXElementChild::JSXFragment(fragment)
+    fn jsx_children_to_expr(&mut self, children: &mut Vec<JSXElementChild>) -> JSXFragment {
+        let mut children = std::mem::take(children);
+        for child in &mut children {
+            if let JSXElementChild::JSXElement(element) = child {
+                if let Some(next) = self.transform_jsx_element(element) {
+                    *child = JSXElementChild::JSXExprContainer(JSXExprContainer {
+                        span: DUMMY_SP,
+                        expr: JSXExpr::Expr(Box::new(next)),
+                    });
+                    continue;
                 }
-                _ => child.clone(),
-            };
-            transformed.push(next);
+            }
+            child.visit_mut_children_with(self);
         }
 
-        Some(JSXFragment {
+        JSXFragment {
             span: DUMMY_SP,
             opening: JSXOpeningFragment { span: DUMMY_SP },
-            children: transformed,
+            children,
             closing: JSXClosingFragment { span: DUMMY_SP },
-        })
+        }
     }
 
-    fn jsx_param_value(&mut self, children: &[JSXElementChild]) -> Option<Expr> {
-        let meaningful: Vec<&JSXElementChild> = children
-            .iter()
-            .filter(|child| match child {
-                JSXElementChild::JSXText(text) => {
-                    !normalize_spaces(&text.value, false).trim().is_empty()
-                }
-                JSXElementChild::JSXExprContainer(container) => {
-                    !matches!(container.expr, JSXExpr::JSXEmptyExpr(_))
-                }
-                _ => true,
-            })
-            .collect();
-
-        if meaningful.len() == 1 {
-            match meaningful[0] {
-                JSXElementChild::JSXExprContainer(container) => {
-                    if let JSXExpr::Expr(expr) = &container.expr {
-                        let mut expr = expr.clone();
-                        if let Some(next) = self.transform_expr(&expr) {
-                            expr = Box::new(next);
-                        } else {
-                            expr.visit_mut_children_with(self);
-                        }
-                        return Some(*expr);
+    fn jsx_param_value(&mut self, children: &mut Vec<JSXElementChild>) -> Expr {
+        let mut meaningful = children.iter_mut().filter(|child| match child {
+            JSXElementChild::JSXText(text) => {
+                !normalize_spaces(&text.value, false).trim().is_empty()
+            }
+            JSXElementChild::JSXExprContainer(container) => {
+                !matches!(container.expr, JSXExpr::JSXEmptyExpr(_))
+            }
+            _ => true,
+        });
+        if let (Some(only), None) = (meaningful.next(), meaningful.next()) {
+            match only {
+                JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                }) => {
+                    let mut expr = expr.take();
+                    if let Some(next) = self.transform_expr(&mut expr) {
+                        *expr = next;
+                    } else {
+                        expr.visit_mut_children_with(self);
                     }
+                    return *expr;
                 }
                 JSXElementChild::JSXElement(element) => {
                     if let Some(expr) = self.transform_jsx_element(element) {
-                        return Some(expr);
+                        return expr;
                     }
                 }
                 _ => {}
             }
         }
 
-        self.jsx_children_to_expr(children).map(Expr::JSXFragment)
+        Expr::JSXFragment(self.jsx_children_to_expr(children))
     }
 
-    fn enum_range_from_expr(&self, expr: &Box<Expr>) -> Result<Vec<(String, String)>, String> {
-        match expr.as_ref() {
+    fn enum_range_from_expr(&self, expr: &Expr) -> Result<Vec<(String, String)>, String> {
+        match unwrap_static_expr(expr) {
             Expr::Array(array) => Ok(array
                 .elems
                 .iter()
                 .flatten()
-                .filter_map(|elem| expr_as_string(&elem.expr).map(|value| (value.clone(), value)))
+                .filter_map(|elem| self.static_string(&elem.expr).map(|value| (value.clone(), value)))
                 .collect()),
             Expr::Object(object) => object
                 .props
@@ -1393,7 +1801,7 @@ impl FbteeTransform {
                         Prop::KeyValue(key_value) => {
                             let key = prop_name_to_string(&key_value.key)
                                 .ok_or("Enum object keys must be strings, numbers, or identifiers.")?;
-                            let value = expr_as_string(&key_value.value)
+                            let value = self.static_string(&key_value.value)
                                 .ok_or("Enum object values must be string literals.")?;
                             Ok((key, value))
                         }
@@ -1404,7 +1812,8 @@ impl FbteeTransform {
                 .collect(),
             Expr::Ident(ident) => self
                 .imported_enums
-                .get(&ident.sym.to_string())
+                .get(ident.sym.as_ref())
+                .and_then(|module| self.options.fbt_enum_manifest.get(module))
                 .map(|range| {
                     range
                         .iter()
@@ -1419,31 +1828,96 @@ impl FbteeTransform {
         }
     }
 
+    /// The table key each variation selects in the source table when its value is a constant, by
+    /// depth. This mirrors the runtime's table access for the source locale.
+    fn constant_variation_keys(&self, builder: &RuntimeBuilder) -> Vec<Option<String>> {
+        builder
+            .variation_parts()
+            .into_iter()
+            .map(|variation| {
+                let key = match builder.phrase.parts.get(variation.index)? {
+                    Part::Enum { value, .. } => {
+                        self.static_string(value)
+                            .or_else(|| match unwrap_static_expr(value) {
+                                Expr::Lit(Lit::Num(number)) => Some(number.value.to_string()),
+                                _ => None,
+                            })?
+                    }
+                    Part::Plural { count, .. } => match unwrap_static_expr(count) {
+                        Expr::Lit(Lit::Num(number)) if number.value == 1.0 => "_1".to_string(),
+                        Expr::Lit(Lit::Num(_)) => "*".to_string(),
+                        _ => return None,
+                    },
+                    Part::Pronoun { usage, gender, .. } => {
+                        let key =
+                            pronoun_gender_key(usage, gender_const_value(gender)?).unwrap_or("*");
+                        if variation.keys.iter().any(|candidate| candidate == key) {
+                            key.to_string()
+                        } else {
+                            "*".to_string()
+                        }
+                    }
+                    _ => return None,
+                };
+                variation.keys.contains(&key).then_some(key)
+            })
+            .collect()
+    }
+
     fn runtime_call(&mut self, phrase: Phrase) -> Expr {
+        check_phrase_tokens(&phrase).unwrap_or_else(|error| compile_error(&error));
         match phrase.module {
             ModuleName::Fbt => self.used_fbt = true,
             ModuleName::Fbs => self.used_fbs = true,
         }
         let module_ident = self.module_ident(phrase.module);
-        let mut builder = RuntimeBuilder::new(&phrase, module_ident.clone());
-        let table = builder.table();
-        let hash_tree = builder.hash_tree();
-        let hk = fbt_hash_key(&hash_tree);
+        let builder = RuntimeBuilder::new(&phrase);
+        self.check_leaf_budget(&builder);
+        let LoweredPhrase {
+            mut table,
+            hash_tree,
+        } = builder.lower();
+        if self.options.fold_constant_variations {
+            table = table.prune(&self.constant_variation_keys(&builder));
+        }
+        if let Some(collected) = &mut self.collected {
+            collected.push(collected_phrase(
+                &hash_tree,
+                builder.jsfbt_meta(),
+                phrase.options.project.as_deref(),
+            ));
+        }
+        let Phrase {
+            options: CallOptions {
+                project, subject, ..
+            },
+            parts,
+            ..
+        } = phrase;
+        let runtime_args = runtime_args(&module_ident, subject, parts);
+        let hk = fbt_hash_key(&hash_tree, self.options.hash_scheme);
+        if let Some(pseudo_locale) = &self.options.pseudo_locale {
+            table = table.map_strings(&|pattern| pseudo_localize(pattern, pseudo_locale));
+        }
+
+        let mut table_expr = table.expr();
+        if let (Some(hoisted), RuntimeNode::Object(_)) = (&mut self.hoisted, &table) {
+            table_expr = hoisted.hoist("table", &hk, format!("{table:?}"), table_expr);
+        }
 
         let mut args = vec![
             ExprOrSpread {
                 spread: None,
-                expr: Box::new(table.expr()),
+                expr: Box::new(table_expr),
             },
             ExprOrSpread {
                 spread: None,
-                expr: Box::new(if builder.runtime_args.is_empty() {
+                expr: Box::new(if runtime_args.is_empty() {
                     null_expr()
                 } else {
                     Expr::Array(ArrayLit {
                         span: DUMMY_SP,
-                        elems: builder
-                            .runtime_args
+                        elems: runtime_args
                             .into_iter()
                             .map(|expr| {
                                 Some(ExprOrSpread {
@@ -1459,26 +1933,32 @@ impl FbteeTransform {
 
         let mut option_props = vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
             key: PropName::Ident(IdentName::new("hk".into(), DUMMY_SP)),
-            value: Box::new(string_expr(hk)),
+            value: Box::new(string_expr(hk.clone())),
         })))];
-        if let Some(project) = phrase
-            .options
-            .project
-            .as_ref()
-            .filter(|project| !project.is_empty())
-        {
+        if self.options.hash_scheme != HashScheme::Jenkins {
+            option_props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(IdentName::new("hs".into(), DUMMY_SP)),
+                value: Box::new(string_expr(self.options.hash_scheme.to_string())),
+            }))));
+        }
+        if let Some(project) = project.as_ref().filter(|project| !project.is_empty()) {
             option_props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
                 key: PropName::Ident(IdentName::new("project".into(), DUMMY_SP)),
                 value: Box::new(string_expr(project.clone())),
             }))));
         }
 
+        let options_key = format!("{hk}|{}", project.as_deref().unwrap_or_default());
+        let mut options_expr = Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: option_props,
+        });
+        if let Some(hoisted) = &mut self.hoisted {
+            options_expr = hoisted.hoist("options", &hk, options_key, options_expr);
+        }
         args.push(ExprOrSpread {
             spread: None,
-            expr: Box::new(Expr::Object(ObjectLit {
-                span: DUMMY_SP,
-                props: option_props,
-            })),
+            expr: Box::new(options_expr),
         });
 
         Expr::Call(CallExpr {
@@ -1577,7 +2057,7 @@ enum ParamVariation {
     Gender(Box<Expr>),
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum ParamRuntimeKind {
     Param,
     Implicit,
@@ -1585,9 +2065,6 @@ enum ParamRuntimeKind {
 
 struct RuntimeBuilder<'a> {
     phrase: &'a Phrase,
-    module_ident: Ident,
-    runtime_args: Vec<Expr>,
-    runtime_tokens: BTreeSet<String>,
 }
 
 #[derive(Clone)]
@@ -1597,11 +2074,201 @@ struct Variation {
     group: Option<String>,
 }
 
-fn keys_for_depth(
-    variations: &[Variation],
+/// Token names must be unique within a phrase unless they repeat an identical value.
+/// `sameParam` is the explicit way to refer to a token again, after the token is declared.
+fn check_phrase_tokens(phrase: &Phrase) -> Result<(), String> {
+    let module = phrase.module.as_str();
+    let mut tokens: BTreeMap<&str, &Part> = BTreeMap::new();
+    let mut declared = BTreeSet::new();
+    for (index, part) in phrase.parts.iter().enumerate() {
+        if let Part::SameParam { name } = part {
+            if !declared.contains(name.as_str()) {
+                return Err(unresolved_same_param_message(
+                    module,
+                    name,
+                    &phrase.parts[index..],
+                ));
+            }
+            continue;
+        }
+        declared.extend(declared_token_name(part));
+        let Some(name) = part_token_name(part) else {
+            continue;
+        };
+        match tokens.get(name) {
+            Some(previous) if !same_token_value(previous, part) => {
+                return Err(format!(
+                    "Token '{name}' is already used in this {module} call. Use {module}.sameParam('{name}') to reuse it, or choose a different name."
+                ));
+            }
+            Some(_) => {}
+            None => {
+                tokens.insert(name, part);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn unresolved_same_param_message(module: &str, name: &str, rest: &[Part]) -> String {
+    if rest
+        .iter()
+        .any(|part| declared_token_name(part) == Some(name))
+    {
+        format!(
+            "Token '{name}' is used by {module}.sameParam('{name}') before it is declared. Move the sameParam after the construct that declares '{name}'."
+        )
+    } else {
+        format!(
+            "{module}.sameParam('{name}') does not refer to a token in this {module} call. Declare '{name}' with {module}.param, {module}.name, {module}.list or a {module}.plural name first."
+        )
+    }
+}
+
+/// The token a part declares in the phrase text, if any.
+fn part_token_name(part: &Part) -> Option<&str> {
+    match part {
+        Part::Param {
+            name, hash_name, ..
+        } => Some(hash_name.as_deref().unwrap_or(name)),
+        Part::Name { name, .. } | Part::List { name, .. } => Some(name),
+        Part::Plural {
+            name: Some(name),
+            show_count,
+            ..
+        } if show_count != "no" => Some(name),
+        _ => None,
+    }
+}
+
+/// The token a `sameParam` can refer to. Plurals pass their `name` to the runtime even when the
+/// count is not shown.
+fn declared_token_name(part: &Part) -> Option<&str> {
+    match part {
+        Part::Plural {
+            name: Some(name), ..
+        } => Some(name),
+        _ => part_token_name(part),
+    }
+}
+
+fn same_token_value(left: &Part, right: &Part) -> bool {
+    match (left, right) {
+        (
+            Part::Param {
+                value,
+                variation,
+                runtime_kind,
+                ..
+            },
+            Part::Param {
+                value: other_value,
+                variation: other_variation,
+                runtime_kind: other_runtime_kind,
+                ..
+            },
+        ) => {
+            runtime_kind == other_runtime_kind
+                && same_pure_expr(value, other_value)
+                && match (variation, other_variation) {
+                    (ParamVariation::None, ParamVariation::None) => true,
+                    (ParamVariation::Number(number), ParamVariation::Number(other)) => {
+                        same_pure_option(number, other)
+                    }
+                    (ParamVariation::Gender(gender), ParamVariation::Gender(other)) => {
+                        same_pure_expr(gender, other)
+                    }
+                    _ => false,
+                }
+        }
+        (
+            Part::Name { value, gender, .. },
+            Part::Name {
+                value: other_value,
+                gender: other_gender,
+                ..
+            },
+        ) => same_pure_expr(value, other_value) && same_pure_expr(gender, other_gender),
+        (
+            Part::List {
+                items,
+                conjunction,
+                delimiter,
+                ..
+            },
+            Part::List {
+                items: other_items,
+                conjunction: other_conjunction,
+                delimiter: other_delimiter,
+                ..
+            },
+        ) => {
+            same_pure_expr(items, other_items)
+                && conjunction == other_conjunction
+                && delimiter == other_delimiter
+        }
+        (
+            Part::Plural { count, value, .. },
+            Part::Plural {
+                count: other_count,
+                value: other_value,
+                ..
+            },
+        ) => same_pure_expr(count, other_count) && same_pure_option(value, other_value),
+        _ => false,
+    }
+}
+
+/// Whether two token values are the same side-effect-free expression, so that evaluating it once
+/// is the same as evaluating it twice.
+fn same_pure_expr(left: &Expr, right: &Expr) -> bool {
+    is_side_effect_free(left) && left.eq_ignore_span(right)
+}
+
+fn same_pure_option(left: &Option<Box<Expr>>, right: &Option<Box<Expr>>) -> bool {
+    match (left, right) {
+        (Some(left), Some(right)) => same_pure_expr(left, right),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Identifiers, literals and member chains over them without calls.
+fn is_side_effect_free(expr: &Expr) -> bool {
+    match unwrap_static_expr(expr) {
+        Expr::Ident(_) | Expr::This(_) | Expr::Lit(_) => true,
+        Expr::Member(member) => {
+            is_side_effect_free(&member.obj) && is_pure_member_prop(&member.prop)
+        }
+        Expr::OptChain(chain) => match chain.base.as_ref() {
+            OptChainBase::Member(member) => {
+                is_side_effect_free(&member.obj) && is_pure_member_prop(&member.prop)
+            }
+            OptChainBase::Call(_) => false,
+        },
+        Expr::Tpl(tpl) => tpl.exprs.iter().all(|expr| is_side_effect_free(expr)),
+        Expr::Unary(unary) => unary.op != UnaryOp::Delete && is_side_effect_free(&unary.arg),
+        Expr::Array(array) => array.elems.iter().all(|elem| {
+            elem.as_ref()
+                .is_none_or(|elem| elem.spread.is_none() && is_side_effect_free(&elem.expr))
+        }),
+        _ => false,
+    }
+}
+
+fn is_pure_member_prop(prop: &MemberProp) -> bool {
+    match prop {
+        MemberProp::Ident(_) | MemberProp::PrivateName(_) => true,
+        MemberProp::Computed(computed) => is_side_effect_free(&computed.expr),
+    }
+}
+
+/// Keys to branch on at `depth`. A variation that repeats an earlier group reuses its key.
+fn keys_for_depth<'v>(
+    variations: &'v [Variation],
     depth: usize,
-    selected: &[(usize, String)],
-) -> Vec<String> {
+    selected: &[&'v str],
+) -> Vec<&'v str> {
     let variation = &variations[depth];
     variation
         .group
@@ -1609,12 +2276,29 @@ fn keys_for_depth(
         .and_then(|group| {
             variations[..depth]
                 .iter()
-                .enumerate()
-                .find(|(_, previous)| previous.group.as_ref() == Some(group))
-                .and_then(|(index, _)| selected.get(index).map(|(_, key)| key.clone()))
+                .position(|previous| previous.group.as_ref() == Some(group))
+                .and_then(|index| selected.get(index).copied())
         })
         .map(|key| vec![key])
-        .unwrap_or_else(|| variation.keys.clone())
+        .unwrap_or_else(|| variation.keys.iter().map(String::as_str).collect())
+}
+
+/// The phrase tables built by `RuntimeBuilder::lower`.
+struct LoweredPhrase {
+    table: RuntimeNode,
+    hash_tree: HashNode,
+}
+
+/// Keys chosen on the current path, by depth and by part index.
+struct Selection<'v> {
+    by_depth: Vec<&'v str>,
+    by_part: Vec<Option<&'v str>>,
+}
+
+/// Leaf fields shared by every leaf of a phrase's hash tree.
+struct LeafTemplate {
+    desc: Rc<str>,
+    token_aliases: Option<Rc<BTreeMap<String, String>>>,
 }
 
 fn variation_group(part: &Part) -> Option<String> {
@@ -1646,6 +2330,22 @@ fn variation_group(part: &Part) -> Option<String> {
     }
 }
 
+fn expr_label(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => {
+            let prop = match &member.prop {
+                MemberProp::Ident(ident) => ident.sym.to_string(),
+                _ => return None,
+            };
+            Some(format!("{}.{prop}", expr_label(&member.obj)?))
+        }
+        Expr::This(_) => Some("this".to_string()),
+        Expr::Paren(paren) => expr_label(&paren.expr),
+        _ => None,
+    }
+}
+
 fn expr_group_key(expr: &Expr) -> String {
     match expr {
         Expr::Ident(ident) => format!("id:{}", ident.sym),
@@ -1668,45 +2368,24 @@ fn expr_group_key(expr: &Expr) -> String {
 }
 
 impl<'a> RuntimeBuilder<'a> {
-    fn new(phrase: &'a Phrase, module_ident: Ident) -> Self {
-        Self {
-            phrase,
-            module_ident,
-            runtime_args: vec![],
-            runtime_tokens: BTreeSet::new(),
-        }
+    fn new(phrase: &'a Phrase) -> Self {
+        Self { phrase }
     }
 
-    fn table(&mut self) -> RuntimeNode {
+    /// Walks the variations once, building the runtime table and the hash tree together.
+    fn lower(&self) -> LoweredPhrase {
         let variations = self.variation_parts();
-        if let Some(subject) = &self.phrase.options.subject {
-            self.runtime_args.push(runtime_helper(
-                &self.module_ident,
-                "_subject",
-                vec![*subject.clone()],
-            ));
-        }
-        for part in &self.phrase.parts {
-            self.collect_runtime_arg(part);
-        }
-
-        if variations.is_empty() {
-            return RuntimeNode::String(self.pattern_for(&[], false));
-        }
-
-        self.branch(&variations, 0, &mut vec![])
-    }
 
-    fn hash_tree(&self) -> HashNode {
-        let variations = self.variation_parts();
-        if variations.is_empty() {
-            return HashNode::Leaf(HashLeaf {
-                desc: self.phrase.desc.clone(),
-                text: self.pattern_for(&[], true),
-                token_aliases: self.hash_token_aliases(),
-            });
-        }
-        self.hash_branch(&variations, 0, &mut vec![])
+        let leaf = LeafTemplate {
+            desc: self.phrase.desc.as_str().into(),
+            token_aliases: self.hash_token_aliases().map(Rc::new),
+        };
+        let mut selection = Selection {
+            by_depth: Vec::with_capacity(variations.len()),
+            by_part: vec![None; self.phrase.parts.len()],
+        };
+        let (table, hash_tree) = self.lower_branch(&variations, &mut selection, &leaf);
+        LoweredPhrase { table, hash_tree }
     }
 
     fn variation_parts(&self) -> Vec<Variation> {
@@ -1732,8 +2411,8 @@ impl<'a> RuntimeBuilder<'a> {
                 Part::Enum { range, .. } => range.iter().map(|(key, _)| key.clone()).collect(),
                 Part::Plural { .. } => vec!["*".to_string(), "_1".to_string()],
                 Part::Pronoun { usage, human, .. } => pronoun_candidates(usage, *human)
-                    .into_iter()
-                    .map(|(key, _)| key)
+                    .iter()
+                    .map(|(key, _)| key.to_string())
                     .collect(),
                 _ => vec![],
             };
@@ -1748,77 +2427,150 @@ impl<'a> RuntimeBuilder<'a> {
         variations
     }
 
-    fn branch(
-        &self,
-        variations: &[Variation],
-        depth: usize,
-        selected: &mut Vec<(usize, String)>,
-    ) -> RuntimeNode {
-        if depth == variations.len() {
-            return RuntimeNode::String(self.pattern_for(selected, false));
-        }
+    /// The `jsfbt.m` entry of each table level, as the collector writes it.
+    fn jsfbt_meta(&self) -> Vec<Option<JsFbtMetaEntry>> {
+        self.variation_parts()
+            .iter()
+            .map(|variation| {
+                let token = |token: &str, variation_type: i32| JsFbtMetaEntry {
+                    token: Some(token.to_string()),
+                    variation_type: Some(variation_type),
+                    ..JsFbtMetaEntry::default()
+                };
+                match self.phrase.parts.get(variation.index) {
+                    None => Some(token("__subject__", VARIATION_TYPE_GENDER)),
+                    Some(Part::Param {
+                        name,
+                        variation: ParamVariation::Number(_),
+                        ..
+                    }) => Some(token(name, VARIATION_TYPE_NUMBER)),
+                    Some(Part::Param { name, .. } | Part::Name { name, .. }) => {
+                        Some(token(name, VARIATION_TYPE_GENDER))
+                    }
+                    Some(Part::Enum { range, .. }) => Some(JsFbtMetaEntry {
+                        range: Some(range.iter().map(|(key, _)| key.clone()).collect()),
+                        ..JsFbtMetaEntry::default()
+                    }),
+                    Some(Part::Plural {
+                        name, show_count, ..
+                    }) => Some(JsFbtMetaEntry {
+                        singular: Some(true),
+                        token: name.clone().filter(|_| show_count != "no"),
+                        variation_type: Some(VARIATION_TYPE_NUMBER),
+                        ..JsFbtMetaEntry::default()
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
 
-        let variation = &variations[depth];
-        let mut items = vec![];
-        let keys = keys_for_depth(variations, depth, selected);
-        for key in keys {
-            selected.push((variation.index, key.clone()));
-            items.push((key.clone(), self.branch(variations, depth + 1, selected)));
-            selected.pop();
-        }
-        RuntimeNode::Object(items)
+    /// The variations that multiply the table, labeled for diagnostics. Variations that repeat an
+    /// earlier group reuse its key and add no leaves.
+    fn variation_dimensions(&self) -> Vec<(String, usize)> {
+        let variations = self.variation_parts();
+        variations
+            .iter()
+            .enumerate()
+            .filter(|(depth, variation)| {
+                variation.keys.len() > 1
+                    && !variations[..*depth].iter().any(|previous| {
+                        variation.group.is_some() && previous.group == variation.group
+                    })
+            })
+            .map(|(_, variation)| {
+                let label = match self.phrase.parts.get(variation.index) {
+                    Some(Part::Enum { value, .. }) => match expr_label(value) {
+                        Some(label) => format!("enum `{label}`"),
+                        None => "enum".to_string(),
+                    },
+                    Some(Part::Plural { name, count, .. }) => {
+                        match name.clone().or_else(|| expr_label(count)) {
+                            Some(label) => format!("plural `{label}`"),
+                            None => "plural".to_string(),
+                        }
+                    }
+                    Some(Part::Pronoun { usage, .. }) => format!("pronoun `{usage}`"),
+                    _ => "variation".to_string(),
+                };
+                (label, variation.keys.len())
+            })
+            .collect()
     }
 
-    fn hash_branch(
+    fn lower_branch<'v>(
         &self,
-        variations: &[Variation],
-        depth: usize,
-        selected: &mut Vec<(usize, String)>,
-    ) -> HashNode {
+        variations: &'v [Variation],
+        selection: &mut Selection<'v>,
+        leaf: &LeafTemplate,
+    ) -> (RuntimeNode, HashNode) {
+        let depth = selection.by_depth.len();
         if depth == variations.len() {
-            return HashNode::Leaf(HashLeaf {
-                desc: self.phrase.desc.clone(),
-                text: self.pattern_for(selected, true),
-                token_aliases: self.hash_token_aliases(),
-            });
+            let (text, hash_text) = self.leaf_patterns(&selection.by_part);
+            return (
+                RuntimeNode::String(text),
+                HashNode::Leaf(HashLeaf {
+                    desc: leaf.desc.clone(),
+                    text: hash_text,
+                    token_aliases: leaf.token_aliases.clone(),
+                }),
+            );
         }
 
         let variation = &variations[depth];
-        let mut items = vec![];
-        let keys = keys_for_depth(variations, depth, selected);
+        let keys = keys_for_depth(variations, depth, &selection.by_depth);
+        let mut table = Vec::with_capacity(keys.len());
+        let mut hash_tree = Vec::with_capacity(keys.len());
         for key in keys {
-            selected.push((variation.index, key.clone()));
-            items.push((
-                key.clone(),
-                self.hash_branch(variations, depth + 1, selected),
-            ));
-            selected.pop();
+            selection.by_depth.push(key);
+            if let Some(slot) = selection.by_part.get_mut(variation.index) {
+                *slot = Some(key);
+            }
+            let (table_node, hash_node) = self.lower_branch(variations, selection, leaf);
+            table.push((key.to_string(), table_node));
+            hash_tree.push((key.to_string(), hash_node));
+            selection.by_depth.pop();
+        }
+        if let Some(slot) = selection.by_part.get_mut(variation.index) {
+            *slot = None;
         }
-        HashNode::Object(items)
+        (RuntimeNode::Object(table), HashNode::Object(hash_tree))
     }
 
-    fn pattern_for(&self, selected: &[(usize, String)], use_hash_tokens: bool) -> String {
-        let selected: BTreeMap<usize, String> = selected.iter().cloned().collect();
+    /// The runtime pattern and the hash pattern of one leaf. They only differ for params whose
+    /// hash token name differs from their runtime name.
+    fn leaf_patterns(&self, selected: &[Option<&str>]) -> (String, String) {
         let mut output = String::new();
+        let mut hash_output: Option<String> = None;
         for (index, part) in self.phrase.parts.iter().enumerate() {
+            let selected = selected[index];
+            let text_start = output.len();
             match part {
                 Part::Text(text) => output.push_str(text),
                 Part::Param {
                     name, hash_name, ..
                 } => {
-                    let name = if use_hash_tokens {
-                        hash_name.as_ref().unwrap_or(name)
-                    } else {
-                        name
-                    };
-                    output.push_str(&format!("{{{name}}}"));
+                    output.push('{');
+                    output.push_str(name);
+                    output.push('}');
+                    if let Some(hash_name) =
+                        hash_name.as_ref().filter(|hash_name| *hash_name != name)
+                    {
+                        let hash_output =
+                            hash_output.get_or_insert_with(|| output[..text_start].to_string());
+                        hash_output.push('{');
+                        hash_output.push_str(hash_name);
+                        hash_output.push('}');
+                        continue;
+                    }
                 }
                 Part::SameParam { name } | Part::Name { name, .. } | Part::List { name, .. } => {
-                    output.push_str(&format!("{{{name}}}"));
+                    output.push('{');
+                    output.push_str(name);
+                    output.push('}');
                 }
                 Part::Enum { range, .. } => {
-                    let key = selected.get(&index);
-                    let text = key
+                    let text = selected
                         .and_then(|key| range.iter().find(|(range_key, _)| range_key == key))
                         .map(|(_, text)| text.as_str())
                         .unwrap_or_default();
@@ -1831,16 +2583,16 @@ impl<'a> RuntimeBuilder<'a> {
                     name,
                     ..
                 } => {
-                    let is_singular = selected.get(&index).map(|key| key == "_1").unwrap_or(false);
-                    if is_singular {
+                    if selected == Some("_1") {
                         if show_count == "yes" {
-                            output.push_str(&format!("1 {singular}"));
-                        } else {
-                            output.push_str(singular);
+                            output.push_str("1 ");
                         }
+                        output.push_str(singular);
                     } else if show_count == "yes" || show_count == "ifMany" {
-                        let token = name.as_deref().unwrap_or("number");
-                        output.push_str(&format!("{{{token}}} {many}"));
+                        output.push('{');
+                        output.push_str(name.as_deref().unwrap_or("number"));
+                        output.push_str("} ");
+                        output.push_str(many);
                     } else {
                         output.push_str(many);
                     }
@@ -1851,36 +2603,33 @@ impl<'a> RuntimeBuilder<'a> {
                     capitalize,
                     ..
                 } => {
-                    let key = selected.get(&index).map(String::as_str).unwrap_or("*");
+                    let key = selected.unwrap_or("*");
                     let text = pronoun_candidates(usage, *human)
-                        .into_iter()
-                        .find(|(candidate, _)| candidate == key)
-                        .map(|(_, text)| text)
-                        .unwrap_or_else(|| "they".to_string());
+                        .iter()
+                        .find(|(candidate, _)| *candidate == key)
+                        .map_or("they", |(_, text)| *text);
                     if *capitalize {
-                        output.push_str(&capitalize_first(&text));
+                        output.push_str(&capitalize_first(text));
                     } else {
-                        output.push_str(&text);
+                        output.push_str(text);
                     }
                 }
             }
-        }
-        normalize_spaces(&output, self.phrase.options.preserve_whitespace)
-            .trim()
-            .to_string()
-    }
-
-    fn collect_runtime_arg(&mut self, part: &Part) {
-        if let Part::Param { name, .. } = part {
-            if !self.runtime_tokens.insert(name.clone()) {
-                return;
-            }
-        }
-        if !matches!(part, Part::Text(_) | Part::SameParam { .. }) {
-            if let Some(expr) = runtime_arg_expr(&self.module_ident, part) {
-                self.runtime_args.push(expr);
+            if let Some(hash_output) = &mut hash_output {
+                hash_output.push_str(&output[text_start..]);
             }
         }
+        let preserve_whitespace = self.phrase.options.preserve_whitespace;
+        let text = normalize_spaces(&output, preserve_whitespace)
+            .trim()
+            .to_string();
+        let hash_text = match hash_output {
+            Some(hash_output) => normalize_spaces(&hash_output, preserve_whitespace)
+                .trim()
+                .to_string(),
+            None => text.clone(),
+        };
+        (text, hash_text)
     }
 
     fn hash_token_aliases(&self) -> Option<BTreeMap<String, String>> {
@@ -1906,7 +2655,28 @@ impl<'a> RuntimeBuilder<'a> {
     }
 }
 
-fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
+/// The runtime arguments of a phrase, moving its expressions into the call. Repeated params are
+/// passed once.
+fn runtime_args(module_ident: &Ident, subject: Option<Box<Expr>>, parts: Vec<Part>) -> Vec<Expr> {
+    let mut args = vec![];
+    if let Some(subject) = subject {
+        args.push(runtime_helper(module_ident, "_subject", vec![*subject]));
+    }
+    let mut tokens = BTreeSet::new();
+    for part in parts {
+        if let Part::Param { name, .. } = &part {
+            if !tokens.insert(name.clone()) {
+                continue;
+            }
+        }
+        if let Some(expr) = runtime_arg_expr(module_ident, part) {
+            args.push(expr);
+        }
+    }
+    args
+}
+
+fn runtime_arg_expr(module_ident: &Ident, part: Part) -> Option<Expr> {
     match part {
         Part::Param {
             name,
@@ -1919,14 +2689,14 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
                 ParamRuntimeKind::Param => "_param",
                 ParamRuntimeKind::Implicit => "_implicitParam",
             };
-            let mut args = vec![string_expr(name.clone()), *value.clone()];
+            let mut args = vec![string_expr(name), *value];
             match variation {
                 ParamVariation::None => {}
                 ParamVariation::Number(expr) => {
-                    args.push(variation_array(NUMBER, expr.clone()));
+                    args.push(variation_array(NUMBER, expr));
                 }
                 ParamVariation::Gender(expr) => {
-                    args.push(variation_array(GENDER, Some(expr.clone())));
+                    args.push(variation_array(GENDER, Some(expr)));
                 }
             }
             Some(runtime_helper(module_ident, method, args))
@@ -1939,7 +2709,7 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
         } => Some(runtime_helper(
             module_ident,
             "_name",
-            vec![string_expr(name.clone()), *value.clone(), *gender.clone()],
+            vec![string_expr(name), *value, *gender],
         )),
         Part::Enum {
             value,
@@ -1949,11 +2719,11 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
             module_ident,
             "_enum",
             vec![
-                *value.clone(),
-                if matches!(range_expr.as_ref(), Expr::Array(_)) {
-                    enum_range_object_expr(range)
+                *value,
+                if matches!(unwrap_static_expr(&range_expr), Expr::Array(_)) {
+                    enum_range_object_expr(&range)
                 } else {
-                    *range_expr.clone()
+                    *range_expr
                 },
             ],
         )),
@@ -1964,10 +2734,10 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
             show_count,
             ..
         } => {
-            let mut args = vec![*count.clone()];
+            let mut args = vec![*count];
             if show_count == "yes" || show_count == "ifMany" || name.is_some() {
                 args.push(match name {
-                    Some(name) => string_expr(name.clone()),
+                    Some(name) => string_expr(name),
                     None => null_expr(),
                 });
             }
@@ -1975,7 +2745,7 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
                 if args.len() == 1 {
                     args.push(null_expr());
                 }
-                args.push(*value.clone());
+                args.push(*value);
             }
             Some(runtime_helper(module_ident, "_plural", args))
         }
@@ -1985,8 +2755,8 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
             human,
             ..
         } => {
-            let mut args = vec![number_expr(pronoun_usage(usage)), *gender.clone()];
-            if *human {
+            let mut args = vec![number_expr(pronoun_usage(&usage)), *gender];
+            if human {
                 args.push(Expr::Object(ObjectLit {
                     span: DUMMY_SP,
                     props: vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
@@ -2003,45 +2773,148 @@ fn runtime_arg_expr(module_ident: &Ident, part: &Part) -> Option<Expr> {
             conjunction,
             delimiter,
         } => {
-            let mut args = vec![string_expr(name.clone()), *items.clone()];
+            let mut args = vec![string_expr(name), *items];
             if conjunction.is_some() || delimiter.is_some() {
-                args.push(
-                    conjunction
-                        .clone()
-                        .map(string_expr)
-                        .unwrap_or_else(null_expr),
-                );
+                args.push(conjunction.map(string_expr).unwrap_or_else(null_expr));
             }
             if let Some(delimiter) = delimiter {
-                args.push(string_expr(delimiter.clone()));
+                args.push(string_expr(delimiter));
             }
             Some(runtime_helper(module_ident, "_list", args))
         }
     }
 }
 
-#[derive(Clone)]
-enum RuntimeNode {
-    String(String),
-    Object(Vec<(String, RuntimeNode)>),
+/// Module-level constants for hoisted tables and options objects, in emit order.
+struct HoistedConstants {
+    items: Vec<(Ident, Expr)>,
+    by_content: BTreeMap<String, Ident>,
+    /// Every identifier name in the module, which hoisted names must not collide with or be
+    /// shadowed by.
+    taken: BTreeSet<String>,
 }
 
-impl RuntimeNode {
-    fn expr(&self) -> Expr {
-        match self {
-            RuntimeNode::String(value) => string_expr(value.clone()),
-            RuntimeNode::Object(items) => Expr::Object(ObjectLit {
-                span: DUMMY_SP,
-                props: items
-                    .iter()
-                    .map(|(key, value)| {
-                        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                            key: prop_name_for_key(key),
-                            value: Box::new(value.expr()),
-                        })))
-                    })
-                    .collect(),
-            }),
+impl HoistedConstants {
+    fn new(module: &Module) -> Self {
+        let mut names = IdentNames::default();
+        module.visit_with(&mut names);
+        Self {
+            items: vec![],
+            by_content: BTreeMap::new(),
+            taken: names.0,
+        }
+    }
+
+    /// Returns a reference to the constant holding `expr`, reusing the constant of an identical
+    /// `content` of the same kind.
+    fn hoist(&mut self, kind: &str, hk: &str, content: String, expr: Expr) -> Expr {
+        let key = format!("{kind}:{content}");
+        if let Some(ident) = self.by_content.get(&key) {
+            return Expr::Ident(ident.clone());
+        }
+        let base: String = format!("_fbt_{kind}_{hk}")
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+            .collect();
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while self.taken.contains(&name) {
+            suffix += 1;
+            name = format!("{base}_{suffix}");
+        }
+        let ident = Ident::new_no_ctxt(name.as_str().into(), DUMMY_SP);
+        self.taken.insert(name);
+        self.items.push((ident.clone(), expr));
+        self.by_content.insert(key, ident.clone());
+        Expr::Ident(ident)
+    }
+
+    fn into_module_items(self) -> Vec<ModuleItem> {
+        self.items
+            .into_iter()
+            .map(|(ident, expr)| {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                    span: DUMMY_SP,
+                    ctxt: Default::default(),
+                    kind: VarDeclKind::Const,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(BindingIdent {
+                            id: ident,
+                            type_ann: None,
+                        }),
+                        init: Some(Box::new(expr)),
+                        definite: false,
+                    }],
+                }))))
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct IdentNames(BTreeSet<String>);
+
+impl Visit for IdentNames {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.0.insert(ident.sym.to_string());
+    }
+}
+
+#[derive(Clone, Debug)]
+enum RuntimeNode {
+    String(String),
+    Object(Vec<(String, RuntimeNode)>),
+}
+
+impl RuntimeNode {
+    /// Keeps only the branch keyed by `keys[depth]` at each depth that has one.
+    fn prune(self, keys: &[Option<String>]) -> Self {
+        match self {
+            RuntimeNode::String(_) => self,
+            RuntimeNode::Object(items) => {
+                let (key, rest) = keys.split_first().unwrap_or((&None, &[]));
+                RuntimeNode::Object(
+                    items
+                        .into_iter()
+                        .filter(|(item_key, _)| key.as_ref().is_none_or(|key| key == item_key))
+                        .map(|(item_key, node)| (item_key, node.prune(rest)))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn map_strings(self, map: &impl Fn(&str) -> String) -> Self {
+        match self {
+            RuntimeNode::String(value) => RuntimeNode::String(map(&value)),
+            RuntimeNode::Object(items) => RuntimeNode::Object(
+                items
+                    .into_iter()
+                    .map(|(key, node)| (key, node.map_strings(map)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn expr(&self) -> Expr {
+        match self {
+            RuntimeNode::String(value) => string_expr(value.clone()),
+            RuntimeNode::Object(items) => Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: items
+                    .iter()
+                    .map(|(key, value)| {
+                        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                            key: prop_name_for_key(key),
+                            value: Box::new(value.expr()),
+                        })))
+                    })
+                    .collect(),
+            }),
         }
     }
 }
@@ -2054,9 +2927,9 @@ enum HashNode {
 
 #[derive(Clone)]
 struct HashLeaf {
-    desc: String,
+    desc: Rc<str>,
     text: String,
-    token_aliases: Option<BTreeMap<String, String>>,
+    token_aliases: Option<Rc<BTreeMap<String, String>>>,
 }
 
 fn call_module_name(call: &CallExpr) -> Option<ModuleName> {
@@ -2094,6 +2967,38 @@ fn call_member_method(call: &CallExpr) -> Option<&str> {
     }
 }
 
+fn tagged_template_module(tag: &Expr) -> Option<ModuleName> {
+    let callee = match tag {
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => callee.as_ref(),
+        tag => tag,
+    };
+    let Expr::Member(member) = callee else {
+        return None;
+    };
+    if !matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == *"t") {
+        return None;
+    }
+    match member.obj.as_ref() {
+        Expr::Ident(ident) if ident.sym == *"fbt" => Some(ModuleName::Fbt),
+        Expr::Ident(ident) if ident.sym == *"fbs" => Some(ModuleName::Fbs),
+        _ => None,
+    }
+}
+
+/// Tagged template interpolations are limited to constructs of the same module and strings.
+fn is_tagged_template_value(expr: &Expr, module: ModuleName) -> bool {
+    match expr {
+        Expr::Call(call) => {
+            call_module_name(call) == Some(module) && call_member_method(call).is_some()
+        }
+        Expr::Paren(paren) => is_tagged_template_value(&paren.expr, module),
+        expr => expr_as_string(expr).is_some(),
+    }
+}
+
 fn is_construct_method(method: &str) -> bool {
     matches!(
         method,
@@ -2121,6 +3026,61 @@ fn require_source(expr: Option<&Expr>) -> Option<String> {
     }
 }
 
+fn jsx_element_display_name(name: &JSXElementName) -> String {
+    match name {
+        JSXElementName::Ident(ident) => ident.sym.to_string(),
+        JSXElementName::JSXMemberExpr(member) => jsx_member_display_name(member),
+        JSXElementName::JSXNamespacedName(namespaced) => {
+            format!("{}:{}", namespaced.ns.sym, namespaced.name.sym)
+        }
+    }
+}
+
+fn jsx_member_display_name(member: &JSXMemberExpr) -> String {
+    let object = match &member.obj {
+        JSXObject::Ident(ident) => ident.sym.to_string(),
+        JSXObject::JSXMemberExpr(inner) => jsx_member_display_name(inner),
+    };
+    format!("{object}.{}", member.prop.sym)
+}
+
+fn fbs_jsx_child_message(element: &str) -> String {
+    format!(
+        "Do not put <{element}> inside fbs. fbs renders plain strings, so it cannot wrap text in JSX elements. Use fbt for rich text."
+    )
+}
+
+/// `fbs` renders plain strings, so its token values must not be JSX.
+fn check_fbs_value(module: ModuleName, construct: &str, value: &Expr) -> Result<(), String> {
+    if module == ModuleName::Fbs && contains_jsx(value) {
+        return Err(format!(
+            "{construct} values must be strings, not JSX. fbs renders plain strings, so it cannot render React elements. Use fbt instead."
+        ));
+    }
+    Ok(())
+}
+
+fn contains_jsx(expr: &Expr) -> bool {
+    match expr {
+        Expr::JSXElement(_) | Expr::JSXFragment(_) => true,
+        Expr::Paren(paren) => contains_jsx(&paren.expr),
+        Expr::TsAs(value) => contains_jsx(&value.expr),
+        Expr::TsSatisfies(value) => contains_jsx(&value.expr),
+        Expr::TsNonNull(value) => contains_jsx(&value.expr),
+        Expr::TsConstAssertion(value) => contains_jsx(&value.expr),
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .flatten()
+            .any(|elem| contains_jsx(&elem.expr)),
+        Expr::Cond(cond) => contains_jsx(&cond.cons) || contains_jsx(&cond.alt),
+        Expr::Bin(binary) if binary.op.may_short_circuit() => {
+            contains_jsx(&binary.left) || contains_jsx(&binary.right)
+        }
+        _ => false,
+    }
+}
+
 fn jsx_element_kind(name: &JSXElementName) -> Option<(ModuleName, Option<String>)> {
     match name {
         JSXElementName::Ident(ident) => match ident.sym.as_ref() {
@@ -2148,6 +3108,44 @@ fn wtf8_to_string(value: &Wtf8Atom) -> String {
     value.to_atom_lossy().as_str().to_string()
 }
 
+/// Option keys and JSX attributes that are read at compile time.
+const STATIC_OPTION_KEYS: &[&str] = &[
+    "common",
+    "conjunction",
+    "delimiter",
+    "desc",
+    "many",
+    "name",
+    "project",
+    "showCount",
+    "type",
+];
+
+/// Strips parentheses and TypeScript wrappers (`as`, `satisfies`, `!`) that do not change a value.
+fn unwrap_static_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => unwrap_static_expr(&paren.expr),
+        Expr::TsAs(ts_as) => unwrap_static_expr(&ts_as.expr),
+        Expr::TsConstAssertion(assertion) => unwrap_static_expr(&assertion.expr),
+        Expr::TsNonNull(non_null) => unwrap_static_expr(&non_null.expr),
+        Expr::TsSatisfies(satisfies) => unwrap_static_expr(&satisfies.expr),
+        Expr::TsTypeAssertion(assertion) => unwrap_static_expr(&assertion.expr),
+        expr => expr,
+    }
+}
+
+fn unwrap_static_expr_mut(expr: &mut Expr) -> &mut Expr {
+    match expr {
+        Expr::Paren(paren) => unwrap_static_expr_mut(&mut paren.expr),
+        Expr::TsAs(ts_as) => unwrap_static_expr_mut(&mut ts_as.expr),
+        Expr::TsConstAssertion(assertion) => unwrap_static_expr_mut(&mut assertion.expr),
+        Expr::TsNonNull(non_null) => unwrap_static_expr_mut(&mut non_null.expr),
+        Expr::TsSatisfies(satisfies) => unwrap_static_expr_mut(&mut satisfies.expr),
+        Expr::TsTypeAssertion(assertion) => unwrap_static_expr_mut(&mut assertion.expr),
+        expr => expr,
+    }
+}
+
 fn expr_as_string(expr: &Expr) -> Option<String> {
     match expr {
         Expr::Lit(Lit::Str(value)) => Some(wtf8_to_string(&value.value)),
@@ -2213,11 +3211,11 @@ impl ObjectOptions {
         self.bools.get(key).copied()
     }
 
-    fn expr(&self, key: &str) -> Option<Box<Expr>> {
-        self.exprs.get(key).cloned()
+    fn expr(&mut self, key: &str) -> Option<Box<Expr>> {
+        self.exprs.remove(key)
     }
 
-    fn number_expr(&self) -> Option<Option<Box<Expr>>> {
+    fn number_expr(&mut self) -> Option<Option<Box<Expr>>> {
         if let Some(value) = self.bools.get("number") {
             return (*value).then_some(None);
         }
@@ -2229,12 +3227,12 @@ impl ObjectOptions {
                 return None;
             }
         }
-        self.exprs.get("number").cloned().map(Some)
+        self.exprs.remove("number").map(Some)
     }
 }
 
-fn parse_call_options(expr: &Expr) -> CallOptions {
-    let object = parse_object(expr);
+fn parse_call_options(expr: &mut Expr) -> CallOptions {
+    let mut object = parse_object(expr);
     CallOptions {
         preserve_whitespace: object.get_bool("preserveWhitespace").unwrap_or(false),
         project: object.string("project"),
@@ -2242,16 +3240,17 @@ fn parse_call_options(expr: &Expr) -> CallOptions {
     }
 }
 
-fn parse_object(expr: &Expr) -> ObjectOptions {
+/// Reads an options object, moving non-literal values out of `expr`.
+fn parse_object(expr: &mut Expr) -> ObjectOptions {
     let mut options = ObjectOptions::default();
     let Expr::Object(object) = expr else {
         return options;
     };
-    for prop in &object.props {
+    for prop in &mut object.props {
         let PropOrSpread::Prop(prop) = prop else {
             continue;
         };
-        let Prop::KeyValue(key_value) = prop.as_ref() else {
+        let Prop::KeyValue(key_value) = prop.as_mut() else {
             continue;
         };
         let Some(key) = prop_name_to_string(&key_value.key) else {
@@ -2269,7 +3268,7 @@ fn parse_object(expr: &Expr) -> ObjectOptions {
                 options.bools.insert(key, value.value);
             }
             _ => {
-                options.exprs.insert(key, key_value.value.clone());
+                options.exprs.insert(key, key_value.value.take());
             }
         }
     }
@@ -2323,24 +3322,33 @@ fn implicit_param_alias(index: usize) -> String {
     format!("=m{index}")
 }
 
+/// Attributes of an fbtee JSX element. Expression values are moved out of the element, which is
+/// replaced by its runtime call.
 struct JsxAttrs<'a> {
-    attrs: &'a [JSXAttrOrSpread],
+    attrs: &'a mut [JSXAttrOrSpread],
 }
 
 impl<'a> JsxAttrs<'a> {
-    fn new(attrs: &'a [JSXAttrOrSpread]) -> Self {
+    fn new(attrs: &'a mut [JSXAttrOrSpread]) -> Self {
         Self { attrs }
     }
 
+    /// Spread attributes cannot be read at compile time.
+    fn reject_spreads(&self, element: &str) -> Result<(), String> {
+        if self
+            .attrs
+            .iter()
+            .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+        {
+            return Err(format!(
+                "<{element}> does not support spread attributes. Its attributes are read at compile time, so pass each one explicitly."
+            ));
+        }
+        Ok(())
+    }
+
     fn string(&self, key: &str) -> Option<String> {
-        self.value(key).and_then(|value| match value {
-            JSXAttrValue::Str(value) => Some(wtf8_to_string(&value.value)),
-            JSXAttrValue::JSXExprContainer(container) => match &container.expr {
-                JSXExpr::Expr(expr) => expr_as_string(expr),
-                _ => None,
-            },
-            _ => None,
-        })
+        jsx_attr_string(self.attrs, key)
     }
 
     fn boolish(&self, key: &str) -> Option<bool> {
@@ -2359,9 +3367,9 @@ impl<'a> JsxAttrs<'a> {
         })
     }
 
-    fn number_expr(&self, key: &str) -> Option<Option<Box<Expr>>> {
-        let attr = self.attr(key)?;
-        match &attr.value {
+    fn number_expr(&mut self, key: &str) -> Option<Option<Box<Expr>>> {
+        let attr = self.attr_mut(key)?;
+        match &mut attr.value {
             None => Some(None),
             Some(JSXAttrValue::Str(value)) => {
                 let value = wtf8_to_string(&value.value);
@@ -2371,10 +3379,10 @@ impl<'a> JsxAttrs<'a> {
                     None
                 }
             }
-            Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+            Some(JSXAttrValue::JSXExprContainer(container)) => match &mut container.expr {
                 JSXExpr::Expr(expr) => match expr.as_ref() {
                     Expr::Lit(Lit::Bool(value)) => value.value.then_some(None),
-                    _ => Some(Some(expr.clone())),
+                    _ => Some(Some(expr.take())),
                 },
                 _ => None,
             },
@@ -2382,27 +3390,25 @@ impl<'a> JsxAttrs<'a> {
         }
     }
 
-    fn expr(&self, key: &str) -> Option<Box<Expr>> {
-        self.value(key).and_then(|value| match value {
-            JSXAttrValue::JSXExprContainer(container) => match &container.expr {
-                JSXExpr::Expr(expr) => Some(expr.clone()),
+    fn expr(&mut self, key: &str) -> Option<Box<Expr>> {
+        self.attr_mut(key)
+            .and_then(|attr| attr.value.as_mut())
+            .and_then(|value| match value {
+                JSXAttrValue::JSXExprContainer(container) => match &mut container.expr {
+                    JSXExpr::Expr(expr) => Some(expr.take()),
+                    _ => None,
+                },
+                JSXAttrValue::Str(value) => Some(Box::new(Expr::Lit(Lit::Str(value.take())))),
                 _ => None,
-            },
-            JSXAttrValue::Str(value) => Some(Box::new(Expr::Lit(Lit::Str(Str {
-                span: value.span,
-                value: value.value.clone(),
-                raw: value.raw.clone(),
-            })))),
-            _ => None,
-        })
+            })
     }
 
-    fn value(&self, key: &str) -> Option<&'a JSXAttrValue> {
-        self.attr(key).and_then(|attr| attr.value.as_ref())
+    fn attr(&self, key: &str) -> Option<&JSXAttr> {
+        jsx_attr(self.attrs, key)
     }
 
-    fn attr(&self, key: &str) -> Option<&'a JSXAttr> {
-        self.attrs.iter().find_map(|attr| match attr {
+    fn attr_mut(&mut self, key: &str) -> Option<&mut JSXAttr> {
+        self.attrs.iter_mut().find_map(|attr| match attr {
             JSXAttrOrSpread::JSXAttr(attr) if jsx_attr_name(&attr.name).as_deref() == Some(key) => {
                 Some(attr)
             }
@@ -2411,6 +3417,28 @@ impl<'a> JsxAttrs<'a> {
     }
 }
 
+fn jsx_attr<'a>(attrs: &'a [JSXAttrOrSpread], key: &str) -> Option<&'a JSXAttr> {
+    attrs.iter().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) if jsx_attr_name(&attr.name).as_deref() == Some(key) => {
+            Some(attr)
+        }
+        _ => None,
+    })
+}
+
+fn jsx_attr_string(attrs: &[JSXAttrOrSpread], key: &str) -> Option<String> {
+    jsx_attr(attrs, key)
+        .and_then(|attr| attr.value.as_ref())
+        .and_then(|value| match value {
+            JSXAttrValue::Str(value) => Some(wtf8_to_string(&value.value)),
+            JSXAttrValue::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => expr_as_string(expr),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
 fn jsx_attr_name(name: &JSXAttrName) -> Option<String> {
     match name {
         JSXAttrName::Ident(ident) => Some(ident.sym.to_string()),
@@ -2465,7 +3493,7 @@ fn jsx_description_text_with_target(
     for child in children {
         match child {
             JSXElementChild::JSXText(text) => output.push_str(&normalize_spaces(
-                &text.value.to_string(),
+                text.value.as_ref(),
                 options.preserve_whitespace,
             )),
             JSXElementChild::JSXExprContainer(container) => {
@@ -2477,7 +3505,7 @@ fn jsx_description_text_with_target(
             }
             JSXElementChild::JSXElement(element) => {
                 if target.is_some_and(|target| std::ptr::eq(element.as_ref(), target)) {
-                    let token = implicit_child_hash_name(child, options);
+                    let token = implicit_child_hash_name(&element.children, options);
                     if token != "=" {
                         output.push_str(&format!("{{{token}}}"));
                     }
@@ -2525,6 +3553,74 @@ fn jsx_description_text_with_target(
         .to_string()
 }
 
+/// Description texts for the implicit children of one `<fbt>`, computed before its children are
+/// moved out of the tree. Element texts are listed in the order `parse_jsx_children` visits them.
+struct JsxDescriptions {
+    fragment: String,
+    elements: std::vec::IntoIter<String>,
+}
+
+impl JsxDescriptions {
+    fn new(children: &[JSXElementChild], options: &CallOptions) -> Self {
+        let mut elements = vec![];
+        let has_fragments =
+            collect_implicit_descriptions(children, children, options, &mut elements);
+        Self {
+            fragment: if has_fragments {
+                jsx_description_text(children, options)
+            } else {
+                String::new()
+            },
+            elements: elements.into_iter(),
+        }
+    }
+
+    fn next_element(&mut self) -> String {
+        self.elements.next().unwrap_or_default()
+    }
+}
+
+/// Pushes the description of each implicit element under `children`, depth first. Returns
+/// whether any implicit fragment was found.
+fn collect_implicit_descriptions(
+    children: &[JSXElementChild],
+    description_children: &[JSXElementChild],
+    options: &CallOptions,
+    descriptions: &mut Vec<String>,
+) -> bool {
+    let mut has_fragments = false;
+    for child in children {
+        match child {
+            JSXElementChild::JSXElement(element)
+                if jsx_element_kind(&element.opening.name).is_none() =>
+            {
+                descriptions.push(jsx_description_text_for_target(
+                    description_children,
+                    element,
+                    options,
+                ));
+                has_fragments |= collect_implicit_descriptions(
+                    &element.children,
+                    description_children,
+                    options,
+                    descriptions,
+                );
+            }
+            JSXElementChild::JSXFragment(fragment) => {
+                collect_implicit_descriptions(
+                    &fragment.children,
+                    description_children,
+                    options,
+                    descriptions,
+                );
+                has_fragments = true;
+            }
+            _ => {}
+        }
+    }
+    has_fragments
+}
+
 fn jsx_children_contain_element(children: &[JSXElementChild], target: &JSXElement) -> bool {
     children.iter().any(|child| match child {
         JSXElementChild::JSXElement(element) => {
@@ -2538,17 +3634,11 @@ fn jsx_children_contain_element(children: &[JSXElementChild], target: &JSXElemen
     })
 }
 
-fn implicit_child_hash_name(child: &JSXElementChild, options: &CallOptions) -> String {
-    let text = match child {
-        JSXElementChild::JSXElement(element) => jsx_text_content(&element.children),
-        JSXElementChild::JSXFragment(fragment) => jsx_fragment_text_content(fragment),
-        _ => String::new(),
-    };
+fn implicit_child_hash_name(children: &[JSXElementChild], options: &CallOptions) -> String {
+    let text = jsx_text_content(children);
     format!(
         "={}",
-        normalize_spaces(&text, options.preserve_whitespace)
-            .trim()
-            .to_string()
+        normalize_spaces(&text, options.preserve_whitespace).trim()
     )
 }
 
@@ -2582,10 +3672,9 @@ fn expr_description_text(expr: &Expr, options: &CallOptions) -> Option<String> {
 }
 
 fn jsx_construct_token_text(element: &JSXElement, kind: &str, options: &CallOptions) -> String {
-    let attrs = JsxAttrs::new(&element.opening.attrs);
     match kind {
         "param" | "same-param" | "sameParam" | "name" | "list" => {
-            attrs.string("name").unwrap_or_else(|| {
+            jsx_attr_string(&element.opening.attrs, "name").unwrap_or_else(|| {
                 normalize_spaces(
                     &jsx_text_content(&element.children),
                     options.preserve_whitespace,
@@ -2594,7 +3683,7 @@ fn jsx_construct_token_text(element: &JSXElement, kind: &str, options: &CallOpti
                 .to_string()
             })
         }
-        "plural" => attrs.string("name").unwrap_or_else(|| {
+        "plural" => jsx_attr_string(&element.opening.attrs, "name").unwrap_or_else(|| {
             normalize_spaces(
                 &jsx_text_content(&element.children),
                 options.preserve_whitespace,
@@ -2783,41 +3872,60 @@ fn pronoun_usage(usage: &str) -> i32 {
     }
 }
 
-fn pronoun_candidates(usage: &str, human: bool) -> Vec<(String, String)> {
+fn pronoun_candidates(usage: &str, human: bool) -> &'static [(&'static str, &'static str)] {
     match usage {
-        "object" if !human => vec![
-            ("0".to_string(), "this".to_string()),
-            ("1".to_string(), "her".to_string()),
-            ("2".to_string(), "him".to_string()),
-            ("*".to_string(), "them".to_string()),
+        "object" if !human => &[("0", "this"), ("1", "her"), ("2", "him"), ("*", "them")],
+        "object" => &[("1", "her"), ("2", "him"), ("*", "them")],
+        "possessive" => &[("1", "her"), ("2", "his"), ("*", "their")],
+        "reflexive" if !human => &[
+            ("0", "themself"),
+            ("1", "herself"),
+            ("2", "himself"),
+            ("*", "themselves"),
         ],
-        "object" => vec![
-            ("1".to_string(), "her".to_string()),
-            ("2".to_string(), "him".to_string()),
-            ("*".to_string(), "them".to_string()),
-        ],
-        "possessive" => vec![
-            ("1".to_string(), "her".to_string()),
-            ("2".to_string(), "his".to_string()),
-            ("*".to_string(), "their".to_string()),
-        ],
-        "reflexive" if !human => vec![
-            ("0".to_string(), "themself".to_string()),
-            ("1".to_string(), "herself".to_string()),
-            ("2".to_string(), "himself".to_string()),
-            ("*".to_string(), "themselves".to_string()),
-        ],
-        "reflexive" => vec![
-            ("1".to_string(), "herself".to_string()),
-            ("2".to_string(), "himself".to_string()),
-            ("*".to_string(), "themselves".to_string()),
-        ],
-        "subject" => vec![
-            ("1".to_string(), "she".to_string()),
-            ("2".to_string(), "he".to_string()),
-            ("*".to_string(), "they".to_string()),
-        ],
-        _ => vec![("*".to_string(), String::new())],
+        "reflexive" => &[("1", "herself"), ("2", "himself"), ("*", "themselves")],
+        "subject" => &[("1", "she"), ("2", "he"), ("*", "they")],
+        _ => &[("*", "")],
+    }
+}
+
+/// `GenderConst` values, from a number literal or a `GenderConst` member.
+fn gender_const_value(expr: &Expr) -> Option<i32> {
+    match unwrap_static_expr(expr) {
+        Expr::Lit(Lit::Num(number)) if number.value.fract() == 0.0 => Some(number.value as i32),
+        Expr::Member(member) => {
+            let (Expr::Ident(object), MemberProp::Ident(prop)) =
+                (member.obj.as_ref(), &member.prop)
+            else {
+                return None;
+            };
+            if object.sym != "GenderConst" {
+                return None;
+            }
+            match prop.sym.as_ref() {
+                "NOT_A_PERSON" => Some(0),
+                "FEMALE_SINGULAR" => Some(1),
+                "MALE_SINGULAR" => Some(2),
+                "UNKNOWN_SINGULAR" => Some(7),
+                "UNKNOWN_PLURAL" => Some(11),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Must match `getPronounGenderKey` in the fbtee runtime.
+fn pronoun_gender_key(usage: &str, gender: i32) -> Option<&'static str> {
+    match gender {
+        0 if usage == "object" || usage == "reflexive" => Some("0"),
+        0 => Some("11"),
+        1 => Some("1"),
+        2 => Some("2"),
+        11 => Some("11"),
+        7 if usage == "reflexive" => Some("0"),
+        7 => Some("11"),
+        _ => None,
     }
 }
 
@@ -2829,30 +3937,86 @@ fn capitalize_first(value: &str) -> String {
     format!("{}{}", first.to_uppercase(), chars.collect::<String>())
 }
 
-fn fbt_hash_key(jsfbt: &HashNode) -> String {
-    uint_to_base_n(fbt_jenkins_hash(jsfbt), 62)
+/// A phrase as `fbtee collect` writes it: its `jsfbt` table and the md5 hash of every leaf.
+fn collected_phrase(
+    hash_tree: &HashNode,
+    m: Vec<Option<JsFbtMetaEntry>>,
+    project: Option<&str>,
+) -> CollectedPhrase {
+    fn table(node: &HashNode) -> serde_json::Value {
+        match node {
+            HashNode::Leaf(leaf) => {
+                let mut object = serde_json::Map::new();
+                object.insert("desc".into(), leaf.desc.to_string().into());
+                object.insert("text".into(), leaf.text.clone().into());
+                if let Some(token_aliases) = &leaf.token_aliases {
+                    object.insert(
+                        "tokenAliases".into(),
+                        serde_json::to_value(token_aliases.as_ref()).unwrap(),
+                    );
+                }
+                object.into()
+            }
+            HashNode::Object(items) => items
+                .iter()
+                .map(|(key, node)| (key.clone(), table(node)))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        }
+    }
+
+    let mut hash_to_leaf = HashToLeaf::new();
+    for_each_hash_leaf(hash_tree, &mut |leaf| {
+        hash_to_leaf.insert(
+            HashScheme::Md5.hash(&format!("{}{}", leaf.text, leaf.desc)),
+            Leaf {
+                desc: leaf.desc.to_string(),
+                text: leaf.text.clone(),
+            },
+        );
+    });
+    CollectedPhrase {
+        hash_to_leaf: Some(hash_to_leaf),
+        jsfbt: Some(JsFbt {
+            m,
+            t: table(hash_tree),
+        }),
+        extra: [(
+            "project".to_string(),
+            project.unwrap_or_default().to_string().into(),
+        )]
+        .into_iter()
+        .collect(),
+    }
 }
 
-fn fbt_jenkins_hash(jsfbt: &HashNode) -> u32 {
-    let leaves = hash_leaves(jsfbt);
-    let Some(first) = leaves.first() else {
-        return 0;
+fn fbt_hash_key(jsfbt: &HashNode, scheme: HashScheme) -> String {
+    scheme.hash(&fbt_hash_input(jsfbt))
+}
+
+fn fbt_hash_input(jsfbt: &HashNode) -> String {
+    let mut first_desc: Option<&str> = None;
+    let mut same_desc = true;
+    for_each_hash_leaf(jsfbt, &mut |leaf| match first_desc {
+        None => first_desc = Some(&leaf.desc),
+        Some(desc) => same_desc &= desc == &*leaf.desc,
+    });
+    let Some(desc) = first_desc else {
+        return String::new();
     };
-    if leaves.iter().all(|leaf| leaf.desc == first.desc) {
-        let key = format!("{}|{}", json_text_tree(jsfbt), first.desc);
-        jenkins_hash(&key)
+    if same_desc {
+        format!("{}|{desc}", json_text_tree(jsfbt))
     } else {
-        jenkins_hash(&json_full_tree(jsfbt))
+        json_full_tree(jsfbt)
     }
 }
 
-fn hash_leaves(node: &HashNode) -> Vec<HashLeaf> {
+fn for_each_hash_leaf<'a>(node: &'a HashNode, visit: &mut impl FnMut(&'a HashLeaf)) {
     match node {
-        HashNode::Leaf(leaf) => vec![leaf.clone()],
+        HashNode::Leaf(leaf) => visit(leaf),
         HashNode::Object(items) => items
             .iter()
-            .flat_map(|(_, node)| hash_leaves(node))
-            .collect(),
+            .for_each(|(_, node)| for_each_hash_leaf(node, visit)),
     }
 }
 
@@ -2913,38 +4077,6 @@ fn json_full_tree(node: &HashNode) -> String {
     }
 }
 
-fn jenkins_hash(value: &str) -> u32 {
-    if value.is_empty() {
-        return 0;
-    }
-    let mut hash = 0u32;
-    for byte in value.as_bytes() {
-        hash = hash.wrapping_add(*byte as u32);
-        hash = hash.wrapping_add(hash << 10);
-        hash ^= hash >> 6;
-    }
-    hash = hash.wrapping_add(hash << 3);
-    hash ^= hash >> 11;
-    hash = hash.wrapping_add(hash << 15);
-    hash
-}
-
-fn uint_to_base_n(mut value: u32, base: u32) -> String {
-    const SYMBOLS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
-    if !(2..=62).contains(&base) {
-        return String::new();
-    }
-    let mut output = vec![];
-    loop {
-        output.push(SYMBOLS[(value % base) as usize] as char);
-        value /= base;
-        if value == 0 {
-            break;
-        }
-    }
-    output.into_iter().rev().collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2958,11 +4090,20 @@ mod tests {
     };
 
     fn transform(source: &str, options: PluginOptions) -> String {
+        transform_with_warnings(source, options).0
+    }
+
+    fn transform_with_warnings(source: &str, options: PluginOptions) -> (String, Vec<String>) {
+        transform_file_with_warnings("test.tsx", source, options)
+    }
+
+    fn transform_file_with_warnings(
+        filename: &str,
+        source: &str,
+        options: PluginOptions,
+    ) -> (String, Vec<String>) {
         let cm: Lrc<SourceMap> = Default::default();
-        let fm = cm.new_source_file(
-            FileName::Custom("test.tsx".into()).into(),
-            source.to_string(),
-        );
+        let fm = cm.new_source_file(FileName::Custom(filename.into()).into(), source.to_string());
         let lexer = Lexer::new(
             Syntax::Typescript(TsSyntax {
                 tsx: true,
@@ -2974,7 +4115,8 @@ mod tests {
         );
         let mut parser = Parser::new_from(lexer);
         let mut module = parser.parse_module().expect("failed to parse module");
-        module.visit_mut_with(&mut FbteeTransform::new(options));
+        let mut transform = FbteeTransform::new(options, Some(filename.to_string()));
+        module.visit_mut_with(&mut transform);
 
         let mut output = Vec::new();
         {
@@ -2986,7 +4128,10 @@ mod tests {
             };
             emitter.emit_module(&module).expect("failed to emit module");
         }
-        String::from_utf8(output).expect("expected utf8")
+        (
+            String::from_utf8(output).expect("expected utf8"),
+            transform.warnings,
+        )
     }
 
     fn default_options() -> PluginOptions {
@@ -2996,21 +4141,26 @@ mod tests {
     #[test]
     fn hashes_simple_strings_like_babel() {
         let tree = HashNode::Leaf(HashLeaf {
-            desc: "It's simple".to_string(),
+            desc: "It's simple".into(),
             text: "A simple string".to_string(),
             token_aliases: None,
         });
-        assert_eq!(fbt_hash_key(&tree), "pITkM");
+        assert_eq!(fbt_hash_key(&tree, HashScheme::Jenkins), "pITkM");
+        assert_eq!(
+            fbt_hash_key(&tree, HashScheme::Md5),
+            "gasWxhWXjWzrjGWE07rKrw=="
+        );
+        assert_eq!(fbt_hash_key(&tree, HashScheme::Fnv1a64), "ltUKBCtAJOY");
     }
 
     #[test]
     fn hashes_list_like_babel() {
         let tree = HashNode::Leaf(HashLeaf {
-            desc: "Lists".to_string(),
+            desc: "Lists".into(),
             text: "Available Locations: {locations}".to_string(),
             token_aliases: None,
         });
-        assert_eq!(fbt_hash_key(&tree), "19372u");
+        assert_eq!(fbt_hash_key(&tree, HashScheme::Jenkins), "19372u");
     }
 
     #[test]
@@ -3039,8 +4189,8 @@ mod tests {
                 },
             ],
         };
-        let mut builder = RuntimeBuilder::new(&phrase, Ident::new_no_ctxt("fbt".into(), DUMMY_SP));
-        let table = builder.table();
+        let builder = RuntimeBuilder::new(&phrase);
+        let table = builder.lower().table;
         match table {
             RuntimeNode::Object(items) => {
                 assert_eq!(items[0].0, "*");
@@ -3056,7 +4206,7 @@ mod tests {
             (
                 "id1".to_string(),
                 HashNode::Leaf(HashLeaf {
-                    desc: "enums!".to_string(),
+                    desc: "enums!".into(),
                     text: "Click to see groups".to_string(),
                     token_aliases: None,
                 }),
@@ -3064,7 +4214,7 @@ mod tests {
             (
                 "id2".to_string(),
                 HashNode::Leaf(HashLeaf {
-                    desc: "enums!".to_string(),
+                    desc: "enums!".into(),
                     text: "Click to see photos".to_string(),
                     token_aliases: None,
                 }),
@@ -3072,13 +4222,13 @@ mod tests {
             (
                 "id3".to_string(),
                 HashNode::Leaf(HashLeaf {
-                    desc: "enums!".to_string(),
+                    desc: "enums!".into(),
                     text: "Click to see videos".to_string(),
                     token_aliases: None,
                 }),
             ),
         ]);
-        assert_eq!(fbt_hash_key(&tree), "3SHnwE");
+        assert_eq!(fbt_hash_key(&tree, HashScheme::Jenkins), "3SHnwE");
     }
 
     #[test]
@@ -3086,12 +4236,12 @@ mod tests {
         let tree = HashNode::Object(vec![(
             "*".to_string(),
             HashNode::Leaf(HashLeaf {
-                desc: "Bar".to_string(),
+                desc: "Bar".into(),
                 text: "Foo".to_string(),
                 token_aliases: None,
             }),
         )]);
-        assert_eq!(fbt_hash_key(&tree), "7I4k2");
+        assert_eq!(fbt_hash_key(&tree, HashScheme::Jenkins), "7I4k2");
     }
 
     #[test]
@@ -3120,9 +4270,9 @@ mod tests {
                 },
             ],
         };
-        let mut builder = RuntimeBuilder::new(&phrase, Ident::new_no_ctxt("fbt".into(), DUMMY_SP));
-        let table = builder.table();
-        let hash = fbt_hash_key(&builder.hash_tree());
+        let builder = RuntimeBuilder::new(&phrase);
+        let LoweredPhrase { table, hash_tree } = builder.lower();
+        let hash = fbt_hash_key(&hash_tree, HashScheme::Jenkins);
         assert_eq!(hash, "41Uj4v");
         match table {
             RuntimeNode::Object(items) => {
@@ -3284,6 +4434,138 @@ mod tests {
         assert!(!output.contains("[\n        2"), "{output}");
     }
 
+    #[test]
+    fn pseudo_localizes_table_strings_without_changing_hash_keys() {
+        let source =
+            "import { fbt } from 'fbtee'; const x = <fbt desc=\"d\">Hello <b>world</b></fbt>;";
+        let output = transform(
+            source,
+            PluginOptions {
+                pseudo_locale: Some(PseudoLocaleOptions::default()),
+                ..default_options()
+            },
+        );
+        assert!(output.contains("\"[Ĥéļļö {=m1}~~]\""), "{output}");
+        assert!(output.contains("\"[ŵöŕļð~~]\""), "{output}");
+        let original = transform(source, default_options());
+        let hash_keys = |output: &str| {
+            output
+                .match_indices("hk: ")
+                .map(|(index, _)| output[index..index + 12].to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hash_keys(&output), hash_keys(&original));
+        assert!(output.contains("hk: \"h8w0J\""), "{output}");
+    }
+
+    const BUDGET_SOURCE: &str = "import { fbt } from 'fbtee'; const x = fbt(fbt.enum(kind, ['a', 'b', 'c']) + ' ' + fbt.enum(props.size, ['s', 'm']) + ' ' + fbt.plural('item', count) + ' ' + fbt.enum(kind, ['a', 'b', 'c']), 'budget');";
+
+    #[test]
+    fn warns_when_phrases_exceed_the_leaf_warning_threshold() {
+        let (_, warnings) = transform_with_warnings(
+            BUDGET_SOURCE,
+            PluginOptions {
+                warn_phrase_leaves: Some(10),
+                ..default_options()
+            },
+        );
+        assert_eq!(
+            warnings,
+            vec!["Phrase 'budget' expands to 12 table leaves, over the limit of 10. Variation dimensions: enum `kind` (3) x enum `props.size` (2) x plural `count` (2).".to_string()]
+        );
+
+        let (_, warnings) = transform_with_warnings(
+            BUDGET_SOURCE,
+            PluginOptions {
+                warn_phrase_leaves: Some(12),
+                max_phrase_leaves: Some(12),
+                ..default_options()
+            },
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Phrase 'budget' expands to 12 table leaves, over the limit of 11.")]
+    fn rejects_phrases_over_the_leaf_budget() {
+        transform(
+            BUDGET_SOURCE,
+            PluginOptions {
+                max_phrase_leaves: Some(11),
+                ..default_options()
+            },
+        );
+    }
+
+    #[test]
+    fn hoists_and_dedupes_static_tables() {
+        let output = transform(
+            "'use client';\nimport { fbt } from 'fbtee';\nfunction A({ kind }) { return fbt(fbt.enum(kind, ['a', 'b']), 'd'); }\nfunction B({ kind }) { return <fbt desc=\"d\"><fbt:enum enum-range={['a', 'b']} value={kind} /></fbt>; }\nconst c = fbt('Plain', 'p');",
+            PluginOptions {
+                hoist_tables: true,
+                ..default_options()
+            },
+        );
+        assert_eq!(output.matches("const _fbt_table_").count(), 1, "{output}");
+        assert_eq!(output.matches("const _fbt_options_").count(), 2, "{output}");
+        assert_eq!(output.matches("fbt._(_fbt_table_").count(), 2, "{output}");
+        assert!(
+            output.contains("fbt._(\"Plain\", null, _fbt_options_"),
+            "{output}"
+        );
+        let hoisted = output.find("const _fbt_table_").unwrap();
+        assert!(output.find("import { fbt }").unwrap() < hoisted, "{output}");
+        assert!(hoisted < output.find("function A").unwrap(), "{output}");
+    }
+
+    #[test]
+    fn hoisted_names_avoid_existing_bindings() {
+        let options = || PluginOptions {
+            hoist_tables: true,
+            ..default_options()
+        };
+        let source = "import { fbt } from 'fbtee';\nfunction A({ kind }) { return fbt(fbt.enum(kind, ['a', 'b']), 'd'); }";
+        let output = transform(source, options());
+        let start = output.find("const _fbt_table_").unwrap() + "const ".len();
+        let name = &output[start..start + output[start..].find(' ').unwrap()];
+
+        let output = transform(
+            &format!("{source}\nfunction B() {{ const {name} = 1; return {name}; }}"),
+            options(),
+        );
+        assert!(output.contains(&format!("const {name}_2 = ")), "{output}");
+        assert!(output.contains(&format!("fbt._({name}_2")), "{output}");
+        assert!(output.contains(&format!("const {name} = 1;")), "{output}");
+    }
+
+    #[test]
+    fn records_non_default_hash_schemes() {
+        let source = "import { fbt } from 'fbtee'; const x = fbt('Hello', 'd');";
+        let input = fbt_hash_input(&HashNode::Leaf(HashLeaf {
+            desc: "d".into(),
+            text: "Hello".to_string(),
+            token_aliases: None,
+        }));
+        assert_eq!(input, "\"Hello\"|d");
+
+        let output = transform(source, default_options());
+        assert!(!output.contains("hs:"), "{output}");
+        for scheme in [HashScheme::Md5, HashScheme::Fnv1a64] {
+            let output = transform(
+                source,
+                PluginOptions {
+                    hash_scheme: scheme,
+                    ..default_options()
+                },
+            );
+            assert!(
+                output.contains(&format!("hk: \"{}\"", scheme.hash(&input))),
+                "{output}"
+            );
+            assert!(output.contains(&format!("hs: \"{scheme}\"")), "{output}");
+        }
+    }
+
     #[test]
     fn string_boolean_options_match_babel_forms() {
         let output = transform(
@@ -3375,11 +4657,14 @@ mod tests {
             "import { fbt } from 'fbtee'; const x = fbt('A', 'desc with    spaces');",
             default_options(),
         );
-        let expected = fbt_hash_key(&HashNode::Leaf(HashLeaf {
-            desc: "desc with spaces".to_string(),
-            text: "A".to_string(),
-            token_aliases: None,
-        }));
+        let expected = fbt_hash_key(
+            &HashNode::Leaf(HashLeaf {
+                desc: "desc with spaces".into(),
+                text: "A".to_string(),
+                token_aliases: None,
+            }),
+            HashScheme::Jenkins,
+        );
         assert!(output.contains(&format!("hk: \"{expected}\"")), "{output}");
     }
 
@@ -3390,11 +4675,14 @@ mod tests {
             .fbt_common
             .insert("Required".to_string(), "A required field label".to_string());
         let output = transform("const x = <fbt common>\n  Required\n</fbt>;", options);
-        let expected = fbt_hash_key(&HashNode::Leaf(HashLeaf {
-            desc: "A required field label".to_string(),
-            text: "Required".to_string(),
-            token_aliases: None,
-        }));
+        let expected = fbt_hash_key(
+            &HashNode::Leaf(HashLeaf {
+                desc: "A required field label".into(),
+                text: "Required".to_string(),
+                token_aliases: None,
+            }),
+            HashScheme::Jenkins,
+        );
         assert!(output.contains(&format!("hk: \"{expected}\"")), "{output}");
     }
 
@@ -3434,6 +4722,200 @@ mod tests {
         );
     }
 
+    #[test]
+    #[should_panic(
+        expected = "Do not put <b> inside fbs. fbs renders plain strings, so it cannot wrap text in JSX elements. Use fbt for rich text."
+    )]
+    fn rejects_implicit_elements_inside_fbs() {
+        transform(
+            "import { fbs } from 'fbtee'; const x = <fbs desc='d'>Hello <b>world</b></fbs>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "fbs.param(...) values must be strings, not JSX. fbs renders plain strings, so it cannot render React elements. Use fbt instead."
+    )]
+    fn rejects_jsx_param_values_inside_fbs() {
+        transform(
+            "import { fbs } from 'fbtee'; const x = fbs('Hi ' + fbs.param('name', (<b>{name}</b>)), 'd');",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "<fbs:param> values must be strings, not JSX.")]
+    fn rejects_jsx_children_of_fbs_params() {
+        transform(
+            "import { fbs } from 'fbtee'; const x = <fbs desc='d'>Hi <fbs:param name='name'><b>{name}</b></fbs:param></fbs>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    fn allows_string_params_inside_fbs() {
+        let output = transform(
+            "import { fbs } from 'fbtee'; const x = <fbs desc='d'>Hi <fbs:param name='name'>{user.name}</fbs:param></fbs>;",
+            default_options(),
+        );
+        assert!(
+            output.contains("fbs._param(\"name\", user.name)"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Token 'name' is already used in this fbt call. Use fbt.sameParam('name') to reuse it, or choose a different name."
+    )]
+    fn rejects_params_that_reuse_a_token_with_another_value() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = fbt(fbt.param('name', a) + ' and ' + fbt.param('name', b), 'd');",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Token 'people' is already used in this fbt call.")]
+    fn rejects_tokens_reused_across_constructs() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = <fbt desc='d'><fbt:param name='people'>{count}</fbt:param> <fbt:list name='people' items={people} /></fbt>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    fn allows_repeating_a_token_with_an_identical_value() {
+        let output = transform(
+            "import { fbt } from 'fbtee'; const x = fbt(fbt.param('name', user.name) + ' is ' + fbt.param('name', user.name), 'd');",
+            default_options(),
+        );
+        assert_eq!(output.matches("fbt._param(\"name\"").count(), 1, "{output}");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Token 'name' is already used in this fbt call. Use fbt.sameParam('name') to reuse it, or choose a different name."
+    )]
+    fn rejects_repeating_a_token_whose_value_has_calls() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = fbt(fbt.param('name', f()) + ' is ' + fbt.param('name', f()), 'd');",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "fbt.sameParam('usr') does not refer to a token in this fbt call. Declare 'usr' with fbt.param, fbt.name, fbt.list or a fbt.plural name first."
+    )]
+    fn rejects_same_params_without_a_declared_token() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = <fbt desc='d'><fbt:param name='user'>{user}</fbt:param> and <fbt:same-param name='usr' /></fbt>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Token 'user' is used by fbt.sameParam('user') before it is declared."
+    )]
+    fn rejects_same_params_before_their_declaration() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = fbt(fbt.sameParam('user') + ' met ' + fbt.param('user', user), 'd');",
+            default_options(),
+        );
+    }
+
+    #[test]
+    fn resolves_same_params_to_plural_names() {
+        let output = transform(
+            "import { fbt } from 'fbtee'; const x = fbt(fbt.plural('item', count, { name: 'count' }) + ', ' + fbt.sameParam('count') + ' total', 'd');",
+            default_options(),
+        );
+        assert!(output.contains("{count} total"), "{output}");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "<fbt> does not support spread attributes. Its attributes are read at compile time, so pass each one explicitly."
+    )]
+    fn rejects_spread_attributes_on_fbt() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = <fbt {...props} desc='d'>Hello</fbt>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "<fbt:param> does not support spread attributes.")]
+    fn rejects_spread_attributes_on_constructs() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = <fbt desc='d'>Hi <fbt:param {...p} name='n'>{n}</fbt:param></fbt>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Spread children are not supported inside <fbt>.")]
+    fn rejects_spread_children_inside_fbt() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = <fbt desc='d'>Hi {...items}</fbt>;",
+            default_options(),
+        );
+    }
+
+    #[test]
+    fn keeps_spread_attributes_on_implicit_elements() {
+        let output = transform(
+            "import { fbt } from 'fbtee'; const x = <fbt desc='d'>Go <a {...linkProps} href='/x'>home</a></fbt>;",
+            default_options(),
+        );
+        assert!(output.contains("<a {...linkProps} href='/x'>"), "{output}");
+    }
+
+    #[test]
+    fn compiles_tagged_templates_like_fbt_calls() {
+        let tagged = transform(
+            "import { fbt } from 'fbtee'; const x = fbt.t('greeting', { project: 'app' })`Hello ${fbt.param('name', user.name)}, you have ${fbt.plural('message', count, { showCount: 'yes' })}`;",
+            default_options(),
+        );
+        let call = transform(
+            "import { fbt } from 'fbtee'; const x = fbt('Hello ' + fbt.param('name', user.name) + ', you have ' + fbt.plural('message', count, { showCount: 'yes' }), 'greeting', { project: 'app' });",
+            default_options(),
+        );
+        assert_eq!(tagged, call);
+
+        let common = || {
+            let mut options = default_options();
+            options
+                .fbt_common
+                .insert("Done".to_string(), "Button label".to_string());
+            options
+        };
+        assert_eq!(
+            transform(
+                "import { fbs } from 'fbtee'; const x = fbs.t`Done`;",
+                common()
+            ),
+            transform(
+                "import { fbs } from 'fbtee'; const x = fbs.c('Done');",
+                common()
+            ),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "fbt.t`...` interpolations must be fbt constructs or strings. Wrap the Identifier value in fbt.param('name', value)."
+    )]
+    fn rejects_untagged_template_interpolations() {
+        transform(
+            "import { fbt } from 'fbtee'; const x = fbt.t('d')`Hello ${name}`;",
+            default_options(),
+        );
+    }
+
     #[test]
     fn passes_object_ranges_to_runtime_for_array_enums() {
         let output = transform(
@@ -3456,4 +4938,175 @@ mod tests {
         assert!(output.contains("fbt._(\"Inner\", null"), "{output}");
         assert!(!output.contains("fbt(\"Inner\""), "{output}");
     }
+
+    #[test]
+    fn hashes_nested_implicit_children_after_moving_them() {
+        let output = transform(
+            r#"import { fbt } from 'fbtee';
+const a = <fbt desc="nested">Go <a href={url}>to <b>your inbox</b> now</a>, <i>then</i> <>rest <em>here</em></> <fbt:param name="who"><span>{who}</span></fbt:param> {fbt.enum(kind, ['x', 'y'])}</fbt>;"#,
+            default_options(),
+        );
+        for hk in ["1w9ywm", "3zLmZD", "zpLeY", "thqOr", "2Y360b", "2Ram0f"] {
+            assert!(
+                output.contains(&format!("hk: \"{hk}\"")),
+                "{hk} in {output}"
+            );
+        }
+        assert!(output.contains("<a href={url}>"));
+        assert!(output.contains("fbt._param(\"who\", <><span>{who}</span></>)"));
+    }
+
+    #[test]
+    fn evaluates_static_descriptions_and_option_values() {
+        let prelude = "import { fbt } from 'fbtee'; const DESC = 'Greeting' as const; const NAME = `user`; const KIND = DESC + ` ${NAME}`;";
+        let static_source = |body: &str| transform(&format!("{prelude} {body}"), default_options());
+        assert_eq!(
+            static_source(
+                "const x = fbt('Hello ' + fbt.param(NAME!, name), (KIND satisfies string), { project: (DESC) });"
+            ),
+            static_source(
+                "const x = fbt('Hello ' + fbt.param('user', name), 'Greeting user', { project: 'Greeting' });"
+            ),
+        );
+        assert_eq!(
+            static_source(
+                "const x = <fbt desc={KIND}>Hi <fbt:param name={NAME}>{name}</fbt:param>, <a href={url}>go</a> <fbt:plural count={n} many={`${NAME}s`} showCount=\"yes\">user</fbt:plural></fbt>;"
+            ),
+            static_source(
+                "const x = <fbt desc=\"Greeting user\">Hi <fbt:param name=\"user\">{name}</fbt:param>, <a href={url}>go</a> <fbt:plural count={n} many=\"users\" showCount=\"yes\">user</fbt:plural></fbt>;"
+            ),
+        );
+    }
+
+    #[test]
+    fn evaluates_static_enum_values() {
+        let output = transform(
+            "import { fbt } from 'fbtee'; const PHOTOS = 'photos'; const x = fbt('See ' + fbt.enum(id, ['groups', PHOTOS] as const), 'enums!');",
+            default_options(),
+        );
+        assert!(output.contains("photos: \"photos\""), "{output}");
+    }
+
+    #[test]
+    #[should_panic(expected = "fbt(...) description must be a string literal.")]
+    fn does_not_read_shadowed_constants() {
+        transform(
+            "import { fbt } from 'fbtee'; const DESC = 'Greeting'; function f(DESC) { return fbt('Hello', DESC); }",
+            default_options(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "fbt(...) description must be a string literal.")]
+    fn does_not_read_reassignable_bindings() {
+        transform(
+            "import { fbt } from 'fbtee'; let DESC = 'Greeting'; const x = fbt('Hello', DESC);",
+            default_options(),
+        );
+    }
+
+    fn folding_options() -> PluginOptions {
+        PluginOptions {
+            fold_constant_variations: true,
+            ..default_options()
+        }
+    }
+
+    fn hash_key(output: &str) -> &str {
+        let start = output.find("hk: \"").expect("expected an hk") + 5;
+        &output[start..start + output[start..].find('"').unwrap()]
+    }
+
+    #[test]
+    fn folds_constant_enum_values_without_changing_hash_keys() {
+        let source = "import { fbt } from 'fbtee'; const KIND = 'photos'; const x = fbt('See ' + fbt.enum(KIND, ['groups', 'photos']), 'enums!');";
+        let full = transform(source, default_options());
+        let folded = transform(source, folding_options());
+        assert!(full.contains("groups: \"See groups\""), "{full}");
+        assert!(!folded.contains("See groups"), "{folded}");
+        assert!(folded.contains("photos: \"See photos\""), "{folded}");
+        assert!(folded.contains("fbt._enum(KIND, {"), "{folded}");
+        assert_eq!(hash_key(&folded), hash_key(&full));
+    }
+
+    #[test]
+    fn folds_constant_plural_counts_and_pronoun_genders() {
+        let source = "import { fbt, GenderConst } from 'fbtee'; const x = fbt(fbt.plural('cat', 1) + ' for ' + fbt.pronoun('object', GenderConst.FEMALE_SINGULAR), 'd');";
+        let full = transform(source, default_options());
+        let folded = transform(source, folding_options());
+        assert!(full.contains("\"*\": {"), "{full}");
+        assert!(
+            folded.contains("_1: {\n        \"1\": \"cat for her\"\n    }"),
+            "{folded}"
+        );
+        assert!(!folded.contains("cats"), "{folded}");
+        assert_eq!(hash_key(&folded), hash_key(&full));
+
+        let plural = transform(
+            "import { fbt } from 'fbtee'; const x = fbt(fbt.plural('cat', 3), 'd');",
+            folding_options(),
+        );
+        assert!(plural.contains("\"*\": \"cats\""), "{plural}");
+        assert!(!plural.contains("_1"), "{plural}");
+    }
+
+    #[test]
+    fn keeps_tables_for_runtime_variation_values() {
+        let source = "import { fbt } from 'fbtee'; const x = fbt(fbt.plural('cat', count) + fbt.enum(kind, ['a', 'b']), 'd');";
+        assert_eq!(
+            transform(source, folding_options()),
+            transform(source, default_options())
+        );
+    }
+
+    fn lint_options(config: &str) -> PluginOptions {
+        serde_json::from_str(&format!("{{\"lint\": {config}}}")).expect("expected lint options")
+    }
+
+    #[test]
+    fn warns_about_empty_phrases_and_unhelpful_descriptions() {
+        let (_, warnings) = transform_with_warnings(
+            "import { fbt } from 'fbtee'; const a = fbt(' ', 'Label of the save button'); const b = <fbt desc=\"Save\">Save</fbt>; const c = fbt('Save  draft', 'save draft'); const d = fbt('Save draft', 'Button that saves the post as a draft'); const e = <fbt common>Accept</fbt>;",
+            PluginOptions {
+                fbt_common: BTreeMap::from([("Accept".to_string(), "Acc".to_string())]),
+                ..lint_options(r#"{"emptyStrings": "warn", "unhelpfulDesc": "warn"}"#)
+            },
+        );
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+        assert!(warnings[0].starts_with("This fbt phrase has no text."));
+        assert!(warnings[1].starts_with("The description 'Save' is too short."));
+        assert!(warnings[2].starts_with("The description 'save draft' repeats the text."));
+    }
+
+    #[test]
+    #[should_panic(expected = "The description 'Hi' is too short.")]
+    fn unhelpful_descriptions_can_fail_the_build() {
+        transform(
+            "import { fbs } from 'fbtee'; const a = fbs('Hello', 'Hi');",
+            lint_options(r#"{"unhelpfulDesc": "error"}"#),
+        );
+    }
+
+    #[test]
+    fn warns_about_untranslated_jsx_text_in_configured_dirs() {
+        let source = "import { fbt } from 'fbtee'; const a = <div title=\"Close\" className=\"close\">Hello <fbt desc=\"Greeting to the user\">World <b>there</b></fbt> <code>npm install</code> {'OK'} {fbt('Bye', 'Farewell to the user')}<>{ready ? 'Done' : null}</></div>;";
+        let options = || {
+            lint_options(
+                r#"{"untranslatedStrings": "warn", "untranslatedStringsDirs": ["src/app"], "ignoredWords": ["ok"]}"#,
+            )
+        };
+        let (_, warnings) =
+            transform_file_with_warnings("/repo/src/app/Home.tsx", source, options());
+        assert_eq!(
+            warnings,
+            [
+                "Text 'Close' must be wrapped in <fbt>, fbt() or fbs() so it can be translated.",
+                "Text 'Hello' must be wrapped in <fbt>, fbt() or fbs() so it can be translated.",
+                "Text 'Done' must be wrapped in <fbt>, fbt() or fbs() so it can be translated.",
+            ]
+        );
+        let (_, warnings) =
+            transform_file_with_warnings("/repo/src/lib/Home.tsx", source, options());
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
 }
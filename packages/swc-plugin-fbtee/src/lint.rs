@@ -0,0 +1,285 @@
+use serde::Deserialize;
+use swc_core::ecma::{
+    ast::*,
+    visit::{noop_visit_type, Visit, VisitWith},
+};
+
+/// Compile-time versions of the `eslint-plugin-fbtee` rules. Every rule is off by default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintOptions {
+    /// Phrases without any text, like `fbt('', 'desc')` or `<fbt desc="desc"> </fbt>`.
+    #[serde(default)]
+    pub empty_strings: LintLevel,
+    /// Descriptions that are empty, too short, or repeat the text.
+    #[serde(default)]
+    pub unhelpful_desc: LintLevel,
+    /// JSX text and user-facing attributes outside of fbt.
+    #[serde(default)]
+    pub untranslated_strings: LintLevel,
+    /// Directories checked by `untranslatedStrings`, matched against the file path. Every file is
+    /// checked when this is empty.
+    #[serde(default)]
+    pub untranslated_strings_dirs: Vec<String>,
+    /// Text that `untranslatedStrings` accepts outside of fbt, compared case-insensitively.
+    #[serde(default)]
+    pub ignored_words: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    #[default]
+    Off,
+    Warn,
+    Error,
+}
+
+/// Descriptions with at most this many characters are too short to help translators.
+const SHORT_DESCRIPTION_LENGTH: usize = 4;
+
+/// Attributes whose values are shown to users.
+pub const TRANSLATABLE_ATTRIBUTES: &[&str] = &[
+    "alt",
+    "aria-errormessage",
+    "aria-label",
+    "label",
+    "placeholder",
+    "title",
+];
+
+pub fn empty_phrase_message(module: &str) -> String {
+    format!("This {module} phrase has no text. Empty strings cannot be translated, so add the text or remove the {module} call.")
+}
+
+/// Explains why `desc` does not help translators, if it does not.
+pub fn unhelpful_description_message(module: &str, desc: &str, text: &str) -> Option<String> {
+    let desc = desc.trim();
+    if desc.is_empty() {
+        return Some(format!(
+            "The description of this {module} phrase is empty. Describe where and how the text is used so translators have context."
+        ));
+    }
+    if desc.chars().count() <= SHORT_DESCRIPTION_LENGTH {
+        return Some(format!(
+            "The description '{desc}' is too short. Describe where and how the text is used so translators have context."
+        ));
+    }
+    if collapse_whitespace(desc).to_lowercase() == collapse_whitespace(text).to_lowercase() {
+        return Some(format!(
+            "The description '{desc}' repeats the text. Describe where and how the text is used instead."
+        ));
+    }
+    None
+}
+
+impl LintOptions {
+    /// Whether `untranslatedStrings` applies to `filename`.
+    pub fn checks_untranslated_strings(&self, filename: Option<&str>) -> bool {
+        if self.untranslated_strings == LintLevel::Off {
+            return false;
+        }
+        if self.untranslated_strings_dirs.is_empty() {
+            return true;
+        }
+        let Some(filename) = filename else {
+            return false;
+        };
+        let path = format!("/{}", filename.replace('\\', "/"));
+        self.untranslated_strings_dirs.iter().any(|dir| {
+            let dir = dir.replace('\\', "/");
+            let dir = dir.trim_start_matches("./").trim_matches('/');
+            !dir.is_empty() && path.contains(&format!("/{dir}/"))
+        })
+    }
+
+    /// Messages for user-facing text in `module` that is not inside fbt or fbs.
+    pub fn untranslated_strings(&self, module: &Module) -> Vec<String> {
+        let mut visitor = UntranslatedStrings {
+            ignored_words: self
+                .ignored_words
+                .iter()
+                .map(|word| collapse_whitespace(word).to_lowercase())
+                .collect(),
+            messages: vec![],
+        };
+        module.visit_with(&mut visitor);
+        visitor.messages
+    }
+}
+
+struct UntranslatedStrings {
+    ignored_words: Vec<String>,
+    messages: Vec<String>,
+}
+
+impl UntranslatedStrings {
+    fn check(&mut self, text: &str) {
+        let text = collapse_whitespace(text);
+        if text.is_empty() || self.ignored_words.contains(&text.to_lowercase()) {
+            return;
+        }
+        self.messages.push(format!(
+            "Text '{text}' must be wrapped in <fbt>, fbt() or fbs() so it can be translated."
+        ));
+    }
+
+    fn check_children(&mut self, parent: Option<&JSXElementName>, children: &[JSXElementChild]) {
+        let is_code = matches!(
+            parent,
+            Some(JSXElementName::Ident(ident)) if ident.sym == "pre" || ident.sym == "code"
+        );
+        for child in children {
+            match child {
+                JSXElementChild::JSXText(text) if !is_code => self.check(&text.value),
+                JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                }) if !is_code => {
+                    if let Some(text) = displayed_string(expr) {
+                        self.check(&text);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Visit for UntranslatedStrings {
+    noop_visit_type!();
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if is_fbt_call(call) {
+            return;
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_jsx_element(&mut self, element: &JSXElement) {
+        if is_fbt_element(&element.opening.name) {
+            return;
+        }
+        for attr in &element.opening.attrs {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                continue;
+            };
+            let JSXAttrName::Ident(name) = &attr.name else {
+                continue;
+            };
+            if !TRANSLATABLE_ATTRIBUTES.contains(&name.sym.as_ref()) {
+                continue;
+            }
+            let text = match &attr.value {
+                Some(JSXAttrValue::Str(value)) => Some(value.value.to_atom_lossy().to_string()),
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) => displayed_string(expr),
+                _ => None,
+            };
+            if let Some(text) = text {
+                self.check(&text);
+            }
+        }
+        self.check_children(Some(&element.opening.name), &element.children);
+        element.visit_children_with(self);
+    }
+
+    fn visit_jsx_fragment(&mut self, fragment: &JSXFragment) {
+        self.check_children(None, &fragment.children);
+        fragment.visit_children_with(self);
+    }
+}
+
+/// The string an expression shows, when it is a string literal or chooses between them.
+fn displayed_string(expr: &Expr) -> Option<String> {
+    string_value(expr).filter(|text| !text.trim().is_empty())
+}
+
+fn string_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(value)) => Some(value.value.to_atom_lossy().to_string()),
+        Expr::Tpl(template) if template.exprs.is_empty() => Some(
+            template
+                .quasis
+                .iter()
+                .map(|quasi| quasi.raw.to_string())
+                .collect(),
+        ),
+        Expr::Paren(paren) => string_value(&paren.expr),
+        Expr::Cond(cond) => displayed_string(&cond.cons).or_else(|| displayed_string(&cond.alt)),
+        Expr::Bin(binary) if binary.op == BinaryOp::Add => Some(format!(
+            "{}{}",
+            string_value(&binary.left)?,
+            string_value(&binary.right)?
+        )),
+        Expr::Bin(binary) if matches!(binary.op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) => {
+            displayed_string(&binary.left).or_else(|| displayed_string(&binary.right))
+        }
+        _ => None,
+    }
+}
+
+pub fn is_fbt_call(call: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call.callee else {
+        return false;
+    };
+    let object = match callee.as_ref() {
+        Expr::Member(member) => member.obj.as_ref(),
+        callee => callee,
+    };
+    matches!(object, Expr::Ident(ident) if ident.sym == "fbt" || ident.sym == "fbs")
+}
+
+pub fn is_fbt_element(name: &JSXElementName) -> bool {
+    match name {
+        JSXElementName::Ident(ident) => ident.sym == "fbt" || ident.sym == "fbs",
+        JSXElementName::JSXNamespacedName(namespaced) => {
+            namespaced.ns.sym == "fbt" || namespaced.ns.sym == "fbs"
+        }
+        JSXElementName::JSXMemberExpr(_) => false,
+    }
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_empty_short_and_repeated_descriptions() {
+        assert!(unhelpful_description_message("fbt", " ", "Hello")
+            .unwrap()
+            .contains("is empty"));
+        assert!(unhelpful_description_message("fbt", "Btn", "Save")
+            .unwrap()
+            .contains("'Btn' is too short"));
+        assert!(
+            unhelpful_description_message("fbt", "Save  Draft", "save draft")
+                .unwrap()
+                .contains("repeats the text")
+        );
+        assert_eq!(
+            unhelpful_description_message("fbt", "Button to save a draft", "Save draft"),
+            None
+        );
+    }
+
+    #[test]
+    fn matches_untranslated_strings_dirs_by_path_segment() {
+        let options = LintOptions {
+            untranslated_strings: LintLevel::Warn,
+            untranslated_strings_dirs: vec!["./src/app/".to_string()],
+            ..Default::default()
+        };
+        assert!(options.checks_untranslated_strings(Some("/repo/src/app/Home.tsx")));
+        assert!(options.checks_untranslated_strings(Some("src\\app\\Home.tsx")));
+        assert!(!options.checks_untranslated_strings(Some("/repo/src/application/Home.tsx")));
+        assert!(!options.checks_untranslated_strings(None));
+        assert!(!LintOptions::default().checks_untranslated_strings(Some("src/app/Home.tsx")));
+    }
+}
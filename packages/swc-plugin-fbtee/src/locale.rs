@@ -0,0 +1,432 @@
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LocaleStyle {
+    #[default]
+    Bcp47,
+    Legacy,
+    Preserve,
+}
+
+impl FromStr for LocaleStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "bcp47" => Ok(Self::Bcp47),
+            "legacy" => Ok(Self::Legacy),
+            "preserve" => Ok(Self::Preserve),
+            _ => Err(format!(
+                "Invalid locale style '{value}'. Choose one of: bcp47, legacy, preserve."
+            )),
+        }
+    }
+}
+
+const LEGACY_TO_BCP47: &[(&str, &str)] = &[
+    ("ar_AR", "ar"),
+    ("es_LA", "es-419"),
+    ("fb_AA", "fb-AA"),
+    ("fb_AC", "fb-AC"),
+    ("fb_AR", "ar"),
+    ("fb_HA", "fb-HA"),
+    ("fb_HX", "fb-HX"),
+    ("fb_LL", "fb-LL"),
+    ("fb_LS", "fb-LS"),
+    ("fb_RL", "fb-RL"),
+    ("fb_ZH", "zh"),
+    ("fbt_AC", "fbt-AC"),
+];
+
+const SPECIAL_LOCALE_TO_LANGUAGE: &[(&str, &str)] = &[
+    ("bp_IN", "bho"),
+    ("bv_DE", "bar"),
+    ("cb_IQ", "ckb"),
+    ("ck_US", "chr"),
+    ("cx_PH", "ceb"),
+    ("eh_IN", "hi"),
+    ("em_ZM", "bem"),
+    ("fb_AA", "en"),
+    ("fb_AC", "en"),
+    ("fb_AR", "ar"),
+    ("fb_HA", "en"),
+    ("fb_HX", "en"),
+    ("fb_LL", "en"),
+    ("fb_LS", "en"),
+    ("fb_RL", "en"),
+    ("fb_ZH", "zh"),
+    ("fbt_AC", "en"),
+    ("fn_IT", "fur"),
+    ("fv_NG", "fuv"),
+    ("gx_GR", "grc"),
+    ("lr_IT", "lij"),
+    ("nh_MX", "nah"),
+    ("ns_ZA", "nso"),
+    ("qb_DE", "hsb"),
+    ("qc_GT", "quc"),
+    ("qe_US", "esu"),
+    ("qk_DZ", "kab"),
+    ("qr_GR", "rup"),
+    ("qs_DE", "dsb"),
+    ("qt_US", "tli"),
+    ("qv_IT", "vec"),
+    ("qz_MM", "my"),
+    ("sy_SY", "syr"),
+    ("sz_PL", "szl"),
+    ("tl_PH", "fil"),
+    ("tl_ST", "tlh"),
+    ("tq_AR", "tob"),
+    ("tz_MA", "tzm"),
+    ("zz_TR", "zza"),
+];
+
+const DEPRECATED_LANGUAGES: &[(&str, &str)] = &[
+    ("in", "id"),
+    ("iw", "he"),
+    ("ji", "yi"),
+    ("jw", "jv"),
+    ("mo", "ro"),
+];
+
+struct LegacyAliases {
+    primary: IndexMap<String, String>,
+    all: IndexMap<String, Vec<String>>,
+}
+
+fn legacy_aliases() -> &'static LegacyAliases {
+    static ALIASES: OnceLock<LegacyAliases> = OnceLock::new();
+    ALIASES.get_or_init(|| {
+        let mut aliases = LegacyAliases {
+            primary: IndexMap::new(),
+            all: IndexMap::new(),
+        };
+        let mut add = |bcp47: &str, legacy: &str| {
+            let identity = canonicalize_bcp47(bcp47);
+            aliases
+                .primary
+                .entry(identity.clone())
+                .or_insert_with(|| legacy.to_string());
+            aliases
+                .all
+                .entry(identity)
+                .or_default()
+                .push(legacy.to_string());
+        };
+        for (legacy, bcp47) in LEGACY_TO_BCP47 {
+            add(bcp47, legacy);
+        }
+        for (legacy, language) in SPECIAL_LOCALE_TO_LANGUAGE {
+            let region = legacy.split('_').nth(1).unwrap_or_default();
+            add(&format!("{language}-{region}"), legacy);
+        }
+        aliases
+    })
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, value)| *value)
+}
+
+struct LanguageTag<'a> {
+    language: String,
+    region: Option<String>,
+    subtags: Vec<&'a str>,
+}
+
+fn parse_language_tag(locale: &str) -> Option<LanguageTag<'_>> {
+    let mut subtags = locale.split('-');
+    let language = subtags.next()?;
+    if !matches!(language.len(), 2..=3 | 5..=8)
+        || !language.chars().all(|ch| ch.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    let rest: Vec<&str> = subtags.collect();
+    if rest
+        .iter()
+        .any(|subtag| subtag.is_empty() || !subtag.chars().all(|ch| ch.is_ascii_alphanumeric()))
+    {
+        return None;
+    }
+    let language = language.to_ascii_lowercase();
+    let language = lookup(DEPRECATED_LANGUAGES, &language)
+        .map(str::to_string)
+        .unwrap_or(language);
+    let region = rest
+        .iter()
+        .take(2)
+        .find(|subtag| is_region_subtag(subtag))
+        .map(|region| region.to_ascii_uppercase());
+    Some(LanguageTag {
+        language,
+        region,
+        subtags: rest,
+    })
+}
+
+fn is_region_subtag(subtag: &str) -> bool {
+    (subtag.len() == 2 && subtag.chars().all(|ch| ch.is_ascii_alphabetic()))
+        || (subtag.len() == 3 && subtag.chars().all(|ch| ch.is_ascii_digit()))
+}
+
+fn canonicalize_bcp47(locale: &str) -> String {
+    let Some(tag) = parse_language_tag(locale) else {
+        return locale.to_string();
+    };
+    let mut output = tag.language;
+    for (index, subtag) in tag.subtags.iter().enumerate() {
+        output.push('-');
+        if index == 0 && subtag.len() == 4 && subtag.chars().all(|ch| ch.is_ascii_alphabetic()) {
+            let mut chars = subtag.chars();
+            if let Some(first) = chars.next() {
+                output.push(first.to_ascii_uppercase());
+                output.push_str(&chars.as_str().to_ascii_lowercase());
+            }
+        } else if index < 2 && is_region_subtag(subtag) {
+            output.push_str(&subtag.to_ascii_uppercase());
+        } else {
+            output.push_str(&subtag.to_ascii_lowercase());
+        }
+    }
+    output
+}
+
+fn identity_language(identity: &str) -> String {
+    match parse_language_tag(identity) {
+        Some(tag) => tag.language,
+        None => identity
+            .split(['_', '-'])
+            .next()
+            .filter(|language| !language.is_empty())
+            .unwrap_or(identity)
+            .to_string(),
+    }
+}
+
+fn legacy_alias(locale: &str) -> Option<String> {
+    let normalized = locale.trim().replace('-', "_");
+    let (language, region) = normalized.split_once('_')?;
+    let is_language =
+        (2..=3).contains(&language.len()) && language.chars().all(|ch| ch.is_ascii_alphabetic());
+    let is_region =
+        (2..=3).contains(&region.len()) && region.chars().all(|ch| ch.is_ascii_alphanumeric());
+    (is_language && is_region).then(|| {
+        format!(
+            "{}_{}",
+            language.to_ascii_lowercase(),
+            region.to_ascii_uppercase()
+        )
+    })
+}
+
+/// Returns the canonical BCP 47 identity shared by every alias of a locale.
+pub fn locale_identity(locale: &str) -> String {
+    let trimmed = locale.trim();
+    if let Some(alias) = legacy_alias(trimmed) {
+        if let Some(special) = lookup(LEGACY_TO_BCP47, &alias) {
+            return canonicalize_bcp47(special);
+        }
+        if let Some(language) = lookup(SPECIAL_LOCALE_TO_LANGUAGE, &alias) {
+            let region = alias.split('_').nth(1).unwrap_or_default();
+            return canonicalize_bcp47(&format!("{language}-{region}"));
+        }
+        return canonicalize_bcp47(&alias.replace('_', "-"));
+    }
+    canonicalize_bcp47(trimmed)
+}
+
+pub fn locale_language(locale: &str) -> String {
+    if let Some(language) =
+        legacy_alias(locale).and_then(|alias| lookup(SPECIAL_LOCALE_TO_LANGUAGE, &alias))
+    {
+        return language.to_string();
+    }
+    identity_language(&locale_identity(locale))
+}
+
+/// Accepts BCP 47 tags (`de-DE`, `zh-Hant-TW`) and legacy fbt locales (`de_DE`, `es_LA`).
+pub fn is_valid_locale(locale: &str) -> bool {
+    !locale.is_empty()
+        && locale == locale.trim()
+        && locale
+            .split(['-', '_'])
+            .all(|subtag| (1..=8).contains(&subtag.len()))
+        && (legacy_alias(locale).is_some() || parse_language_tag(locale).is_some())
+}
+
+pub fn format_locale_for_style(locale: &str, style: LocaleStyle) -> String {
+    if style == LocaleStyle::Preserve {
+        return locale.to_string();
+    }
+
+    let identity = locale_identity(locale);
+    if style == LocaleStyle::Bcp47 {
+        return identity;
+    }
+
+    if let Some(legacy) = legacy_aliases().primary.get(&identity) {
+        return legacy.clone();
+    }
+    if let Some(alias) = legacy_alias(locale) {
+        return alias;
+    }
+
+    match parse_language_tag(&identity) {
+        Some(LanguageTag {
+            language,
+            region: Some(region),
+            ..
+        }) => format!("{language}_{region}"),
+        _ => identity.replace('-', "_"),
+    }
+}
+
+pub fn locale_file_aliases(locale: &str) -> Vec<String> {
+    let identity = locale_identity(locale);
+    let mut aliases = IndexSet::new();
+    aliases.insert(locale.to_string());
+    aliases.insert(identity.clone());
+    aliases.insert(format_locale_for_style(locale, LocaleStyle::Legacy));
+    if let Some(legacy) = legacy_aliases().all.get(&identity) {
+        aliases.extend(legacy.iter().cloned());
+    }
+    aliases
+        .into_iter()
+        .filter(|alias| !alias.is_empty())
+        .collect()
+}
+
+fn locale_file_stem(file: &Path) -> String {
+    file.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+pub fn conflicting_locale_files(files: &[PathBuf]) -> Vec<Vec<PathBuf>> {
+    let mut identity_to_files: IndexMap<String, Vec<PathBuf>> = IndexMap::new();
+    for file in files {
+        identity_to_files
+            .entry(locale_identity(&locale_file_stem(file)))
+            .or_default()
+            .push(file.clone());
+    }
+    identity_to_files
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .collect()
+}
+
+pub fn check_locale_file_conflicts(files: &[PathBuf]) -> Result<(), String> {
+    let conflicts = conflicting_locale_files(files);
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    Err(conflicts
+        .iter()
+        .map(|files| {
+            let mut lines = vec![format!(
+                "Conflicting translation files for locale \"{}\":",
+                locale_identity(&locale_file_stem(&files[0]))
+            )];
+            lines.extend(files.iter().map(|file| format!("- {}", file.display())));
+            lines.push("Keep only one file. These names refer to the same locale.".to_string());
+            lines.join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
+/// Lists the `*.json` files of a translation directory in a stable order.
+pub fn locale_files(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    if !directory.exists() {
+        return Ok(vec![]);
+    }
+    let entries = fs::read_dir(directory).map_err(|error| {
+        format!(
+            "Could not read directory '{}': {error}",
+            directory.display()
+        )
+    })?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+pub fn available_locale_file(directory: &Path, locale: &str) -> Result<Option<PathBuf>, String> {
+    let aliases: IndexSet<String> = locale_file_aliases(locale).into_iter().collect();
+    let matches: Vec<PathBuf> = locale_files(directory)?
+        .into_iter()
+        .filter(|file| aliases.contains(&locale_file_stem(file)))
+        .collect();
+    if matches.len() > 1 {
+        check_locale_file_conflicts(&matches)?;
+    }
+    Ok(matches.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_legacy_and_special_locales_to_bcp47() {
+        assert_eq!(locale_identity("en_US"), "en-US");
+        assert_eq!(locale_identity("es_LA"), "es-419");
+        assert_eq!(locale_identity("fb_HX"), "fb-HX");
+        assert_eq!(locale_identity("tl_PH"), "fil-PH");
+        assert_eq!(locale_identity("zh-hant-tw"), "zh-Hant-TW");
+        assert_eq!(locale_language("cb_IQ"), "ckb");
+        assert_eq!(locale_language("pt-BR"), "pt");
+        assert!(is_valid_locale("es_LA") && is_valid_locale("zh-Hant-TW"));
+        assert!(!is_valid_locale("de DE") && !is_valid_locale("x") && !is_valid_locale(""));
+    }
+
+    #[test]
+    fn formats_locales_for_each_style() {
+        assert_eq!(format_locale_for_style("ar_AR", LocaleStyle::Bcp47), "ar");
+        assert_eq!(format_locale_for_style("ar", LocaleStyle::Legacy), "ar_AR");
+        assert_eq!(
+            format_locale_for_style("es-419", LocaleStyle::Legacy),
+            "es_LA"
+        );
+        assert_eq!(
+            format_locale_for_style("de-DE", LocaleStyle::Legacy),
+            "de_DE"
+        );
+        assert_eq!(format_locale_for_style("ja", LocaleStyle::Legacy), "ja");
+        assert_eq!(
+            format_locale_for_style("de-de", LocaleStyle::Preserve),
+            "de-de"
+        );
+    }
+
+    #[test]
+    fn reports_conflicting_locale_files() {
+        let files = vec![
+            PathBuf::from("translations/de_DE.json"),
+            PathBuf::from("translations/de-DE.json"),
+            PathBuf::from("translations/fr_FR.json"),
+        ];
+        let error = check_locale_file_conflicts(&files).unwrap_err();
+        assert_eq!(
+            error,
+            "Conflicting translation files for locale \"de-DE\":\n- translations/de_DE.json\n- translations/de-DE.json\nKeep only one file. These names refer to the same locale."
+        );
+    }
+}
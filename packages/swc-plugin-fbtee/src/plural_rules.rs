@@ -0,0 +1,596 @@
+use crate::{
+    locale::{locale_identity, locale_language},
+    translations::{TranslationEntry, MASK_NUMBER},
+};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+/// CLDR 44 plural rules from `cldr-json`, distributed under the Unicode License in `cldr/LICENSE`.
+const CARDINAL_DATA: &str = include_str!("cldr/plurals.json");
+const ORDINAL_DATA: &str = include_str!("cldr/ordinals.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    pub const ALL: [PluralCategory; 6] = [
+        Self::Zero,
+        Self::One,
+        Self::Two,
+        Self::Few,
+        Self::Many,
+        Self::Other,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+
+    /// The `IntlVariations` number value fbtee uses for this category in translation files.
+    pub fn variation(self) -> i32 {
+        match self {
+            Self::Zero => 16,
+            Self::One => 4,
+            Self::Two => 8,
+            Self::Few => 20,
+            Self::Many => 12,
+            Self::Other => 24,
+        }
+    }
+
+    pub fn from_variation(value: i32) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.variation() == value)
+    }
+}
+
+impl fmt::Display for PluralCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PluralCategory {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == value)
+            .ok_or_else(|| format!("Unknown plural category '{value}'."))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralType {
+    Cardinal,
+    Ordinal,
+}
+
+/// Plural rules for one locale, evaluated from the bundled CLDR data.
+#[derive(Debug, Clone)]
+pub struct PluralRules {
+    rules: Vec<(PluralCategory, Condition)>,
+}
+
+impl PluralRules {
+    /// Resolves rules by full locale identity first, then language, then CLDR `root`.
+    pub fn new(locale: &str, kind: PluralType) -> Self {
+        let data = match kind {
+            PluralType::Cardinal => cardinal_data(),
+            PluralType::Ordinal => ordinal_data(),
+        };
+        let rules = [locale_identity(locale), locale_language(locale)]
+            .iter()
+            .find_map(|key| data.get(key))
+            .or_else(|| data.get("root"))
+            .cloned()
+            .unwrap_or_default();
+        Self { rules }
+    }
+
+    pub fn cardinal(locale: &str) -> Self {
+        Self::new(locale, PluralType::Cardinal)
+    }
+
+    pub fn ordinal(locale: &str) -> Self {
+        Self::new(locale, PluralType::Ordinal)
+    }
+
+    /// Categories used by this locale, in CLDR order. Always ends with `other`.
+    pub fn categories(&self) -> Vec<PluralCategory> {
+        let mut categories: Vec<PluralCategory> =
+            self.rules.iter().map(|(category, _)| *category).collect();
+        categories.push(PluralCategory::Other);
+        categories
+    }
+
+    /// Selects the category for a number written in source notation, e.g. `1`, `1.50` or `1c6`.
+    pub fn select(&self, number: &str) -> Result<PluralCategory, String> {
+        let operands = Operands::from_str(number)?;
+        Ok(self.select_operands(&operands))
+    }
+
+    pub fn select_operands(&self, operands: &Operands) -> PluralCategory {
+        self.rules
+            .iter()
+            .find(|(_, condition)| condition.matches(operands))
+            .map(|(category, _)| *category)
+            .unwrap_or(PluralCategory::Other)
+    }
+}
+
+/// Returns the cardinal categories `locale` needs but that no translation of `entry` covers,
+/// keyed by the index of each number variation.
+pub fn missing_number_categories(
+    entry: &TranslationEntry,
+    locale: &str,
+) -> Vec<(usize, Vec<PluralCategory>)> {
+    let categories = PluralRules::cardinal(locale).categories();
+    entry
+        .types
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter(|(_, kind)| **kind == MASK_NUMBER)
+        .filter_map(|(index, _)| {
+            let key = index.to_string();
+            let present: Vec<PluralCategory> = entry
+                .translations
+                .iter()
+                .filter_map(|translation| translation.variations.as_ref()?.get(&key)?.as_i64())
+                .filter_map(|value| PluralCategory::from_variation(value as i32))
+                .collect();
+            let missing: Vec<PluralCategory> = categories
+                .iter()
+                .copied()
+                .filter(|category| !present.contains(category))
+                .collect();
+            (!missing.is_empty()).then_some((index, missing))
+        })
+        .collect()
+}
+
+type RuleSet = IndexMap<String, Vec<(PluralCategory, Condition)>>;
+
+#[derive(Deserialize)]
+struct CldrPluralsFile {
+    supplemental: IndexMap<String, serde_json::Value>,
+}
+
+fn cardinal_data() -> &'static RuleSet {
+    static DATA: OnceLock<RuleSet> = OnceLock::new();
+    DATA.get_or_init(|| load_rules(CARDINAL_DATA, "plurals-type-cardinal"))
+}
+
+fn ordinal_data() -> &'static RuleSet {
+    static DATA: OnceLock<RuleSet> = OnceLock::new();
+    DATA.get_or_init(|| load_rules(ORDINAL_DATA, "plurals-type-ordinal"))
+}
+
+fn load_rules(source: &str, key: &str) -> RuleSet {
+    let file: CldrPluralsFile =
+        serde_json::from_str(source).expect("bundled CLDR plural data must be valid JSON");
+    let locales: IndexMap<String, IndexMap<String, String>> = serde_json::from_value(
+        file.supplemental
+            .get(key)
+            .cloned()
+            .expect("bundled CLDR plural data must contain rules"),
+    )
+    .expect("bundled CLDR plural rules must be strings");
+    locales
+        .into_iter()
+        .map(|(locale, rules)| {
+            let rules = rules
+                .into_iter()
+                .filter_map(|(name, rule)| {
+                    let category = name
+                        .strip_prefix("pluralRule-count-")?
+                        .parse::<PluralCategory>()
+                        .ok()?;
+                    (category != PluralCategory::Other).then(|| {
+                        let condition = parse_condition(&rule).unwrap_or_else(|error| {
+                            panic!("Invalid CLDR plural rule for '{locale}': {error}")
+                        });
+                        (category, condition)
+                    })
+                })
+                .collect();
+            (locale, rules)
+        })
+        .collect()
+}
+
+/// CLDR plural operands, see https://unicode.org/reports/tr35/tr35-numbers.html#Operands.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Operands {
+    pub n: f64,
+    pub i: u64,
+    pub v: u64,
+    pub w: u64,
+    pub f: u64,
+    pub t: u64,
+    pub c: u64,
+}
+
+impl Operands {
+    fn get(&self, operand: Operand) -> f64 {
+        match operand {
+            Operand::N => self.n,
+            Operand::I => self.i as f64,
+            Operand::V => self.v as f64,
+            Operand::W => self.w as f64,
+            Operand::F => self.f as f64,
+            Operand::T => self.t as f64,
+            Operand::C => self.c as f64,
+        }
+    }
+}
+
+impl From<u64> for Operands {
+    fn from(value: u64) -> Self {
+        Self {
+            n: value as f64,
+            i: value,
+            ..Self::default()
+        }
+    }
+}
+
+impl FromStr for Operands {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{value}' is not a valid number.");
+        let unsigned = value.trim().trim_start_matches(['-', '+']);
+        let (mantissa, exponent) = match unsigned.split_once(['c', 'e']) {
+            Some((mantissa, exponent)) => {
+                (mantissa, exponent.parse::<u32>().map_err(|_| invalid())?)
+            }
+            None => (unsigned, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if integer.is_empty()
+            || !integer.chars().all(|ch| ch.is_ascii_digit())
+            || !fraction.chars().all(|ch| ch.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        // Apply the compact exponent by shifting the decimal point.
+        let shift = (exponent as usize).min(fraction.len());
+        let integer = format!(
+            "{integer}{}{}",
+            &fraction[..shift],
+            "0".repeat(exponent as usize - shift)
+        );
+        let fraction = &fraction[shift..];
+        let trimmed = fraction.trim_end_matches('0');
+        let parse = |digits: &str| -> u64 {
+            digits.chars().fold(0u64, |acc, ch| {
+                acc.saturating_mul(10)
+                    .saturating_add(u64::from(ch as u8 - b'0'))
+            })
+        };
+        Ok(Self {
+            n: format!("{integer}.{fraction}0")
+                .parse::<f64>()
+                .map_err(|_| invalid())?,
+            i: parse(&integer),
+            v: fraction.len() as u64,
+            w: trimmed.len() as u64,
+            f: parse(fraction),
+            t: parse(trimmed),
+            c: u64::from(exponent),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    N,
+    I,
+    V,
+    W,
+    F,
+    T,
+    C,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Or(Vec<Condition>),
+    And(Vec<Condition>),
+    Relation {
+        operand: Operand,
+        modulus: Option<u64>,
+        negated: bool,
+        within: bool,
+        ranges: Vec<(u64, u64)>,
+    },
+}
+
+impl Condition {
+    fn matches(&self, operands: &Operands) -> bool {
+        match self {
+            Condition::Or(conditions) => conditions.iter().any(|c| c.matches(operands)),
+            Condition::And(conditions) => conditions.iter().all(|c| c.matches(operands)),
+            Condition::Relation {
+                operand,
+                modulus,
+                negated,
+                within,
+                ranges,
+            } => {
+                let mut value = operands.get(*operand);
+                if let Some(modulus) = modulus {
+                    value %= *modulus as f64;
+                }
+                let is_integer = value.fract() == 0.0;
+                let found = ranges.iter().any(|(start, end)| {
+                    (*within || is_integer) && value >= *start as f64 && value <= *end as f64
+                });
+                found != *negated
+            }
+        }
+    }
+}
+
+fn parse_condition(rule: &str) -> Result<Condition, String> {
+    // Everything after '@' is sample data.
+    let rule = rule.split('@').next().unwrap_or_default().trim();
+    if rule.is_empty() {
+        return Ok(Condition::Or(vec![]));
+    }
+    let tokens = tokenize(rule)?;
+    let mut parser = RuleParser { tokens, index: 0 };
+    let condition = parser.or_condition()?;
+    match parser.peek() {
+        None => Ok(condition),
+        Some(token) => Err(format!("Unexpected token '{token}' in '{rule}'.")),
+    }
+}
+
+fn tokenize(rule: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut chars = rule.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+        } else if ch.is_ascii_alphanumeric() {
+            let mut word = String::new();
+            while let Some(&ch) = chars.peek().filter(|ch| ch.is_ascii_alphanumeric()) {
+                word.push(ch);
+                chars.next();
+            }
+            tokens.push(word);
+        } else if ch == '.' {
+            chars.next();
+            if chars.next() != Some('.') {
+                return Err(format!("Expected '..' in '{rule}'."));
+            }
+            tokens.push("..".to_string());
+        } else if ch == '!' {
+            chars.next();
+            if chars.next() != Some('=') {
+                return Err(format!("Expected '!=' in '{rule}'."));
+            }
+            tokens.push("!=".to_string());
+        } else if matches!(ch, '=' | ',' | '%') {
+            chars.next();
+            tokens.push(ch.to_string());
+        } else {
+            return Err(format!("Unexpected character '{ch}' in '{rule}'."));
+        }
+    }
+    Ok(tokens)
+}
+
+struct RuleParser {
+    tokens: Vec<String>,
+    index: usize,
+}
+
+impl RuleParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.index).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Result<String, String> {
+        let token = self
+            .tokens
+            .get(self.index)
+            .cloned()
+            .ok_or_else(|| "Unexpected end of plural rule.".to_string())?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or_condition(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.and_condition()?];
+        while self.eat("or") {
+            conditions.push(self.and_condition()?);
+        }
+        Ok(Condition::Or(conditions))
+    }
+
+    fn and_condition(&mut self) -> Result<Condition, String> {
+        let mut relations = vec![self.relation()?];
+        while self.eat("and") {
+            relations.push(self.relation()?);
+        }
+        Ok(Condition::And(relations))
+    }
+
+    fn relation(&mut self) -> Result<Condition, String> {
+        let operand = match self.next()?.as_str() {
+            "n" => Operand::N,
+            "i" => Operand::I,
+            "v" => Operand::V,
+            "w" => Operand::W,
+            "f" => Operand::F,
+            "t" => Operand::T,
+            "c" | "e" => Operand::C,
+            token => return Err(format!("Unknown plural operand '{token}'.")),
+        };
+        let modulus = if self.eat("mod") || self.eat("%") {
+            Some(self.number()?)
+        } else {
+            None
+        };
+        let (negated, within) = match self.next()?.as_str() {
+            "=" => (false, false),
+            "!=" => (true, false),
+            "is" => (self.eat("not"), false),
+            "in" => (false, false),
+            "within" => (false, true),
+            "not" => match self.next()?.as_str() {
+                "in" => (true, false),
+                "within" => (true, true),
+                token => return Err(format!("Unexpected token '{token}' after 'not'.")),
+            },
+            token => return Err(format!("Unknown plural relation '{token}'.")),
+        };
+        let mut ranges = vec![];
+        loop {
+            let start = self.number()?;
+            let end = if self.eat("..") {
+                self.number()?
+            } else {
+                start
+            };
+            ranges.push((start, end));
+            if !self.eat(",") {
+                break;
+            }
+        }
+        Ok(Condition::Relation {
+            operand,
+            modulus,
+            negated,
+            within,
+            ranges,
+        })
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        let token = self.next()?;
+        token
+            .parse::<u64>()
+            .map_err(|_| format!("Expected a number in plural rule, found '{token}'."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(rules: &PluralRules, number: &str) -> &'static str {
+        rules.select(number).unwrap().as_str()
+    }
+
+    #[test]
+    fn selects_russian_cardinal_categories() {
+        let rules = PluralRules::cardinal("ru_RU");
+        assert_eq!(
+            rules.categories(),
+            vec![
+                PluralCategory::One,
+                PluralCategory::Few,
+                PluralCategory::Many,
+                PluralCategory::Other
+            ]
+        );
+        assert_eq!(select(&rules, "1"), "one");
+        assert_eq!(select(&rules, "21"), "one");
+        assert_eq!(select(&rules, "3"), "few");
+        assert_eq!(select(&rules, "12"), "many");
+        assert_eq!(select(&rules, "5"), "many");
+        assert_eq!(select(&rules, "1.5"), "other");
+    }
+
+    #[test]
+    fn uses_visible_fraction_digits() {
+        let rules = PluralRules::cardinal("en");
+        assert_eq!(select(&rules, "1"), "one");
+        assert_eq!(select(&rules, "1.0"), "other");
+        let rules = PluralRules::cardinal("fr_FR");
+        assert_eq!(select(&rules, "1.5"), "one");
+        assert_eq!(select(&rules, "1000000"), "many");
+        assert_eq!(select(&rules, "1c6"), "many");
+    }
+
+    #[test]
+    fn selects_arabic_and_ordinal_categories() {
+        let rules = PluralRules::cardinal("ar_AR");
+        assert_eq!(select(&rules, "0"), "zero");
+        assert_eq!(select(&rules, "2"), "two");
+        assert_eq!(select(&rules, "105"), "few");
+        assert_eq!(select(&rules, "111"), "many");
+        assert_eq!(select(&rules, "100"), "other");
+
+        let rules = PluralRules::ordinal("en_US");
+        assert_eq!(select(&rules, "1"), "one");
+        assert_eq!(select(&rules, "22"), "two");
+        assert_eq!(select(&rules, "13"), "other");
+        assert_eq!(select(&rules, "103"), "few");
+    }
+
+    #[test]
+    fn prefers_regional_rules_and_falls_back_to_root() {
+        assert_eq!(select(&PluralRules::cardinal("pt-BR"), "0"), "one");
+        assert_eq!(select(&PluralRules::cardinal("pt_PT"), "0"), "other");
+        assert_eq!(
+            PluralRules::cardinal("xx_XX").categories(),
+            vec![PluralCategory::Other]
+        );
+    }
+
+    #[test]
+    fn reports_missing_number_forms() {
+        let entry: TranslationEntry = serde_json::from_str(
+            r#"{"tokens":["count"],"types":[28],"translations":[
+              {"translation":"{count} файл","variations":{"0":4}},
+              {"translation":"{count} файлов","variations":{"0":12}},
+              {"translation":"{count} файла","variations":{"0":24}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            missing_number_categories(&entry, "ru_RU"),
+            vec![(0, vec![PluralCategory::Few])]
+        );
+        assert!(missing_number_categories(&entry, "de_DE").is_empty());
+    }
+
+    #[test]
+    fn parses_every_bundled_rule() {
+        assert!(cardinal_data().len() > 150);
+        assert!(ordinal_data().len() > 100);
+    }
+}
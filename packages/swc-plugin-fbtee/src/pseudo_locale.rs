@@ -0,0 +1,157 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PseudoLocaleOptions {
+    /// Percentage of the visible text length to add as padding.
+    #[serde(default = "default_expansion")]
+    pub expansion: u32,
+    #[serde(default = "enabled")]
+    pub accents: bool,
+    #[serde(default = "enabled")]
+    pub brackets: bool,
+}
+
+impl Default for PseudoLocaleOptions {
+    fn default() -> Self {
+        Self {
+            expansion: default_expansion(),
+            accents: true,
+            brackets: true,
+        }
+    }
+}
+
+fn default_expansion() -> u32 {
+    30
+}
+
+fn enabled() -> bool {
+    true
+}
+
+const PADDING: char = '~';
+
+/// Rewrites a table pattern into a pseudo-locale, leaving `{token}` placeholders untouched.
+pub fn pseudo_localize(pattern: &str, options: &PseudoLocaleOptions) -> String {
+    if pattern.trim().is_empty() {
+        return pattern.to_string();
+    }
+
+    let mut output = String::new();
+    let mut visible = 0;
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        let token = next_token(rest);
+        let text_end = token.map_or(rest.len(), |(start, _)| start);
+        for ch in rest[..text_end].chars() {
+            if !ch.is_whitespace() {
+                visible += 1;
+            }
+            output.push(if options.accents { accent(ch) } else { ch });
+        }
+        match token {
+            Some((_, end)) => {
+                output.push_str(&rest[text_end..=end]);
+                rest = &rest[end + 1..];
+            }
+            None => rest = "",
+        }
+    }
+
+    let padding = (visible * options.expansion as usize).div_ceil(100);
+    output.extend(std::iter::repeat_n(PADDING, padding));
+    if options.brackets {
+        format!("[{output}]")
+    } else {
+        output
+    }
+}
+
+/// Byte range of the first non-empty `{token}` in `text`, including the braces.
+fn next_token(text: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find('{').map(|start| offset + start) {
+        let end = start + 1 + text[start + 1..].find('}')?;
+        if end > start + 1 {
+            return Some((start, end));
+        }
+        offset = end;
+    }
+    None
+}
+
+fn accent(ch: char) -> char {
+    match ch {
+        'a' => 'á',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'í',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'p' => 'þ',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'ü',
+        'w' => 'ŵ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'B' => 'Ɓ',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Û',
+        'W' => 'Ŵ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accents_pads_and_brackets_text() {
+        let options = PseudoLocaleOptions::default();
+        assert_eq!(
+            pseudo_localize("Hello {name}!", &options),
+            "[Ĥéļļö {name}!~~]"
+        );
+        assert_eq!(pseudo_localize("{=m1}{count}", &options), "[{=m1}{count}]");
+        assert_eq!(pseudo_localize("", &options), "");
+    }
+
+    #[test]
+    fn respects_disabled_features() {
+        let options = PseudoLocaleOptions {
+            expansion: 100,
+            accents: false,
+            brackets: false,
+        };
+        assert_eq!(pseudo_localize("Hi {} {x", &options), "Hi {} {x~~~~~~");
+        assert_eq!(pseudo_localize("a{}{b}", &options), "a{}{b}~~~");
+    }
+}
@@ -0,0 +1,439 @@
+use crate::locale::{
+    available_locale_file, check_locale_file_conflicts, format_locale_for_style, locale_files,
+    LocaleStyle,
+};
+use indexmap::{IndexMap, IndexSet};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub type HashToLeaf = IndexMap<String, Leaf>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Leaf {
+    pub desc: String,
+    pub text: String,
+}
+
+/// The `source_strings.json` file written by `fbtee collect`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectFbtOutput {
+    #[serde(default)]
+    pub child_parent_mappings: IndexMap<String, usize>,
+    pub phrases: Vec<CollectedPhrase>,
+    #[serde(flatten)]
+    pub extra: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectedPhrase {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_to_leaf: Option<HashToLeaf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jsfbt: Option<JsFbt>,
+    #[serde(flatten)]
+    pub extra: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct JsFbt {
+    #[serde(default)]
+    pub m: Vec<Option<JsFbtMetaEntry>>,
+    pub t: Value,
+}
+
+/// Describes one level of the `jsfbt.t` table: a gender/number variation, a pronoun or an enum.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct JsFbtMetaEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub singular: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub variation_type: Option<i32>,
+}
+
+/// `FbtVariationType` values used in `jsfbt.m`.
+pub const VARIATION_TYPE_GENDER: i32 = 1;
+pub const VARIATION_TYPE_NUMBER: i32 = 2;
+pub const VARIATION_TYPE_PRONOUN: i32 = 3;
+
+/// `IntlVariationMask` values used in translation `types`.
+pub const MASK_GENDER: i32 = 3;
+pub const MASK_NUMBER: i32 = 28;
+
+impl JsFbt {
+    /// Returns every leaf of the `t` table with the keys leading to it.
+    pub fn leaves(&self) -> Vec<(Vec<String>, Leaf)> {
+        fn walk(node: &Value, path: &mut Vec<String>, leaves: &mut Vec<(Vec<String>, Leaf)>) {
+            let Value::Object(object) = node else {
+                return;
+            };
+            if let (Some(Value::String(desc)), Some(Value::String(text))) =
+                (object.get("desc"), object.get("text"))
+            {
+                leaves.push((
+                    path.clone(),
+                    Leaf {
+                        desc: desc.clone(),
+                        text: text.clone(),
+                    },
+                ));
+                return;
+            }
+            for (key, child) in object {
+                path.push(key.clone());
+                walk(child, path, leaves);
+                path.pop();
+            }
+        }
+
+        let mut leaves = vec![];
+        walk(&self.t, &mut vec![], &mut leaves);
+        leaves
+    }
+}
+
+impl CollectedPhrase {
+    /// The `file:line` the phrase was collected from, if known.
+    pub fn source_reference(&self) -> Option<String> {
+        let filename = self.extra.get("filename")?.as_str()?;
+        match self
+            .extra
+            .get("loc")
+            .and_then(|loc| loc["start"]["line"].as_u64())
+        {
+            Some(line) => Some(format!("{filename}:{line}")),
+            None => Some(filename.to_string()),
+        }
+    }
+}
+
+impl JsFbtMetaEntry {
+    /// The translation `types` mask a translator may vary this token by, if any.
+    pub fn variation_mask(&self) -> Option<i32> {
+        match self.variation_type {
+            Some(VARIATION_TYPE_GENDER) => Some(MASK_GENDER),
+            Some(VARIATION_TYPE_NUMBER) => Some(MASK_NUMBER),
+            _ => None,
+        }
+    }
+}
+
+impl CollectFbtOutput {
+    /// Merges the `hashToLeaf` maps of every phrase. Later phrases win.
+    pub fn hash_to_leaf(&self) -> HashToLeaf {
+        let mut phrases = HashToLeaf::new();
+        for phrase in &self.phrases {
+            if let Some(hash_to_leaf) = &phrase.hash_to_leaf {
+                for (hash, leaf) in hash_to_leaf {
+                    phrases.insert(hash.clone(), leaf.clone());
+                }
+            }
+        }
+        phrases
+    }
+}
+
+/// Translations for one locale, as read by `fbtee translate`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TranslationGroup {
+    #[serde(
+        default,
+        rename = "fb-locale",
+        skip_serializing_if = "String::is_empty"
+    )]
+    pub fb_locale: String,
+    #[serde(default)]
+    pub translations: Translations,
+    #[serde(flatten)]
+    pub extra: IndexMap<String, Value>,
+}
+
+pub type Translations = IndexMap<String, Option<TranslationEntry>>;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct TranslationEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<Vec<String>>,
+    pub translations: Vec<Translation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<i32>>,
+    #[serde(flatten)]
+    pub extra: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Translation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub translation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variations: Option<IndexMap<String, Value>>,
+}
+
+/// Returns the `{token}` names of a pattern string in order of first appearance, matching the
+/// runtime's `/\{([^}]+)\}/` token pattern.
+pub fn pattern_tokens(text: &str) -> IndexSet<String> {
+    let mut tokens = IndexSet::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        if end > 0 {
+            tokens.insert(rest[..end].to_string());
+            rest = &rest[end + 1..];
+        }
+    }
+    tokens
+}
+
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))?;
+    serde_json::from_str(&contents)
+        .map_err(|error| format!("{error}\nFile path: \"{}\"", path.display()))
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Could not create '{}': {error}", parent.display()))?;
+    }
+    let contents = serde_json::to_string_pretty(value).map_err(|error| error.to_string())?;
+    fs::write(path, contents)
+        .map_err(|error| format!("Could not write '{}': {error}", path.display()))
+}
+
+/// Adds entries for new hashes and drops entries whose hash is no longer collected.
+pub fn update_translations(
+    phrases: &HashToLeaf,
+    translations: Translations,
+    sort_by_hash: bool,
+) -> Translations {
+    let mut updated: Translations = translations
+        .into_iter()
+        .filter(|(hash, _)| phrases.contains_key(hash))
+        .collect();
+    for (hash, phrase) in phrases {
+        if updated.contains_key(hash) {
+            continue;
+        }
+        updated.insert(
+            hash.clone(),
+            Some(TranslationEntry {
+                description: Some(phrase.desc.clone()),
+                status: Some("new".to_string()),
+                tokens: Some(vec![]),
+                translations: vec![Translation {
+                    id: None,
+                    translation: phrase.text.clone(),
+                    variations: Some(IndexMap::new()),
+                }],
+                types: Some(vec![]),
+                extra: IndexMap::new(),
+            }),
+        );
+    }
+
+    if sort_by_hash {
+        updated.sort_keys();
+    }
+    updated
+}
+
+#[derive(Debug, Clone)]
+pub struct PrepareTranslationsOptions {
+    pub locales: Vec<String>,
+    pub output_locale_style: LocaleStyle,
+    pub sort_by_hash: bool,
+}
+
+/// Writes one translation file per locale, merging `source` into existing files. Returns the
+/// output locale names in processing order.
+pub fn prepare_translations(
+    source: &CollectFbtOutput,
+    output_dir: &Path,
+    options: &PrepareTranslationsOptions,
+) -> Result<Vec<String>, String> {
+    let files = locale_files(output_dir)?;
+    check_locale_file_conflicts(&files)?;
+
+    let phrases = source.hash_to_leaf();
+    let mut locales: IndexSet<String> = options.locales.iter().cloned().collect();
+    for file in &files {
+        if let Some(stem) = file.file_stem() {
+            locales.insert(stem.to_string_lossy().into_owned());
+        }
+    }
+
+    let mut processed = vec![];
+    for locale in locales {
+        let existing_file = available_locale_file(output_dir, &locale)?;
+        let output_locale = match &existing_file {
+            Some(file) => file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            None => format_locale_for_style(&locale, options.output_locale_style),
+        };
+        let file_path =
+            existing_file.unwrap_or_else(|| output_dir.join(format!("{output_locale}.json")));
+        let mut group = if file_path.exists() {
+            load_json::<TranslationGroup>(&file_path)?
+        } else {
+            TranslationGroup::default()
+        };
+        group.fb_locale = output_locale.clone();
+        group.translations = update_translations(
+            &phrases,
+            std::mem::take(&mut group.translations),
+            options.sort_by_hash,
+        );
+        write_json(&file_path, &group)?;
+        processed.push(output_locale);
+    }
+    Ok(processed)
+}
+
+/// Merges imported translations into the locale's file in `output_dir`, replacing entries with
+/// the same hash. Returns the path of the written file.
+pub fn merge_translation_group(
+    output_dir: &Path,
+    imported: TranslationGroup,
+    output_locale_style: LocaleStyle,
+) -> Result<PathBuf, String> {
+    check_locale_file_conflicts(&locale_files(output_dir)?)?;
+    let existing_file = available_locale_file(output_dir, &imported.fb_locale)?;
+    let output_locale = match &existing_file {
+        Some(file) => file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        None => format_locale_for_style(&imported.fb_locale, output_locale_style),
+    };
+    let file_path =
+        existing_file.unwrap_or_else(|| output_dir.join(format!("{output_locale}.json")));
+    let mut group = if file_path.exists() {
+        load_json::<TranslationGroup>(&file_path)?
+    } else {
+        TranslationGroup::default()
+    };
+    group.fb_locale = output_locale;
+    group.translations.extend(imported.translations);
+    write_json(&file_path, &group)?;
+    Ok(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(desc: &str, text: &str) -> Leaf {
+        Leaf {
+            desc: desc.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn existing_entry(translation: &str) -> Option<TranslationEntry> {
+        Some(TranslationEntry {
+            description: Some("desc".to_string()),
+            status: Some("translated".to_string()),
+            tokens: Some(vec![]),
+            translations: vec![Translation {
+                id: None,
+                translation: translation.to_string(),
+                variations: Some(IndexMap::new()),
+            }],
+            types: Some(vec![]),
+            extra: IndexMap::new(),
+        })
+    }
+
+    #[test]
+    fn preserves_existing_order_and_appends_new_hashes() {
+        let phrases = HashToLeaf::from([
+            ("zzz".to_string(), leaf("z desc", "z text")),
+            ("aaa".to_string(), leaf("a desc", "a text")),
+            ("newOne".to_string(), leaf("new desc", "new text")),
+        ]);
+        let translations = Translations::from([
+            ("zzz".to_string(), existing_entry("Z translated")),
+            ("removed".to_string(), existing_entry("R translated")),
+            ("aaa".to_string(), existing_entry("A translated")),
+        ]);
+
+        let result = update_translations(&phrases, translations, false);
+
+        assert_eq!(
+            result.keys().collect::<Vec<_>>(),
+            vec!["zzz", "aaa", "newOne"]
+        );
+        let new_entry = result["newOne"].as_ref().unwrap();
+        assert_eq!(new_entry.status.as_deref(), Some("new"));
+        assert_eq!(new_entry.translations[0].translation, "new text");
+        assert_eq!(
+            result["zzz"].as_ref().unwrap().translations[0].translation,
+            "Z translated"
+        );
+    }
+
+    #[test]
+    fn sorts_entries_by_hash() {
+        let phrases = HashToLeaf::from([
+            ("keep2".to_string(), leaf("keep2 desc", "keep2 text")),
+            ("new1".to_string(), leaf("new1 desc", "new1 text")),
+            ("keep1".to_string(), leaf("keep1 desc", "keep1 text")),
+        ]);
+        let translations = Translations::from([
+            ("keep2".to_string(), existing_entry("keep2 translated")),
+            ("keep1".to_string(), existing_entry("keep1 translated")),
+        ]);
+
+        let result = update_translations(&phrases, translations, true);
+
+        assert_eq!(
+            result.keys().collect::<Vec<_>>(),
+            vec!["keep1", "keep2", "new1"]
+        );
+    }
+
+    #[test]
+    fn serializes_new_entries_like_the_babel_cli() {
+        let phrases = HashToLeaf::from([("abc".to_string(), leaf("greeting", "Hello"))]);
+        let group = TranslationGroup {
+            fb_locale: "de-DE".to_string(),
+            extra: IndexMap::new(),
+            translations: update_translations(&phrases, Translations::new(), false),
+        };
+        assert_eq!(
+            serde_json::to_string(&group).unwrap(),
+            r#"{"fb-locale":"de-DE","translations":{"abc":{"description":"greeting","status":"new","tokens":[],"translations":[{"translation":"Hello","variations":{}}],"types":[]}}}"#
+        );
+    }
+
+    #[test]
+    fn round_trips_groups_without_optional_keys() {
+        let json = r#"{"translations":{"abc":{"translations":[{"translation":"Hallo"}]}},"project":"app"}"#;
+        let group: TranslationGroup = serde_json::from_str(json).unwrap();
+        assert_eq!(group.fb_locale, "");
+        assert_eq!(serde_json::to_string(&group).unwrap(), json);
+    }
+}
@@ -0,0 +1,458 @@
+use crate::{
+    locale::{is_valid_locale, locale_identity},
+    plural_rules::missing_number_categories,
+    translations::{
+        pattern_tokens, CollectFbtOutput, JsFbtMetaEntry, Leaf, TranslationEntry, TranslationGroup,
+        MASK_GENDER, MASK_NUMBER,
+    },
+};
+use indexmap::{IndexMap, IndexSet};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Hidden token translators use to vary a phrase by the viewer's gender.
+const VIEWING_USER: &str = "__viewing_user__";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueKind {
+    MalformedLocale,
+    LocaleMismatch,
+    UnknownHash,
+    EmptyTranslation,
+    MissingToken,
+    ExtraToken,
+    UnknownVariationToken,
+    VariationTypeMismatch,
+    InvalidVariation,
+    MissingPluralForm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub severity: Severity,
+    pub file: String,
+    pub locale: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Index into the entry's `translations` array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub errors: usize,
+    pub warnings: usize,
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    fn push(&mut self, issue: Issue) {
+        match issue.severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+        }
+        self.issues.push(issue);
+    }
+
+    /// One `file: severity [kind] hash: message` line per issue.
+    pub fn to_text(&self) -> String {
+        self.issues
+            .iter()
+            .map(|issue| {
+                let severity = match issue.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                let kind = serde_json::to_value(issue.kind)
+                    .ok()
+                    .and_then(|kind| kind.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                match &issue.hash {
+                    Some(hash) => format!(
+                        "{}: {severity} [{kind}] {hash}: {}",
+                        issue.file, issue.message
+                    ),
+                    None => format!("{}: {severity} [{kind}] {}", issue.file, issue.message),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+struct SourceLeaf<'a> {
+    leaf: &'a Leaf,
+    metadata: &'a [Option<JsFbtMetaEntry>],
+}
+
+/// Checks translation files against the phrases collected by `fbtee collect`.
+pub struct TranslationValidator<'a> {
+    leaves: IndexMap<&'a str, SourceLeaf<'a>>,
+}
+
+impl<'a> TranslationValidator<'a> {
+    pub fn new(source: &'a CollectFbtOutput) -> Self {
+        let mut leaves = IndexMap::new();
+        for phrase in &source.phrases {
+            let metadata = phrase
+                .jsfbt
+                .as_ref()
+                .map_or(&[][..], |jsfbt| jsfbt.m.as_slice());
+            for (hash, leaf) in phrase.hash_to_leaf.iter().flatten() {
+                leaves.insert(hash.as_str(), SourceLeaf { leaf, metadata });
+            }
+        }
+        Self { leaves }
+    }
+
+    /// Validates one translation file. `file` is only used to label issues.
+    pub fn validate(&self, file: &str, group: &TranslationGroup) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let locale = group.fb_locale.as_str();
+        let issue = |kind, severity, hash: Option<&str>, message: String| Issue {
+            kind,
+            severity,
+            file: file.to_string(),
+            locale: locale.to_string(),
+            hash: hash.map(str::to_string),
+            translation: None,
+            tokens: vec![],
+            message,
+        };
+
+        let stem = std::path::Path::new(file)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if !is_valid_locale(locale) {
+            report.push(issue(
+                IssueKind::MalformedLocale,
+                Severity::Error,
+                None,
+                format!("Malformed fb-locale '{locale}'."),
+            ));
+        } else if !is_valid_locale(&stem) {
+            report.push(issue(
+                IssueKind::MalformedLocale,
+                Severity::Error,
+                None,
+                format!("Malformed locale file name '{stem}'."),
+            ));
+        } else if locale_identity(&stem) != locale_identity(locale) {
+            report.push(issue(
+                IssueKind::LocaleMismatch,
+                Severity::Error,
+                None,
+                format!("fb-locale '{locale}' does not match the file name '{stem}'."),
+            ));
+        }
+
+        for (hash, entry) in &group.translations {
+            let Some(source) = self.leaves.get(hash.as_str()) else {
+                report.push(issue(
+                    IssueKind::UnknownHash,
+                    Severity::Error,
+                    Some(hash),
+                    "Hash is not part of the collected source strings.".to_string(),
+                ));
+                continue;
+            };
+            if let Some(entry) = entry {
+                for mut found in self.validate_entry(entry, source, locale) {
+                    found.file = file.to_string();
+                    found.locale = locale.to_string();
+                    found.hash = Some(hash.clone());
+                    report.push(found);
+                }
+            }
+        }
+        report
+    }
+
+    fn validate_entry(
+        &self,
+        entry: &TranslationEntry,
+        source: &SourceLeaf,
+        locale: &str,
+    ) -> Vec<Issue> {
+        let mut issues = vec![];
+        let mut push = |kind, severity, translation, tokens: Vec<String>, message| {
+            issues.push(Issue {
+                kind,
+                severity,
+                file: String::new(),
+                locale: String::new(),
+                hash: None,
+                translation,
+                tokens,
+                message,
+            });
+        };
+
+        let tokens = entry.tokens.as_deref().unwrap_or_default();
+        let types = entry.types.as_deref().unwrap_or_default();
+        if tokens.len() != types.len() {
+            push(
+                IssueKind::VariationTypeMismatch,
+                Severity::Error,
+                None,
+                vec![],
+                format!(
+                    "Entry declares {} variation tokens but {} types.",
+                    tokens.len(),
+                    types.len()
+                ),
+            );
+        }
+        let mut masks = vec![];
+        for (token, mask) in tokens.iter().zip(types) {
+            masks.push(*mask);
+            let expected = if token == VIEWING_USER {
+                Some(MASK_GENDER)
+            } else {
+                match source
+                    .metadata
+                    .iter()
+                    .flatten()
+                    .find(|meta| meta.token.as_deref() == Some(token))
+                {
+                    Some(meta) => meta.variation_mask(),
+                    None => {
+                        push(
+                            IssueKind::UnknownVariationToken,
+                            Severity::Error,
+                            None,
+                            vec![token.clone()],
+                            format!("'{token}' is not a gender or number variation of the source phrase."),
+                        );
+                        continue;
+                    }
+                }
+            };
+            if expected != Some(*mask) {
+                push(
+                    IssueKind::VariationTypeMismatch,
+                    Severity::Error,
+                    None,
+                    vec![token.clone()],
+                    format!(
+                        "'{token}' varies by {} but the source phrase declares {}.",
+                        mask_name(Some(*mask)),
+                        mask_name(expected)
+                    ),
+                );
+            }
+        }
+
+        let source_tokens = pattern_tokens(&source.leaf.text);
+        for (index, translation) in entry.translations.iter().enumerate() {
+            for (key, value) in translation.variations.iter().flatten() {
+                let mask = key.parse::<usize>().ok().and_then(|key| masks.get(key));
+                let valid = match mask {
+                    Some(mask) => is_valid_variation(*mask, value),
+                    None => false,
+                };
+                if !valid {
+                    push(
+                        IssueKind::InvalidVariation,
+                        Severity::Error,
+                        Some(index),
+                        vec![],
+                        format!("Invalid variation {key}: {value}."),
+                    );
+                }
+            }
+
+            if translation.translation.trim().is_empty() {
+                if !source.leaf.text.trim().is_empty() {
+                    push(
+                        IssueKind::EmptyTranslation,
+                        Severity::Error,
+                        Some(index),
+                        vec![],
+                        "Translation is empty.".to_string(),
+                    );
+                }
+                continue;
+            }
+
+            let translated_tokens = pattern_tokens(&translation.translation);
+            let missing: Vec<String> = source_tokens
+                .difference(&translated_tokens)
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                push(
+                    IssueKind::MissingToken,
+                    Severity::Error,
+                    Some(index),
+                    missing.clone(),
+                    format!("Translation is missing {}.", format_tokens(&missing)),
+                );
+            }
+            let extra: Vec<String> = translated_tokens
+                .difference(&source_tokens)
+                .cloned()
+                .collect();
+            if !extra.is_empty() {
+                push(
+                    IssueKind::ExtraToken,
+                    Severity::Error,
+                    Some(index),
+                    extra.clone(),
+                    format!("Translation uses unknown {}.", format_tokens(&extra)),
+                );
+            }
+        }
+
+        for (index, categories) in missing_number_categories(entry, locale) {
+            let categories: Vec<&str> = categories
+                .iter()
+                .map(|category| category.as_str())
+                .collect();
+            push(
+                IssueKind::MissingPluralForm,
+                Severity::Warning,
+                None,
+                tokens.get(index).cloned().into_iter().collect(),
+                format!(
+                    "Number variation is missing the {} form(s) required by '{locale}'.",
+                    categories.join(", ")
+                ),
+            );
+        }
+        issues
+    }
+}
+
+fn mask_name(mask: Option<i32>) -> &'static str {
+    match mask {
+        Some(MASK_GENDER) => "gender",
+        Some(MASK_NUMBER) => "number",
+        _ => "no variation",
+    }
+}
+
+fn is_valid_variation(mask: i32, value: &Value) -> bool {
+    match value {
+        Value::String(value) => value == "*" || (mask == MASK_NUMBER && value == "_1"),
+        Value::Number(value) => value.as_i64().is_some_and(|value| match mask {
+            MASK_GENDER => (1..=3).contains(&value),
+            MASK_NUMBER => [4, 8, 12, 16, 20, 24].contains(&value),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+fn format_tokens(tokens: &[String]) -> String {
+    let tokens: IndexSet<String> = tokens.iter().map(|token| format!("{{{token}}}")).collect();
+    tokens.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> CollectFbtOutput {
+        serde_json::from_str(
+            r#"{"phrases":[{
+              "hashToLeaf":{"h1":{"desc":"share","text":"{name} shared {=a photo} {count} times"}},
+              "jsfbt":{"m":[{"token":"name","type":1},{"token":"count","type":2,"singular":true}],"t":{}}
+            }]}"#,
+        )
+        .unwrap()
+    }
+
+    fn group(locale: &str, translations: &str) -> TranslationGroup {
+        serde_json::from_str(&format!(
+            r#"{{"fb-locale":"{locale}","translations":{translations}}}"#
+        ))
+        .unwrap()
+    }
+
+    fn kinds(report: &ValidationReport) -> Vec<IssueKind> {
+        report.issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn accepts_valid_translations() {
+        let source = source();
+        let report = TranslationValidator::new(&source).validate(
+            "de_DE.json",
+            &group(
+                "de_DE",
+                r#"{"h1":{"tokens":["name"],"types":[3],"translations":[
+                  {"translation":"{name} hat {=a photo} {count} mal geteilt","variations":{"0":1}},
+                  {"translation":"{name} hat {=a photo} {count} mal geteilt","variations":{"0":"*"}}
+                ]},"h2":null}"#,
+            ),
+        );
+        assert_eq!(kinds(&report), vec![IssueKind::UnknownHash]);
+    }
+
+    #[test]
+    fn reports_token_and_variation_issues() {
+        let source = source();
+        let report = TranslationValidator::new(&source).validate(
+            "ru_RU.json",
+            &group(
+                "ru_RU",
+                r#"{"h1":{"tokens":["name","count"],"types":[28,28],"translations":[
+                  {"translation":"{name} поделился {photo} {count} раз","variations":{"0":1,"1":4}},
+                  {"translation":"","variations":{"1":24}},
+                  {"translation":"{name} {=a photo} {count}","variations":{"1":7}}
+                ]}}"#,
+            ),
+        );
+        assert_eq!(
+            kinds(&report),
+            vec![
+                IssueKind::VariationTypeMismatch,
+                IssueKind::InvalidVariation,
+                IssueKind::MissingToken,
+                IssueKind::ExtraToken,
+                IssueKind::EmptyTranslation,
+                IssueKind::InvalidVariation,
+                IssueKind::MissingPluralForm,
+                IssueKind::MissingPluralForm,
+            ]
+        );
+        assert_eq!(report.errors, 6);
+        assert_eq!(report.issues[2].tokens, vec!["=a photo"]);
+        assert_eq!(report.issues[2].translation, Some(0));
+    }
+
+    #[test]
+    fn reports_malformed_and_mismatched_locales() {
+        let source = source();
+        let validator = TranslationValidator::new(&source);
+        let report = validator.validate("de DE.json", &group("de DE", "{}"));
+        assert_eq!(kinds(&report), vec![IssueKind::MalformedLocale]);
+        let report = validator.validate("fr_FR.json", &group("de_DE", "{}"));
+        assert_eq!(kinds(&report), vec![IssueKind::LocaleMismatch]);
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"errors":1,"warnings":0,"issues":[{"kind":"locale-mismatch","severity":"error","file":"fr_FR.json","locale":"de_DE","message":"fb-locale 'de_DE' does not match the file name 'fr_FR'."}]}"#
+        );
+    }
+}
@@ -0,0 +1,481 @@
+use crate::{
+    locale::locale_identity,
+    translations::{
+        pattern_tokens, CollectFbtOutput, Translation, TranslationEntry, TranslationGroup,
+        Translations,
+    },
+};
+use indexmap::IndexMap;
+use quick_xml::{
+    escape::{escape, resolve_predefined_entity},
+    events::{BytesStart, Event},
+    Reader, XmlVersion,
+};
+use serde_json::Value;
+
+const XLIFF_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:2.0";
+const METADATA_NAMESPACE: &str = "urn:oasis:names:tc:xliff:metadata:2.0";
+
+/// Writes one XLIFF 2.0 unit per collected hash. Translated entries of `group` become targets,
+/// one segment per translation with its variation keys recorded as unit metadata.
+pub fn export_xliff(
+    source: &CollectFbtOutput,
+    group: Option<&TranslationGroup>,
+    source_locale: &str,
+) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<xliff xmlns=\"{XLIFF_NAMESPACE}\" xmlns:mda=\"{METADATA_NAMESPACE}\" version=\"2.0\" srcLang=\"{}\"",
+        escape(locale_identity(source_locale))
+    ));
+    if let Some(group) = group {
+        xml.push_str(&format!(
+            " trgLang=\"{}\"",
+            escape(locale_identity(&group.fb_locale))
+        ));
+    }
+    xml.push_str(">\n  <file id=\"f1\">\n");
+
+    for (hash, leaf) in source.hash_to_leaf() {
+        let entry = group
+            .and_then(|group| group.translations.get(&hash))
+            .and_then(Option::as_ref)
+            .filter(|entry| entry.status.as_deref() != Some("new"));
+        let mut data = IndexMap::new();
+        for token in pattern_tokens(&leaf.text) {
+            let id = format!("d{}", data.len() + 1);
+            data.insert(token, id);
+        }
+        let mut segments = vec![];
+        match entry.filter(|entry| !entry.translations.is_empty()) {
+            Some(entry) => {
+                for (index, translation) in entry.translations.iter().enumerate() {
+                    for token in pattern_tokens(&translation.translation) {
+                        let id = format!("d{}", data.len() + 1);
+                        data.entry(token).or_insert(id);
+                    }
+                    segments.push((
+                        format!("s{}", index + 1),
+                        Some(translation.translation.as_str()),
+                        translation.variations.clone().unwrap_or_default(),
+                    ));
+                }
+            }
+            None => segments.push(("s1".to_string(), None, IndexMap::new())),
+        }
+
+        xml.push_str(&format!("    <unit id=\"{}\">\n", escape(hash.as_str())));
+        let tokens = entry
+            .and_then(|entry| entry.tokens.as_deref())
+            .unwrap_or_default();
+        let has_variations = segments
+            .iter()
+            .any(|(_, _, variations)| !variations.is_empty());
+        if !tokens.is_empty() || has_variations {
+            xml.push_str("      <mda:metadata>\n");
+            let types = entry.map_or(&[][..], |entry| entry.types.as_deref().unwrap_or_default());
+            for (token, mask) in tokens.iter().zip(types) {
+                xml.push_str(&format!(
+                    "        <mda:metaGroup category=\"token\">\n          <mda:meta type=\"name\">{}</mda:meta>\n          <mda:meta type=\"mask\">{mask}</mda:meta>\n        </mda:metaGroup>\n",
+                    escape(token.as_str())
+                ));
+            }
+            for (id, _, variations) in segments.iter().filter(|(_, _, v)| !v.is_empty()) {
+                xml.push_str(&format!(
+                    "        <mda:metaGroup category=\"variations:{id}\">\n"
+                ));
+                for (index, value) in variations {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    };
+                    xml.push_str(&format!(
+                        "          <mda:meta type=\"{}\">{}</mda:meta>\n",
+                        escape(index.as_str()),
+                        escape(value)
+                    ));
+                }
+                xml.push_str("        </mda:metaGroup>\n");
+            }
+            xml.push_str("      </mda:metadata>\n");
+        }
+        xml.push_str(&format!(
+            "      <notes>\n        <note category=\"description\">{}</note>\n      </notes>\n",
+            escape(leaf.desc.as_str())
+        ));
+        if !data.is_empty() {
+            xml.push_str("      <originalData>\n");
+            for (token, id) in &data {
+                xml.push_str(&format!(
+                    "        <data id=\"{id}\">{{{}}}</data>\n",
+                    escape(token.as_str())
+                ));
+            }
+            xml.push_str("      </originalData>\n");
+        }
+        for (id, target, _) in &segments {
+            let state = if target.is_some() {
+                "translated"
+            } else {
+                "initial"
+            };
+            xml.push_str(&format!(
+                "      <segment id=\"{id}\" state=\"{state}\">\n        <source>{}</source>\n",
+                inline_content(&leaf.text, &data, id)
+            ));
+            if let Some(target) = target {
+                xml.push_str(&format!(
+                    "        <target>{}</target>\n",
+                    inline_content(target, &data, id)
+                ));
+            }
+            xml.push_str("      </segment>\n");
+        }
+        xml.push_str("    </unit>\n");
+    }
+    xml.push_str("  </file>\n</xliff>\n");
+    xml
+}
+
+/// Escapes `text` and replaces `{token}` placeholders with `<ph>` elements. Placeholder ids
+/// are derived from the token and its occurrence so source and target codes correspond.
+fn inline_content(text: &str, data: &IndexMap<String, String>, segment: &str) -> String {
+    let mut output = String::new();
+    let mut occurrences: IndexMap<&str, usize> = IndexMap::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start + 1..].find('}').map(|end| start + 1 + end) else {
+            break;
+        };
+        let token = &rest[start + 1..end];
+        match data.get(token) {
+            Some(id) => {
+                let occurrence = occurrences.entry(token).or_default();
+                *occurrence += 1;
+                output.push_str(&escape(&rest[..start]));
+                output.push_str(&format!(
+                    "<ph id=\"{segment}-{id}-{occurrence}\" dataRef=\"{id}\" disp=\"{}\"/>",
+                    escape(format!("{{{token}}}"))
+                ));
+            }
+            None => output.push_str(&escape(&rest[..=end])),
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(&escape(rest));
+    output
+}
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: IndexMap<String, String>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    fn elements<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter_map(move |child| match child {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    fn element(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find_map(|child| match child {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    fn text(&self) -> String {
+        self.children
+            .iter()
+            .map(|child| match child {
+                Node::Text(text) => text.clone(),
+                Node::Element(element) => element.text(),
+            })
+            .collect()
+    }
+}
+
+fn parse_xml(xml: &str) -> Result<Element, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack = vec![Element::default()];
+    let error = |error: quick_xml::Error| format!("Invalid XLIFF: {error}");
+    loop {
+        match reader.read_event().map_err(error)? {
+            Event::Start(start) => stack.push(element_from(&start)?),
+            Event::Empty(start) => {
+                let element = element_from(&start)?;
+                push_node(&mut stack, Node::Element(element));
+            }
+            Event::End(_) => {
+                let element = stack.pop().filter(|_| !stack.is_empty());
+                let Some(element) = element else {
+                    return Err("Invalid XLIFF: unexpected closing tag.".to_string());
+                };
+                push_node(&mut stack, Node::Element(element));
+            }
+            Event::Text(text) => {
+                push_node(&mut stack, Node::Text(text.xml10_content().into_owned()))
+            }
+            Event::CData(data) => push_node(&mut stack, Node::Text(data.into_inner().into_owned())),
+            Event::GeneralRef(reference) => {
+                let text = match reference.resolve_char_ref().map_err(error)? {
+                    Some(ch) => ch.to_string(),
+                    None => resolve_predefined_entity(&reference)
+                        .ok_or_else(|| {
+                            format!("Invalid XLIFF: unknown entity '&{};'.", &*reference)
+                        })?
+                        .to_string(),
+                };
+                push_node(&mut stack, Node::Text(text));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    match (stack.pop(), stack.is_empty()) {
+        (Some(root), true) => Ok(root),
+        _ => Err("Invalid XLIFF: unclosed element.".to_string()),
+    }
+}
+
+fn element_from(start: &BytesStart) -> Result<Element, String> {
+    let mut attributes = IndexMap::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|error| format!("Invalid XLIFF: {error}"))?;
+        let value = attribute
+            .normalized_value(XmlVersion::Implicit1_0)
+            .map_err(|error| format!("Invalid XLIFF: {error}"))?;
+        attributes.insert(
+            attribute.key.local_name().as_ref().to_string(),
+            value.into_owned(),
+        );
+    }
+    Ok(Element {
+        name: start.local_name().as_ref().to_string(),
+        attributes,
+        children: vec![],
+    })
+}
+
+fn push_node(stack: &mut [Element], node: Node) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
+
+/// Rebuilds `{token}` placeholders from `<ph>` elements.
+fn pattern_from_inline(element: &Element, data: &IndexMap<String, String>) -> String {
+    element
+        .children
+        .iter()
+        .map(|child| match child {
+            Node::Text(text) => text.clone(),
+            Node::Element(element) if element.name == "ph" => element
+                .attribute("dataRef")
+                .and_then(|id| data.get(id))
+                .cloned()
+                .or_else(|| element.attribute("equiv").map(str::to_string))
+                .or_else(|| element.attribute("disp").map(str::to_string))
+                .unwrap_or_default(),
+            Node::Element(element) => pattern_from_inline(element, data),
+        })
+        .collect()
+}
+
+/// Reads an XLIFF 2.0 document into translation entries keyed by hash. Units without any
+/// target are skipped.
+pub fn import_xliff(xml: &str) -> Result<TranslationGroup, String> {
+    let document = parse_xml(xml)?;
+    let root = document
+        .element("xliff")
+        .ok_or_else(|| "Invalid XLIFF: missing <xliff> root element.".to_string())?;
+    if root.attribute("version") != Some("2.0") {
+        return Err(format!(
+            "Unsupported XLIFF version '{}'. Expected '2.0'.",
+            root.attribute("version").unwrap_or_default()
+        ));
+    }
+    let locale = root
+        .attribute("trgLang")
+        .ok_or_else(|| "Invalid XLIFF: missing 'trgLang' attribute.".to_string())?;
+
+    let mut translations = Translations::new();
+    for unit in root.elements("file").flat_map(|file| file.elements("unit")) {
+        let hash = unit
+            .attribute("id")
+            .ok_or_else(|| "Invalid XLIFF: <unit> without 'id'.".to_string())?;
+        let data: IndexMap<String, String> = unit
+            .element("originalData")
+            .into_iter()
+            .flat_map(|original| original.elements("data"))
+            .filter_map(|data| Some((data.attribute("id")?.to_string(), data.text())))
+            .collect();
+
+        let mut tokens = vec![];
+        let mut types = vec![];
+        let mut variations: IndexMap<String, IndexMap<String, Value>> = IndexMap::new();
+        for group in unit
+            .element("metadata")
+            .into_iter()
+            .flat_map(|metadata| metadata.elements("metaGroup"))
+        {
+            let meta = |name: &str| {
+                group
+                    .elements("meta")
+                    .find(|meta| meta.attribute("type") == Some(name))
+                    .map(Element::text)
+            };
+            let category = group.attribute("category").unwrap_or_default();
+            if category == "token" {
+                let (Some(name), Some(mask)) = (meta("name"), meta("mask")) else {
+                    return Err(format!(
+                        "Invalid XLIFF: incomplete token metadata in unit '{hash}'."
+                    ));
+                };
+                tokens.push(name);
+                types.push(mask.trim().parse::<i32>().map_err(|_| {
+                    format!("Invalid XLIFF: invalid variation mask '{mask}' in unit '{hash}'.")
+                })?);
+            } else if let Some(segment) = category.strip_prefix("variations:") {
+                let values = group
+                    .elements("meta")
+                    .filter_map(|meta| {
+                        let value = meta.text();
+                        let value = value
+                            .parse::<i64>()
+                            .map(Value::from)
+                            .unwrap_or(Value::String(value));
+                        Some((meta.attribute("type")?.to_string(), value))
+                    })
+                    .collect();
+                variations.insert(segment.to_string(), values);
+            }
+        }
+
+        let mut entry_translations = vec![];
+        for segment in unit.elements("segment") {
+            let Some(target) = segment.element("target") else {
+                continue;
+            };
+            let id = segment.attribute("id").unwrap_or_default();
+            entry_translations.push(Translation {
+                id: None,
+                translation: pattern_from_inline(target, &data),
+                variations: Some(variations.get(id).cloned().unwrap_or_default()),
+            });
+        }
+        if entry_translations.is_empty() {
+            continue;
+        }
+        let description = unit
+            .element("notes")
+            .into_iter()
+            .flat_map(|notes| notes.elements("note"))
+            .find(|note| note.attribute("category") == Some("description"))
+            .map(Element::text);
+        translations.insert(
+            hash.to_string(),
+            Some(TranslationEntry {
+                description,
+                status: None,
+                tokens: Some(tokens),
+                translations: entry_translations,
+                types: Some(types),
+                extra: IndexMap::new(),
+            }),
+        );
+    }
+
+    Ok(TranslationGroup {
+        fb_locale: locale.to_string(),
+        extra: IndexMap::new(),
+        translations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> CollectFbtOutput {
+        serde_json::from_str(
+            r#"{"phrases":[{"hashToLeaf":{
+              "h1":{"desc":"Greeting & <b>","text":"Hello {name}, you have {count} {=new messages}"},
+              "h2":{"desc":"plain","text":"Plain"}
+            }}]}"#,
+        )
+        .unwrap()
+    }
+
+    fn group() -> TranslationGroup {
+        serde_json::from_str(
+            r#"{"fb-locale":"de-DE","translations":{"h1":{
+              "description":"Greeting & <b>",
+              "tokens":["name","count"],
+              "types":[3,28],
+              "translations":[
+                {"translation":"Hallo {name}, du hast {count} {=new messages}","variations":{"0":1,"1":4}},
+                {"translation":"Hallo {name} & {count} {=new messages} {extra}","variations":{"0":"*","1":24}}
+              ]
+            }}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn exports_units_with_notes_placeholders_and_variations() {
+        let xliff = export_xliff(&source(), Some(&group()), "en_US");
+        assert!(
+            xliff.contains(r#"srcLang="en-US" trgLang="de-DE""#),
+            "{xliff}"
+        );
+        assert!(xliff.contains(r#"<note category="description">Greeting &amp; &lt;b&gt;</note>"#));
+        assert!(xliff.contains(r#"<data id="d3">{=new messages}</data>"#));
+        assert!(xliff
+            .contains(r#"<source>Hello <ph id="s1-d1-1" dataRef="d1" disp="{name}"/>, you have"#));
+        assert!(xliff.contains(r#"<mda:metaGroup category="variations:s2">"#));
+        assert!(xliff.contains(r#"<mda:meta type="0">*</mda:meta>"#));
+        assert!(xliff.contains("<unit id=\"h2\">"));
+    }
+
+    #[test]
+    fn round_trips_tokens_and_variation_keys() {
+        let group = group();
+        let imported = import_xliff(&export_xliff(&source(), Some(&group), "en_US")).unwrap();
+        assert_eq!(imported.fb_locale, "de-DE");
+        assert_eq!(imported.translations.keys().collect::<Vec<_>>(), vec!["h1"]);
+        assert_eq!(imported.translations["h1"], group.translations["h1"]);
+    }
+
+    #[test]
+    fn imports_vendor_formatted_targets() {
+        let group = import_xliff(
+            r#"<?xml version="1.0"?>
+            <xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="fr-FR">
+              <file id="f"><unit id="abc"><segment id="s1">
+                <source>Hi <ph id="1" dataRef="x"/></source>
+                <target>Salut <ph id="1" equiv="{name}"/> &#x263A; <![CDATA[<3]]></target>
+              </segment></unit></file>
+            </xliff>"#,
+        )
+        .unwrap();
+        let entry = group.translations["abc"].as_ref().unwrap();
+        assert_eq!(entry.translations[0].translation, "Salut {name} ☺ <3");
+        assert_eq!(entry.tokens, Some(vec![]));
+        assert!(import_xliff("<xliff version=\"1.2\"/>").is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use std::{fs, path::PathBuf, process::Command};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fbtee-rs-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn codemods_keep_going_past_files_that_fail() {
+    let dir = temp_dir("codemod-errors");
+    fs::write(dir.join("a.jsx"), "const a = <p>Hello world</p>;\n").unwrap();
+    fs::write(dir.join("b.jsx"), "const b = <p>Broken</;\n").unwrap();
+    fs::write(dir.join("c.jsx"), "const c = <p>Goodbye</p>;\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_fbtee-rs"))
+        .arg("wrap-jsx-text")
+        .arg(&dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("Changed 2 phrases in 2 files."), "{stdout}");
+    assert!(stderr.contains("Could not parse"), "{stderr}");
+    assert!(stderr.contains("b.jsx"), "{stderr}");
+    assert!(stderr.contains("1 files could not be changed."), "{stderr}");
+    for (file, text) in [("a.jsx", "Hello world"), ("c.jsx", "Goodbye")] {
+        let source = fs::read_to_string(dir.join(file)).unwrap();
+        assert!(
+            source.contains(&format!("<fbt desc=\"TODO: {text}\">{text}</fbt>")),
+            "{source}"
+        );
+    }
+    assert_eq!(
+        fs::read_to_string(dir.join("b.jsx")).unwrap(),
+        "const b = <p>Broken</;\n"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}